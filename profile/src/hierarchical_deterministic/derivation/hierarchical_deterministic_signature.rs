@@ -0,0 +1,102 @@
+use crate::prelude::*;
+use radix_engine_common::crypto::Hash;
+use transaction::signing::{
+    ed25519::Ed25519Signature, secp256k1::Secp256k1Signature,
+};
+
+/// A signature of some hash, produced by the private key derived at the
+/// derivation path of `public_key`, e.g. by a Ledger hardware wallet.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, uniffi::Record)]
+pub struct HierarchicalDeterministicSignature {
+    /// The public key of the private key which produced the signature,
+    /// and the derivation path used to derive it.
+    pub public_key: HierarchicalDeterministicPublicKey,
+
+    /// The bytes of the signature, 64 bytes for `Curve25519` and 65 bytes,
+    /// including the recovery byte, for `secp256k1`.
+    pub signature: BagOfBytes,
+}
+
+impl HierarchicalDeterministicSignature {
+    pub fn new(
+        public_key: HierarchicalDeterministicPublicKey,
+        signature: BagOfBytes,
+    ) -> Self {
+        Self {
+            public_key,
+            signature,
+        }
+    }
+
+    /// Verifies that `signature` is a valid signature of `hash` by
+    /// `public_key`, returns `false` if the signature is malformed.
+    pub fn is_valid_for(&self, hash: &Hash) -> bool {
+        let bytes = self.signature.bytes();
+        match &self.public_key.public_key {
+            PublicKey::Ed25519 { value } => Ed25519Signature::try_from(bytes)
+                .is_ok_and(|signature| value.is_valid(&signature, hash)),
+            PublicKey::Secp256k1 { value } => {
+                Secp256k1Signature::try_from(bytes)
+                    .is_ok_and(|signature| value.is_valid(&signature, hash))
+            }
+        }
+    }
+}
+
+impl HierarchicalDeterministicPrivateKey {
+    /// Signs `hash` with the private key, returning the signature together
+    /// with the public key and derivation path.
+    pub fn sign(&self, hash: &Hash) -> HierarchicalDeterministicSignature {
+        let signature = match &self.private_key {
+            PrivateKey::Ed25519(key) => key.sign(hash).0.to_vec(),
+            PrivateKey::Secp256k1(key) => key.sign(hash).0.to_vec(),
+        };
+        HierarchicalDeterministicSignature::new(
+            self.public_key(),
+            signature.into(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn sign_and_verify_ed25519() {
+        let msg = hash("Test");
+        let sut = HierarchicalDeterministicPrivateKey::placeholder().sign(&msg);
+        assert_eq!(sut.signature.len(), 64);
+        assert!(sut.is_valid_for(&msg));
+        assert!(!sut.is_valid_for(&hash("Other")));
+    }
+
+    #[test]
+    fn sign_and_verify_secp256k1() {
+        let msg = hash("Test");
+        let sut =
+            HierarchicalDeterministicPrivateKey::placeholder_other().sign(&msg);
+        assert_eq!(sut.signature.len(), 65);
+        assert!(sut.is_valid_for(&msg));
+        assert!(!sut.is_valid_for(&hash("Other")));
+    }
+
+    #[test]
+    fn malformed_signature_is_invalid() {
+        let msg = hash("Test");
+        let mut sut =
+            HierarchicalDeterministicPrivateKey::placeholder().sign(&msg);
+        sut.signature = BagOfBytes::from(vec![0xde, 0xad]);
+        assert!(!sut.is_valid_for(&msg));
+    }
+
+    #[test]
+    fn signature_by_other_key_is_invalid() {
+        let msg = hash("Test");
+        let mut sut =
+            HierarchicalDeterministicPrivateKey::placeholder().sign(&msg);
+        sut.public_key =
+            HierarchicalDeterministicPublicKey::placeholder_other();
+        assert!(!sut.is_valid_for(&msg));
+    }
+}
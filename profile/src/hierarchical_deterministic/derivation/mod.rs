@@ -3,6 +3,7 @@ mod derivation_path;
 mod derivation_path_scheme;
 mod hierarchical_deterministic_private_key;
 mod hierarchical_deterministic_public_key;
+mod hierarchical_deterministic_signature;
 mod mnemonic_with_passphrase;
 
 pub use derivation::*;
@@ -10,4 +11,5 @@ pub use derivation_path::*;
 pub use derivation_path_scheme::*;
 pub use hierarchical_deterministic_private_key::*;
 pub use hierarchical_deterministic_public_key::*;
+pub use hierarchical_deterministic_signature::*;
 pub use mnemonic_with_passphrase::*;
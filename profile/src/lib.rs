@@ -3,6 +3,7 @@
 mod hierarchical_deterministic;
 mod identified_vec_via;
 mod logic;
+mod migration;
 mod profilesnapshot_version;
 mod v100;
 mod wallet;
@@ -13,12 +14,13 @@ pub mod prelude {
     pub use crate::hierarchical_deterministic::*;
     pub use crate::identified_vec_via::*;
     pub use crate::logic::*;
+    pub use crate::migration::*;
     pub use crate::profilesnapshot_version::*;
     pub use crate::v100::*;
     pub use crate::wallet::*;
     pub use crate::wallet_kit_common::*;
 
-    pub(crate) use std::collections::{BTreeSet, HashMap, HashSet};
+    pub(crate) use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 
     pub(crate) use ::identified_vec::{
         Identifiable, IdentifiedVec, IdentifiedVecOf, IsIdentifiedVec,
@@ -1,5 +1,15 @@
 #![allow(unused_imports)]
 
+mod profile_authorized_dapp_cascade;
+mod profile_diff;
+mod profile_factor_source_removal;
+mod profile_merge;
 mod profile_next_derivation;
+mod profile_validation;
 
+pub use profile_authorized_dapp_cascade::*;
+pub use profile_diff::*;
+pub use profile_factor_source_removal::*;
+pub use profile_merge::*;
 pub use profile_next_derivation::*;
+pub use profile_validation::*;
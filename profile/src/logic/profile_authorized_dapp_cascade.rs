@@ -0,0 +1,245 @@
+use crate::prelude::*;
+
+impl Profile {
+    /// Flags the Persona with `address` as `DeletedByUser` and removes every
+    /// reference to it from the AuthorizedDapps on its network, removing the
+    /// Dapps left without any authorized Personas.
+    ///
+    /// Returns `Err` if the persona is unknown.
+    pub fn delete_persona(
+        &mut self,
+        address: &IdentityAddress,
+    ) -> Result<Vec<AuthorizedDappCascade>> {
+        self.update_persona(address, |p| {
+            _ = p.flags.insert_flag(EntityFlag::DeletedByUser)
+        })
+        .ok_or(CommonError::UnknownPersona)?;
+
+        Ok(self.cascade_authorized_dapps_on(address.network_id, |d| {
+            d.remove_persona_reference(address)
+        }))
+    }
+
+    /// Flags the Account with `address` as `DeletedByUser` and removes it
+    /// from the Accounts shared with the AuthorizedDapps on its network.
+    ///
+    /// Returns `Err` if the account is unknown.
+    pub fn delete_account(
+        &mut self,
+        address: &AccountAddress,
+    ) -> Result<Vec<AuthorizedDappCascade>> {
+        self.update_account(address, |a| {
+            _ = a.flags.insert_flag(EntityFlag::DeletedByUser)
+        })
+        .ok_or(CommonError::UnknownAccount)?;
+
+        Ok(self.cascade_authorized_dapps_on(address.network_id, |d| {
+            d.remove_shared_account(address)
+        }))
+    }
+
+    /// Removes the PersonaData entry with `id` of the Persona with `address`
+    /// and removes it from the entries the Persona shares with the
+    /// AuthorizedDapps on its network.
+    ///
+    /// Returns `Err` if the persona is unknown or if it has no entry with `id`.
+    pub fn remove_persona_data_entry(
+        &mut self,
+        address: &IdentityAddress,
+        id: &PersonaDataEntryID,
+    ) -> Result<Vec<AuthorizedDappCascade>> {
+        let mut persona = self
+            .networks
+            .get_persona(address)
+            .ok_or(CommonError::UnknownPersona)?;
+        persona.persona_data.remove_entry(id)?;
+        self.update_persona(address, |p| *p = persona.clone());
+
+        Ok(self.cascade_authorized_dapps_on(address.network_id, |d| {
+            d.remove_shared_persona_data_entry(address, id)
+        }))
+    }
+
+    fn cascade_authorized_dapps_on<F>(
+        &mut self,
+        network_id: NetworkID,
+        mut cascade: F,
+    ) -> Vec<AuthorizedDappCascade>
+    where
+        F: FnMut(&mut AuthorizedDapp) -> Vec<AuthorizedDappCascade>,
+    {
+        let mut cascades = Vec::new();
+        self.networks.update_with(&network_id, |n| {
+            cascades = n.cascade_authorized_dapps(&mut cascade)
+        });
+        cascades
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[allow(clippy::upper_case_acronyms)]
+    type SUT = Profile;
+
+    fn mainnet_dapps(sut: &SUT) -> AuthorizedDapps {
+        sut.networks
+            .get(&NetworkID::Mainnet)
+            .unwrap()
+            .authorized_dapps
+            .clone()
+    }
+
+    #[test]
+    fn delete_persona_flags_it_and_removes_references() {
+        let mut sut = SUT::placeholder();
+        let batman = Persona::placeholder_mainnet_batman().address;
+        let cascades = sut.delete_persona(&batman).unwrap();
+
+        assert!(sut
+            .networks
+            .get_persona(&batman)
+            .unwrap()
+            .flags
+            .contains_id(&EntityFlag::DeletedByUser));
+
+        let gumball = AuthorizedDapp::placeholder_mainnet_gumballclub();
+        assert!(cascades.contains(
+            &AuthorizedDappCascade::AuthorizedDappRemoved {
+                dapp_definition_address: gumball
+                    .dapp_definition_address
+                    .clone(),
+            }
+        ));
+        let dapps = mainnet_dapps(&sut);
+        assert!(!dapps.contains_id(&gumball.dapp_definition_address));
+        assert!(dapps.iter().all(|d| !d
+            .references_to_authorized_personas
+            .contains_id(&batman)));
+
+        // Other networks are untouched.
+        assert_eq!(
+            sut.networks.get(&NetworkID::Stokenet),
+            SUT::placeholder().networks.get(&NetworkID::Stokenet)
+        );
+    }
+
+    #[test]
+    fn delete_unknown_persona_fails() {
+        let mut sut = SUT::placeholder();
+        let satoshi = Persona::placeholder_mainnet_satoshi().address;
+        sut.networks.update_with(&NetworkID::Mainnet, |n| {
+            _ = n.personas.remove_by_id(&satoshi)
+        });
+        let before = sut.clone();
+        assert_eq!(
+            sut.delete_persona(&satoshi),
+            Err(CommonError::UnknownPersona)
+        );
+        assert_eq!(sut, before);
+    }
+
+    #[test]
+    fn delete_account_removes_it_from_shared_accounts() {
+        let mut sut = SUT::placeholder();
+        let alice = Account::placeholder_mainnet_alice().address;
+        // Share Alice with the Dashboard by Satoshi
+        let dashboard = AuthorizedDapp::placeholder_mainnet_dashboard();
+        sut.networks.update_with(&NetworkID::Mainnet, |n| {
+            n.authorized_dapps.update_with(
+                &dashboard.dapp_definition_address,
+                |d| {
+                    d.references_to_authorized_personas.update_with(
+                        &IdentityAddress::placeholder_mainnet(),
+                        |p| {
+                            p.shared_accounts = Some(
+                                SharedToDappWithPersonaAccountAddresses::new(
+                                    RequestedQuantity::exactly(1),
+                                    IdentifiedVecVia::from_iter(
+                                        [alice.clone()],
+                                    ),
+                                ),
+                            )
+                        },
+                    );
+                },
+            );
+        });
+
+        let cascades = sut.delete_account(&alice).unwrap();
+        assert!(sut
+            .networks
+            .get_account(&alice)
+            .unwrap()
+            .flags
+            .contains_id(&EntityFlag::DeletedByUser));
+        assert_eq!(
+            cascades,
+            vec![
+                AuthorizedDappCascade::SharedAccountRemoved {
+                    dapp_definition_address: dashboard
+                        .dapp_definition_address
+                        .clone(),
+                    identity_address: IdentityAddress::placeholder_mainnet(),
+                    account_address: alice.clone(),
+                },
+                AuthorizedDappCascade::RequestedQuantityDowngraded {
+                    dapp_definition_address: dashboard
+                        .dapp_definition_address
+                        .clone(),
+                    identity_address: IdentityAddress::placeholder_mainnet(),
+                    from: RequestedQuantity::exactly(1),
+                    to: RequestedQuantity::at_least(0),
+                },
+            ]
+        );
+        // The Dapp still has its Personas, so it is kept.
+        assert!(
+            mainnet_dapps(&sut).contains_id(&dashboard.dapp_definition_address)
+        );
+    }
+
+    #[test]
+    fn delete_unknown_account_fails() {
+        let mut sut = SUT::placeholder();
+        assert_eq!(
+            sut.delete_account(&AccountAddress::placeholder_mainnet()),
+            Err(CommonError::UnknownAccount)
+        );
+    }
+
+    #[test]
+    fn remove_persona_data_entry_removes_it_from_shared_persona_data() {
+        let mut sut = SUT::placeholder();
+        let satoshi = Persona::placeholder_mainnet_satoshi();
+        let name_id = satoshi.persona_data.name.clone().unwrap().id;
+
+        let cascades = sut
+            .remove_persona_data_entry(&satoshi.address, &name_id)
+            .unwrap();
+
+        assert_eq!(
+            sut.networks
+                .get_persona(&satoshi.address)
+                .unwrap()
+                .persona_data
+                .name,
+            None
+        );
+        assert_eq!(
+            cascades,
+            vec![AuthorizedDappCascade::SharedPersonaDataEntryRemoved {
+                dapp_definition_address:
+                    AuthorizedDapp::placeholder_mainnet_dashboard()
+                        .dapp_definition_address,
+                identity_address: satoshi.address.clone(),
+                entry_id: name_id.clone(),
+            }]
+        );
+        assert_eq!(
+            sut.remove_persona_data_entry(&satoshi.address, &name_id),
+            Err(CommonError::UnknownPersonaDataEntry(name_id))
+        );
+    }
+}
@@ -0,0 +1,263 @@
+use crate::prelude::*;
+
+// We have to use macros since UniFFI does not support generics, that is the only
+// reason this macro exists, if/when UniFFI supports generics, this macro should
+// be replaced by `IdentifiedVecDiff<T>`.
+macro_rules! declare_identified_diff {
+    ($element:ty,$struct_name:ident) => {
+        /// The difference between two collections of identifiable elements,
+        /// where elements are matched by their ID rather than their position.
+        #[derive(Clone, Debug, Default, PartialEq, Eq, uniffi::Record)]
+        pub struct $struct_name {
+            /// Elements with an ID not present in `from` but present in `to`.
+            pub added: Vec<$element>,
+
+            /// Elements with an ID present in `from` but not in `to`, as they
+            /// were in `from`.
+            pub removed: Vec<$element>,
+
+            /// Elements with an ID present in both `from` and `to` but which
+            /// are not equal, as they are in `to`.
+            pub modified: Vec<$element>,
+        }
+
+        impl $struct_name {
+            /// The difference going from `from` to `to`, in the order of the
+            /// elements in `to`, apart from `removed` which is in the order
+            /// of `from`.
+            pub fn new(
+                from: &IdentifiedVecVia<$element>,
+                to: &IdentifiedVecVia<$element>,
+            ) -> Self {
+                let mut diff = Self::default();
+                for element in to.iter() {
+                    match from.get(&element.id()) {
+                        None => diff.added.push(element.clone()),
+                        Some(old) if old != element => {
+                            diff.modified.push(element.clone())
+                        }
+                        Some(_) => {}
+                    }
+                }
+                diff.removed = from
+                    .iter()
+                    .filter(|e| !to.contains_id(&e.id()))
+                    .cloned()
+                    .collect();
+                diff
+            }
+
+            /// `true` if the two collections contain equal elements.
+            pub fn is_empty(&self) -> bool {
+                self.added.is_empty()
+                    && self.removed.is_empty()
+                    && self.modified.is_empty()
+            }
+        }
+    };
+}
+
+declare_identified_diff!(Account, AccountsDiff);
+declare_identified_diff!(Persona, PersonasDiff);
+declare_identified_diff!(AuthorizedDapp, AuthorizedDappsDiff);
+declare_identified_diff!(FactorSource, FactorSourcesDiff);
+declare_identified_diff!(Gateway, GatewaysDiff);
+declare_identified_diff!(P2PLink, P2PLinksDiff);
+
+/// A structural difference between two Profiles, per collection of entities
+/// or values identified by an ID, e.g. Accounts by their address.
+///
+/// Accounts, Personas and AuthorizedDapps of all networks are compared
+/// together, since their IDs are addresses which are unique across networks.
+#[derive(Clone, Debug, Default, PartialEq, Eq, uniffi::Record)]
+pub struct ProfileDiff {
+    pub accounts: AccountsDiff,
+    pub personas: PersonasDiff,
+    pub authorized_dapps: AuthorizedDappsDiff,
+    pub factor_sources: FactorSourcesDiff,
+
+    /// Difference of all saved gateways, including the current one.
+    pub gateways: GatewaysDiff,
+    pub p2p_links: P2PLinksDiff,
+}
+
+impl ProfileDiff {
+    /// `true` if none of the compared collections differ.
+    pub fn is_empty(&self) -> bool {
+        self.accounts.is_empty()
+            && self.personas.is_empty()
+            && self.authorized_dapps.is_empty()
+            && self.factor_sources.is_empty()
+            && self.gateways.is_empty()
+            && self.p2p_links.is_empty()
+    }
+}
+
+/// Returns the structural difference going from the Profile `from` to the
+/// Profile `to`.
+#[uniffi::export]
+pub fn profile_diff(from: &Profile, to: &Profile) -> ProfileDiff {
+    from.diff(to)
+}
+
+impl Profile {
+    /// Returns the structural difference going from this Profile to `to`.
+    pub fn diff(&self, to: &Profile) -> ProfileDiff {
+        ProfileDiff {
+            accounts: AccountsDiff::new(
+                &self.all_accounts(),
+                &to.all_accounts(),
+            ),
+            personas: PersonasDiff::new(
+                &self.all_personas(),
+                &to.all_personas(),
+            ),
+            authorized_dapps: AuthorizedDappsDiff::new(
+                &self.all_authorized_dapps(),
+                &to.all_authorized_dapps(),
+            ),
+            factor_sources: FactorSourcesDiff::new(
+                &self.factor_sources,
+                &to.factor_sources,
+            ),
+            gateways: GatewaysDiff::new(
+                &self.all_gateways(),
+                &to.all_gateways(),
+            ),
+            p2p_links: P2PLinksDiff::new(
+                &self.app_preferences.p2p_links,
+                &to.app_preferences.p2p_links,
+            ),
+        }
+    }
+
+    fn all_accounts(&self) -> Accounts {
+        self.networks
+            .iter()
+            .flat_map(|n| n.accounts.items())
+            .collect()
+    }
+
+    fn all_personas(&self) -> Personas {
+        self.networks
+            .iter()
+            .flat_map(|n| n.personas.items())
+            .collect()
+    }
+
+    fn all_authorized_dapps(&self) -> AuthorizedDapps {
+        self.networks
+            .iter()
+            .flat_map(|n| n.authorized_dapps.items())
+            .collect()
+    }
+
+    fn all_gateways(&self) -> IdentifiedVecVia<Gateway> {
+        self.app_preferences.gateways.all().into_iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn diff_of_equal_profiles_is_empty() {
+        let sut = Profile::placeholder();
+        assert!(sut.diff(&sut.clone()).is_empty());
+        assert_eq!(sut.diff(&sut.clone()), ProfileDiff::default());
+    }
+
+    #[test]
+    fn account_added_removed_and_modified() {
+        let from = Profile::placeholder();
+        let mut to = from.clone();
+        let alice = Account::placeholder_mainnet_alice();
+        let bob = Account::placeholder_mainnet_bob();
+        let carol = Account::placeholder_mainnet_carol();
+        to.networks.update_with(&NetworkID::Mainnet, |n| {
+            n.accounts.remove_by_id(&bob.address);
+            n.accounts.append(carol.clone());
+            n.accounts.update_with(&alice.address, |a| {
+                a.display_name = DisplayName::new("Stella").unwrap()
+            });
+        });
+        let diff = from.diff(&to);
+        let mut renamed_alice = alice.clone();
+        renamed_alice.display_name = DisplayName::new("Stella").unwrap();
+        assert_eq!(
+            diff.accounts,
+            AccountsDiff {
+                added: vec![carol],
+                removed: vec![bob],
+                modified: vec![renamed_alice],
+            }
+        );
+        assert!(diff.personas.is_empty());
+        assert!(!diff.is_empty());
+    }
+
+    #[test]
+    fn persona_removed() {
+        let from = Profile::placeholder();
+        let mut to = from.clone();
+        let satoshi = Persona::placeholder_mainnet_satoshi();
+        to.networks.update_with(&NetworkID::Mainnet, |n| {
+            n.personas.remove_by_id(&satoshi.address);
+        });
+        assert_eq!(
+            from.diff(&to).personas,
+            PersonasDiff {
+                removed: vec![satoshi],
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn factor_source_added() {
+        let from = Profile::placeholder();
+        let mut to = from.clone();
+        let olympia = FactorSource::placeholder_device_olympia();
+        to.factor_sources.append(olympia.clone());
+        assert_eq!(
+            from.diff(&to).factor_sources,
+            FactorSourcesDiff {
+                added: vec![olympia],
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn gateways_and_p2p_links() {
+        let from = Profile::placeholder();
+        let mut to = from.clone();
+        to.app_preferences.gateways = Gateways::new(Gateway::mainnet());
+        to.app_preferences.p2p_links = P2PLinks::placeholder_other();
+        let diff = from.diff(&to);
+        assert_eq!(
+            diff.gateways,
+            GatewaysDiff {
+                removed: vec![Gateway::rcnet(), Gateway::stokenet()],
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            diff.p2p_links,
+            P2PLinksDiff {
+                added: P2PLinks::placeholder_other().items(),
+                removed: P2PLinks::placeholder().items(),
+                modified: Vec::new(),
+            }
+        );
+    }
+
+    #[test]
+    fn profile_diff_global_function() {
+        assert!(
+            profile_diff(&Profile::placeholder(), &Profile::placeholder())
+                .is_empty()
+        );
+    }
+}
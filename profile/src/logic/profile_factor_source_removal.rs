@@ -0,0 +1,292 @@
+use crate::prelude::*;
+
+impl Profile {
+    /// The addresses of all Accounts and Personas, on any network, with a
+    /// factor instance - for transaction signing or authentication signing -
+    /// from the FactorSource with `id`.
+    pub fn entities_controlled_by_factor_source(
+        &self,
+        id: &FactorSourceID,
+    ) -> Vec<AddressOfAccountOrPersona> {
+        let is_controlled_by = |security_state: &EntitySecurityState| {
+            let EntitySecurityState::Unsecured { value: control } =
+                security_state;
+            std::iter::once(&control.transaction_signing)
+                .chain(control.authentication_signing.iter())
+                .any(|i| {
+                    FactorSourceID::from(i.factor_source_id.clone()) == *id
+                })
+        };
+        self.networks
+            .iter()
+            .flat_map(|n| {
+                n.accounts
+                    .iter()
+                    .filter(|a| is_controlled_by(&a.security_state))
+                    .map(|a| a.address.clone().into())
+                    .chain(
+                        n.personas
+                            .iter()
+                            .filter(|p| is_controlled_by(&p.security_state))
+                            .map(|p| p.address.clone().into()),
+                    )
+                    .collect_vec()
+            })
+            .collect()
+    }
+
+    /// Returns `Ok` if the FactorSource with `id` can be removed, which is if
+    /// it exists, does not control any Account or Persona, and is not the
+    /// only Babylon `DeviceFactorSource` not deleted by the user.
+    pub fn ensure_factor_source_can_be_removed(
+        &self,
+        id: &FactorSourceID,
+    ) -> Result<()> {
+        let factor_source = self.factor_sources.get(id).ok_or_else(|| {
+            CommonError::ProfileDoesNotContainFactorSourceWithID(id.clone())
+        })?;
+
+        if !self.entities_controlled_by_factor_source(id).is_empty() {
+            return Err(
+                CommonError::UnableToRemoveFactorSourceControllingEntities(
+                    id.clone(),
+                ),
+            );
+        }
+
+        let is_babylon_device = factor_source
+            .as_device()
+            .map(|d| d.common.supports_babylon())
+            .unwrap_or(false);
+        if is_babylon_device
+            && self.other_babylon_device_factor_source(id).is_none()
+        {
+            return Err(
+                CommonError::UnableToRemoveOnlyBabylonDeviceFactorSource(
+                    id.clone(),
+                ),
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Removes the FactorSource with `id` from this Profile, if allowed by
+    /// `ensure_factor_source_can_be_removed`, returning the removed
+    /// FactorSource.
+    ///
+    /// If the removed FactorSource was the main Babylon `DeviceFactorSource`
+    /// another Babylon `DeviceFactorSource` is made the main one.
+    pub fn remove_factor_source(
+        &mut self,
+        id: &FactorSourceID,
+    ) -> Result<FactorSource> {
+        self.ensure_factor_source_can_be_removed(id)?;
+        self.transfer_main_flag_from(id);
+        self.factor_sources.remove_by_id(id).ok_or_else(|| {
+            CommonError::ProfileDoesNotContainFactorSourceWithID(id.clone())
+        })
+    }
+
+    /// Flags the FactorSource with `id` as `DeletedByUser`, if allowed by
+    /// `ensure_factor_source_can_be_removed`, keeping it in this Profile.
+    ///
+    /// If the FactorSource was the main Babylon `DeviceFactorSource`
+    /// another Babylon `DeviceFactorSource` is made the main one.
+    pub fn soft_delete_factor_source(
+        &mut self,
+        id: &FactorSourceID,
+    ) -> Result<()> {
+        self.ensure_factor_source_can_be_removed(id)?;
+        self.transfer_main_flag_from(id);
+        self.factor_sources.update_with(id, |f| {
+            _ = f
+                .common_properties_mut()
+                .flags
+                .append(FactorSourceFlag::DeletedByUser);
+        });
+        Ok(())
+    }
+
+    /// A Babylon `DeviceFactorSource` other than the one with `id`, which
+    /// has not been deleted by the user.
+    fn other_babylon_device_factor_source(
+        &self,
+        id: &FactorSourceID,
+    ) -> Option<DeviceFactorSource> {
+        self.factor_sources
+            .iter()
+            .filter(|f| f.factor_source_id() != *id)
+            .filter_map(|f| f.as_device().cloned())
+            .find(|d| {
+                d.common.supports_babylon()
+                    && !d
+                        .common
+                        .flags
+                        .contains_id(&FactorSourceFlag::DeletedByUser)
+            })
+    }
+
+    /// If the FactorSource with `id` is flagged as `Main`, moves that flag to
+    /// another Babylon `DeviceFactorSource`.
+    fn transfer_main_flag_from(&mut self, id: &FactorSourceID) {
+        let is_main = self
+            .factor_sources
+            .get(id)
+            .map(|f| {
+                f.common_properties()
+                    .flags
+                    .contains_id(&FactorSourceFlag::Main)
+            })
+            .unwrap_or(false);
+        if !is_main {
+            return;
+        }
+        self.factor_sources.update_with(id, |f| {
+            _ = f
+                .common_properties_mut()
+                .flags
+                .remove_by_id(&FactorSourceFlag::Main);
+        });
+        if let Some(other) = self.other_babylon_device_factor_source(id) {
+            self.factor_sources
+                .update_with(&other.factor_source_id(), |f| {
+                    _ = f
+                        .common_properties_mut()
+                        .flags
+                        .append(FactorSourceFlag::Main);
+                });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    fn device_id() -> FactorSourceID {
+        FactorSource::placeholder_device().factor_source_id()
+    }
+
+    /// A Profile without entities with two Babylon DeviceFactorSources, the
+    /// first one being main.
+    fn profile_with_two_babylon_device_factor_sources(
+    ) -> (Profile, FactorSourceID, FactorSourceID) {
+        let main = PrivateHierarchicalDeterministicFactorSource::generate_new(
+            WalletClientModel::Unknown,
+        );
+        let mut sut = Profile::new(main.clone(), "Test");
+        let other = DeviceFactorSource::babylon(
+            false,
+            MnemonicWithPassphrase::placeholder(),
+            WalletClientModel::Unknown,
+        );
+        sut.factor_sources.append(other.clone().into());
+        (
+            sut,
+            main.factor_source.factor_source_id(),
+            other.factor_source_id(),
+        )
+    }
+
+    #[test]
+    fn entities_controlled_by_factor_source() {
+        let sut = Profile::placeholder();
+        assert_eq!(
+            sut.entities_controlled_by_factor_source(&device_id()).len(),
+            8
+        );
+        assert!(sut
+            .entities_controlled_by_factor_source(
+                &FactorSource::placeholder_ledger().factor_source_id()
+            )
+            .is_empty());
+    }
+
+    #[test]
+    fn remove_unused_ledger() {
+        let mut sut = Profile::placeholder();
+        let ledger = FactorSource::placeholder_ledger();
+        assert_eq!(
+            sut.remove_factor_source(&ledger.factor_source_id()),
+            Ok(ledger.clone())
+        );
+        assert!(!sut.factor_sources.contains_id(&ledger.factor_source_id()));
+    }
+
+    #[test]
+    fn remove_unknown_fails() {
+        let mut sut = Profile::placeholder();
+        let id = FactorSource::placeholder_device_olympia().factor_source_id();
+        assert_eq!(
+            sut.remove_factor_source(&id),
+            Err(CommonError::ProfileDoesNotContainFactorSourceWithID(id))
+        );
+    }
+
+    #[test]
+    fn remove_controlling_entities_fails() {
+        let mut sut = Profile::placeholder();
+        assert_eq!(
+            sut.remove_factor_source(&device_id()),
+            Err(CommonError::UnableToRemoveFactorSourceControllingEntities(
+                device_id()
+            ))
+        );
+        assert_eq!(
+            sut.soft_delete_factor_source(&device_id()),
+            Err(CommonError::UnableToRemoveFactorSourceControllingEntities(
+                device_id()
+            ))
+        );
+        assert_eq!(sut, Profile::placeholder());
+    }
+
+    #[test]
+    fn remove_only_babylon_device_factor_source_fails() {
+        let private =
+            PrivateHierarchicalDeterministicFactorSource::placeholder();
+        let mut sut = Profile::new(private.clone(), "Test");
+        let id = private.factor_source.factor_source_id();
+        assert_eq!(
+            sut.remove_factor_source(&id),
+            Err(CommonError::UnableToRemoveOnlyBabylonDeviceFactorSource(id))
+        );
+    }
+
+    #[test]
+    fn remove_main_transfers_main_flag() {
+        let (mut sut, main, other) =
+            profile_with_two_babylon_device_factor_sources();
+        assert!(sut.remove_factor_source(&main).is_ok());
+        assert_eq!(sut.bdfs().factor_source_id(), other);
+        assert!(sut.bdfs().is_main_bdfs());
+    }
+
+    #[test]
+    fn soft_delete_flags_and_transfers_main_flag() {
+        let (mut sut, main, other) =
+            profile_with_two_babylon_device_factor_sources();
+        assert!(sut.soft_delete_factor_source(&main).is_ok());
+        let flags = sut
+            .factor_sources
+            .get(&main)
+            .unwrap()
+            .common_properties()
+            .flags
+            .clone();
+        assert_eq!(
+            flags,
+            FactorSourceFlags::from_iter([FactorSourceFlag::DeletedByUser])
+        );
+        assert_eq!(sut.bdfs().factor_source_id(), other);
+
+        // `other` is now the only Babylon DeviceFactorSource not deleted.
+        assert_eq!(
+            sut.soft_delete_factor_source(&other),
+            Err(CommonError::UnableToRemoveOnlyBabylonDeviceFactorSource(
+                other
+            ))
+        );
+    }
+}
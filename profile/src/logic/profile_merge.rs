@@ -0,0 +1,656 @@
+use crate::prelude::*;
+
+/// A change made to the same element, or value, by both sides of a three-way
+/// merge of Profiles, which could not be resolved automatically.
+///
+/// For each conflict the version of `ours` is kept in the merged Profile, or
+/// if `ours` has removed an element which `theirs` has modified, the version
+/// of `theirs` is kept, so that no element is lost. Wallet Clients can use
+/// `base`, `ours` and `theirs` to let the user pick another version.
+#[derive(Clone, Debug, PartialEq, Eq, uniffi::Enum)]
+pub enum ProfileMergeConflict {
+    /// Both sides changed the same Account, `None` if removed or not yet added.
+    Accounts {
+        base: Option<Account>,
+        ours: Option<Account>,
+        theirs: Option<Account>,
+    },
+
+    /// Both sides changed the same Persona, `None` if removed or not yet added.
+    Personas {
+        base: Option<Persona>,
+        ours: Option<Persona>,
+        theirs: Option<Persona>,
+    },
+
+    /// Both sides changed the same AuthorizedDapp, `None` if removed or not yet
+    /// added.
+    AuthorizedDapps {
+        base: Option<AuthorizedDapp>,
+        ours: Option<AuthorizedDapp>,
+        theirs: Option<AuthorizedDapp>,
+    },
+
+    /// Both sides changed the same FactorSource, `None` if removed or not yet
+    /// added.
+    FactorSources {
+        base: Option<FactorSource>,
+        ours: Option<FactorSource>,
+        theirs: Option<FactorSource>,
+    },
+
+    /// Both sides changed the same saved Gateway, `None` if removed or not yet
+    /// added.
+    SavedGateways {
+        base: Option<Gateway>,
+        ours: Option<Gateway>,
+        theirs: Option<Gateway>,
+    },
+
+    /// Both sides changed the same P2PLink, `None` if removed or not yet added.
+    P2PLinks {
+        base: Option<P2PLink>,
+        ours: Option<P2PLink>,
+        theirs: Option<P2PLink>,
+    },
+
+    /// Both sides switched to different current Gateways.
+    CurrentGateway {
+        base: Gateway,
+        ours: Gateway,
+        theirs: Gateway,
+    },
+
+    /// Both sides changed the `AppDisplay` settings differently.
+    AppDisplaySettings {
+        base: AppDisplay,
+        ours: AppDisplay,
+        theirs: AppDisplay,
+    },
+
+    /// Both sides changed the `Security` settings differently.
+    SecuritySettings {
+        base: Security,
+        ours: Security,
+        theirs: Security,
+    },
+
+    /// Both sides changed the `TransactionPreferences` differently.
+    TransactionSettings {
+        base: TransactionPreferences,
+        ours: TransactionPreferences,
+        theirs: TransactionPreferences,
+    },
+
+    /// The merged Profile violates an invariant which neither `ours` nor
+    /// `theirs` violate, e.g. `theirs` changed a Dapp authorized with a
+    /// Persona which `ours` has removed.
+    InvalidMergedProfile { violation: ProfileViolation },
+}
+
+/// The result of a three-way merge of Profiles, see `Profile::merge`.
+#[derive(Clone, Debug, PartialEq, Eq, uniffi::Record)]
+pub struct ProfileMerge {
+    /// The merged Profile, containing the changes of both sides which did not
+    /// conflict, and for each conflict the version described in
+    /// `ProfileMergeConflict`.
+    pub merged: Profile,
+
+    /// The conflicts which could not be resolved automatically, empty if
+    /// the changes of both sides could be merged.
+    pub conflicts: Vec<ProfileMergeConflict>,
+}
+
+/// Merges the changes made by `ours` and by `theirs` to their common
+/// ancestor `base`, see `Profile::merge`.
+#[uniffi::export]
+pub fn profile_three_way_merge(
+    base: &Profile,
+    ours: &Profile,
+    theirs: &Profile,
+) -> Result<ProfileMerge> {
+    Profile::merge(base, ours, theirs)
+}
+
+impl Profile {
+    /// Merges the changes made by `ours` and by `theirs` to their common
+    /// ancestor `base`, e.g. when two devices have edited the same Profile.
+    ///
+    /// Entities, FactorSources, Gateways and P2PLinks are matched by their
+    /// ID, an element changed by only one side gets the version of that side,
+    /// the same holds for additions and removals. AppDisplay, Security and
+    /// TransactionPreferences are merged as whole values.
+    ///
+    /// The merged Profile is validated, see `Profile::validate`, and every
+    /// violation not already present in `ours` or `theirs` is reported as a
+    /// `ProfileMergeConflict::InvalidMergedProfile`.
+    ///
+    /// Returns `Err` if the Profiles do not all have the same ID.
+    pub fn merge(
+        base: &Profile,
+        ours: &Profile,
+        theirs: &Profile,
+    ) -> Result<ProfileMerge> {
+        if ours.id() != base.id() || theirs.id() != base.id() {
+            return Err(CommonError::UnableToMergeProfilesWithDifferentIDs);
+        }
+
+        let mut conflicts = Vec::new();
+
+        let networks = merge_networks(
+            &base.networks,
+            &ours.networks,
+            &theirs.networks,
+            &mut conflicts,
+        );
+
+        let factor_sources = merge_identified(
+            &base.factor_sources,
+            &ours.factor_sources,
+            &theirs.factor_sources,
+            |base, ours, theirs| ProfileMergeConflict::FactorSources {
+                base,
+                ours,
+                theirs,
+            },
+            &mut conflicts,
+        );
+
+        let app_preferences = merge_app_preferences(
+            &base.app_preferences,
+            &ours.app_preferences,
+            &theirs.app_preferences,
+            &mut conflicts,
+        );
+
+        let mut header = ours.header.clone();
+        header.last_modified =
+            ours.header.last_modified.max(theirs.header.last_modified);
+        header.content_hint = networks.content_hint();
+
+        let merged =
+            Profile::with(header, factor_sources, app_preferences, networks);
+
+        let existing_violations = ours
+            .validate()
+            .into_iter()
+            .chain(theirs.validate())
+            .collect::<HashSet<_>>();
+        conflicts.extend(
+            merged
+                .validate()
+                .into_iter()
+                .filter(|v| !existing_violations.contains(v))
+                .map(|violation| ProfileMergeConflict::InvalidMergedProfile {
+                    violation,
+                }),
+        );
+
+        Ok(ProfileMerge { merged, conflicts })
+    }
+}
+
+/// Returns the three-way merge of `ours` and `theirs`, or `None` if both have
+/// changed `base`, differently.
+fn merge_value<T: PartialEq + Clone>(
+    base: &T,
+    ours: &T,
+    theirs: &T,
+) -> Option<T> {
+    if ours == theirs || theirs == base {
+        Some(ours.clone())
+    } else if ours == base {
+        Some(theirs.clone())
+    } else {
+        None
+    }
+}
+
+fn merge_value_or_conflict<T: PartialEq + Clone>(
+    base: &T,
+    ours: &T,
+    theirs: &T,
+    conflict: impl FnOnce(T, T, T) -> ProfileMergeConflict,
+    conflicts: &mut Vec<ProfileMergeConflict>,
+) -> T {
+    merge_value(base, ours, theirs).unwrap_or_else(|| {
+        conflicts.push(conflict(base.clone(), ours.clone(), theirs.clone()));
+        ours.clone()
+    })
+}
+
+/// Merges the elements of `ours` and `theirs` by ID, elements of `ours`
+/// come first, in the order of `ours`, followed by elements only in
+/// `theirs`.
+fn merge_identified<E>(
+    base: &IdentifiedVecVia<E>,
+    ours: &IdentifiedVecVia<E>,
+    theirs: &IdentifiedVecVia<E>,
+    conflict: impl Fn(Option<E>, Option<E>, Option<E>) -> ProfileMergeConflict,
+    conflicts: &mut Vec<ProfileMergeConflict>,
+) -> IdentifiedVecVia<E>
+where
+    E: Identifiable + std::fmt::Debug + Clone + PartialEq,
+{
+    let ids = ours
+        .iter()
+        .chain(theirs.iter())
+        .map(|e| e.id())
+        .unique()
+        .collect_vec();
+
+    let mut merged = IdentifiedVecVia::new();
+    for id in ids {
+        let (b, o, t) = (base.get(&id), ours.get(&id), theirs.get(&id));
+        let element = merge_value(&b, &o, &t).unwrap_or_else(|| {
+            conflicts.push(conflict(b.cloned(), o.cloned(), t.cloned()));
+            o.or(t)
+        });
+        if let Some(element) = element {
+            merged.append(element.clone());
+        }
+    }
+    merged
+}
+
+fn merge_networks(
+    base: &ProfileNetworks,
+    ours: &ProfileNetworks,
+    theirs: &ProfileNetworks,
+    conflicts: &mut Vec<ProfileMergeConflict>,
+) -> ProfileNetworks {
+    let ids = ours
+        .iter()
+        .chain(theirs.iter())
+        .map(|n| n.id)
+        .unique()
+        .collect_vec();
+
+    let mut merged = ProfileNetworks::new();
+    for id in ids {
+        let network = |networks: &ProfileNetworks| {
+            networks.get(&id).cloned().unwrap_or_else(|| {
+                ProfileNetwork::new(
+                    id,
+                    Accounts::new(),
+                    Personas::new(),
+                    AuthorizedDapps::new(),
+                )
+            })
+        };
+        let (b, o, t) = (network(base), network(ours), network(theirs));
+        let accounts = merge_identified(
+            &b.accounts,
+            &o.accounts,
+            &t.accounts,
+            |base, ours, theirs| ProfileMergeConflict::Accounts {
+                base,
+                ours,
+                theirs,
+            },
+            conflicts,
+        );
+        let personas = merge_identified(
+            &b.personas,
+            &o.personas,
+            &t.personas,
+            |base, ours, theirs| ProfileMergeConflict::Personas {
+                base,
+                ours,
+                theirs,
+            },
+            conflicts,
+        );
+        let authorized_dapps = merge_identified(
+            &b.authorized_dapps,
+            &o.authorized_dapps,
+            &t.authorized_dapps,
+            |base, ours, theirs| ProfileMergeConflict::AuthorizedDapps {
+                base,
+                ours,
+                theirs,
+            },
+            conflicts,
+        );
+        merged.append(ProfileNetwork::new(
+            id,
+            accounts,
+            personas,
+            authorized_dapps,
+        ));
+    }
+    merged
+}
+
+fn merge_app_preferences(
+    base: &AppPreferences,
+    ours: &AppPreferences,
+    theirs: &AppPreferences,
+    conflicts: &mut Vec<ProfileMergeConflict>,
+) -> AppPreferences {
+    let display = merge_value_or_conflict(
+        &base.display,
+        &ours.display,
+        &theirs.display,
+        |base, ours, theirs| ProfileMergeConflict::AppDisplaySettings {
+            base,
+            ours,
+            theirs,
+        },
+        conflicts,
+    );
+    let gateways = merge_gateways(
+        &base.gateways,
+        &ours.gateways,
+        &theirs.gateways,
+        conflicts,
+    );
+    let p2p_links = merge_identified(
+        &base.p2p_links,
+        &ours.p2p_links,
+        &theirs.p2p_links,
+        |base, ours, theirs| ProfileMergeConflict::P2PLinks {
+            base,
+            ours,
+            theirs,
+        },
+        conflicts,
+    );
+    let security = merge_value_or_conflict(
+        &base.security,
+        &ours.security,
+        &theirs.security,
+        |base, ours, theirs| ProfileMergeConflict::SecuritySettings {
+            base,
+            ours,
+            theirs,
+        },
+        conflicts,
+    );
+    let transaction = merge_value_or_conflict(
+        &base.transaction,
+        &ours.transaction,
+        &theirs.transaction,
+        |base, ours, theirs| ProfileMergeConflict::TransactionSettings {
+            base,
+            ours,
+            theirs,
+        },
+        conflicts,
+    );
+    AppPreferences::new(display, gateways, p2p_links, security, transaction)
+}
+
+/// Merges all saved Gateways by URL and the current Gateway as a value, the
+/// merged current Gateway is never part of the merged `other` Gateways.
+fn merge_gateways(
+    base: &Gateways,
+    ours: &Gateways,
+    theirs: &Gateways,
+    conflicts: &mut Vec<ProfileMergeConflict>,
+) -> Gateways {
+    let all = |gateways: &Gateways| {
+        gateways
+            .all()
+            .into_iter()
+            .collect::<IdentifiedVecVia<Gateway>>()
+    };
+    let saved = merge_identified(
+        &all(base),
+        &all(ours),
+        &all(theirs),
+        |base, ours, theirs| ProfileMergeConflict::SavedGateways {
+            base,
+            ours,
+            theirs,
+        },
+        conflicts,
+    );
+    let current = merge_value_or_conflict(
+        &base.current,
+        &ours.current,
+        &theirs.current,
+        |base, ours, theirs| ProfileMergeConflict::CurrentGateway {
+            base,
+            ours,
+            theirs,
+        },
+        conflicts,
+    );
+    let other = saved
+        .into_iter()
+        .filter(|g| g.id() != current.id())
+        .collect();
+    Gateways { current, other }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    fn rename_account(
+        profile: &mut Profile,
+        address: &AccountAddress,
+        name: &str,
+    ) {
+        profile
+            .update_account(address, |a| {
+                a.display_name = DisplayName::new(name).unwrap()
+            })
+            .unwrap();
+    }
+
+    fn account_name(profile: &Profile, address: &AccountAddress) -> String {
+        profile
+            .networks
+            .get_account(address)
+            .unwrap()
+            .display_name
+            .value
+    }
+
+    #[test]
+    fn merge_unchanged() {
+        let base = Profile::placeholder();
+        let sut = Profile::merge(&base, &base, &base).unwrap();
+        assert_eq!(sut.merged, base);
+        assert!(sut.conflicts.is_empty());
+    }
+
+    #[test]
+    fn merge_different_ids_fails() {
+        let base = Profile::placeholder();
+        assert_eq!(
+            Profile::merge(&base, &base, &Profile::placeholder_other()),
+            Err(CommonError::UnableToMergeProfilesWithDifferentIDs)
+        );
+    }
+
+    #[test]
+    fn merge_non_conflicting_changes_of_both_sides() {
+        let base = Profile::placeholder();
+        let alice = Account::placeholder_mainnet_alice();
+        let bob = Account::placeholder_mainnet_bob();
+        let carol = Account::placeholder_mainnet_carol();
+
+        let mut ours = base.clone();
+        rename_account(&mut ours, &alice.address, "Stella");
+        ours.app_preferences
+            .p2p_links
+            .append(P2PLink::placeholder_arc());
+
+        let mut theirs = base.clone();
+        rename_account(&mut theirs, &bob.address, "Bobby");
+        theirs.networks.update_with(&NetworkID::Mainnet, |n| {
+            n.accounts.append(carol.clone());
+        });
+        theirs
+            .factor_sources
+            .append(FactorSource::placeholder_device_olympia());
+
+        let sut = profile_three_way_merge(&base, &ours, &theirs).unwrap();
+        assert!(sut.conflicts.is_empty());
+
+        let merged = sut.merged;
+        assert_eq!(account_name(&merged, &alice.address), "Stella");
+        assert_eq!(account_name(&merged, &bob.address), "Bobby");
+        assert_eq!(account_name(&merged, &carol.address), "Carol");
+        assert!(merged.factor_sources.contains_id(
+            &FactorSource::placeholder_device_olympia().factor_source_id()
+        ));
+        assert!(merged
+            .app_preferences
+            .p2p_links
+            .contains_id(&P2PLink::placeholder_arc().id()));
+        assert_eq!(merged.header.content_hint, merged.networks.content_hint());
+        assert_eq!(
+            merged
+                .header
+                .content_hint
+                .number_of_accounts_on_all_networks_in_total,
+            5
+        );
+    }
+
+    #[test]
+    fn merge_removal_by_one_side() {
+        let base = Profile::placeholder();
+        let satoshi = Persona::placeholder_mainnet_satoshi();
+        let ours = base.clone();
+        let mut theirs = base.clone();
+        theirs.networks.update_with(&NetworkID::Mainnet, |n| {
+            n.personas.remove_by_id(&satoshi.address);
+        });
+        let sut = Profile::merge(&base, &ours, &theirs).unwrap();
+        assert!(sut.conflicts.is_empty());
+        assert_eq!(sut.merged.diff(&theirs), ProfileDiff::default());
+    }
+
+    #[test]
+    fn merge_conflicting_modifications_keeps_ours() {
+        let base = Profile::placeholder();
+        let alice = Account::placeholder_mainnet_alice();
+        let mut ours = base.clone();
+        rename_account(&mut ours, &alice.address, "Stella");
+        let mut theirs = base.clone();
+        rename_account(&mut theirs, &alice.address, "Luna");
+
+        let sut = Profile::merge(&base, &ours, &theirs).unwrap();
+        assert_eq!(account_name(&sut.merged, &alice.address), "Stella");
+        assert_eq!(
+            sut.conflicts,
+            vec![ProfileMergeConflict::Accounts {
+                base: base.networks.get_account(&alice.address),
+                ours: ours.networks.get_account(&alice.address),
+                theirs: theirs.networks.get_account(&alice.address),
+            }]
+        );
+    }
+
+    #[test]
+    fn merge_removal_conflicting_with_modification_keeps_element() {
+        let base = Profile::placeholder();
+        let alice = Account::placeholder_mainnet_alice();
+        let mut ours = base.clone();
+        ours.networks.update_with(&NetworkID::Mainnet, |n| {
+            n.accounts.remove_by_id(&alice.address);
+        });
+        let mut theirs = base.clone();
+        rename_account(&mut theirs, &alice.address, "Luna");
+
+        let sut = Profile::merge(&base, &ours, &theirs).unwrap();
+        assert_eq!(account_name(&sut.merged, &alice.address), "Luna");
+        assert_eq!(
+            sut.conflicts,
+            vec![ProfileMergeConflict::Accounts {
+                base: Some(alice.clone()),
+                ours: None,
+                theirs: theirs.networks.get_account(&alice.address),
+            }]
+        );
+    }
+
+    #[test]
+    fn merge_resulting_in_dangling_persona_reference_is_reported() {
+        let base = Profile::placeholder();
+        let dashboard = AuthorizedDapp::placeholder_mainnet_dashboard();
+        let persona_address =
+            AuthorizedPersonaSimple::placeholder_mainnet().identity_address;
+
+        let mut ours = base.clone();
+        ours.networks.update_with(&NetworkID::Mainnet, |n| {
+            n.personas.remove_by_id(&persona_address);
+            n.authorized_dapps = AuthorizedDapps::new();
+        });
+        let mut theirs = base.clone();
+        theirs.networks.update_with(&NetworkID::Mainnet, |n| {
+            n.authorized_dapps
+                .update_with(&dashboard.dapp_definition_address, |d| {
+                    d.display_name = Some("Dashboard".to_owned())
+                });
+        });
+
+        let sut = Profile::merge(&base, &ours, &theirs).unwrap();
+        assert!(sut.conflicts.contains(
+            &ProfileMergeConflict::InvalidMergedProfile {
+                violation: ProfileViolation::UnknownPersonaReferencedByDapp {
+                    dapp_definition_address: dashboard.dapp_definition_address,
+                    identity_address: persona_address,
+                }
+            }
+        ));
+        assert!(!sut.merged.validate().is_empty());
+    }
+
+    #[test]
+    fn merge_conflicting_current_gateway() {
+        let base = Profile::placeholder();
+        let mut ours = base.clone();
+        ours.app_preferences.gateways =
+            Gateways::new_with_other(Gateway::mainnet(), [Gateway::rcnet()])
+                .unwrap();
+        let mut theirs = base.clone();
+        theirs.app_preferences.gateways =
+            Gateways::new_with_other(Gateway::stokenet(), [Gateway::rcnet()])
+                .unwrap();
+
+        let sut = Profile::merge(&base, &ours, &theirs).unwrap();
+        let gateways = sut.merged.app_preferences.gateways;
+        assert_eq!(gateways.current, Gateway::mainnet());
+        assert!(!gateways.other.contains_id(&Gateway::mainnet().id()));
+        assert_eq!(
+            sut.conflicts,
+            vec![ProfileMergeConflict::CurrentGateway {
+                base: Gateway::rcnet(),
+                ours: Gateway::mainnet(),
+                theirs: Gateway::stokenet(),
+            }]
+        );
+    }
+
+    #[test]
+    fn merge_transaction_preferences() {
+        let base = Profile::placeholder();
+        let mut ours = base.clone();
+        ours.app_preferences.transaction =
+            TransactionPreferences::placeholder_other();
+        let theirs = base.clone();
+        let sut = Profile::merge(&base, &ours, &theirs).unwrap();
+        assert_eq!(
+            sut.merged.app_preferences.transaction,
+            TransactionPreferences::placeholder_other()
+        );
+
+        let mut theirs = base.clone();
+        theirs.app_preferences.transaction = TransactionPreferences::default();
+        let sut = Profile::merge(&base, &ours, &theirs).unwrap();
+        assert_eq!(
+            sut.conflicts,
+            vec![ProfileMergeConflict::TransactionSettings {
+                base: TransactionPreferences::placeholder(),
+                ours: TransactionPreferences::placeholder_other(),
+                theirs: TransactionPreferences::default(),
+            }]
+        );
+    }
+}
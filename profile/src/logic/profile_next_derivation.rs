@@ -1,6 +1,10 @@
 use crate::prelude::*;
 
 impl Profile {
+    /// The number of unhardened derivation indices, i.e. the first index
+    /// which cannot be used as the last component of a derivation path.
+    pub const DERIVATION_INDEX_LIMIT: HDPathValue = 1 << 31;
+
     #[cfg(not(tarpaulin_include))] // false negative
     pub fn factor_source_by_id<F>(&self, id: &FactorSourceID) -> Result<F>
     where
@@ -57,42 +61,144 @@ impl Profile {
         explicit_main.unwrap_or(implicit_main).clone()
     }
 
-    fn next_derivation_index_for_entity_for_factor_source(
+    /// The CAP26 indices of keys of `key_kind` derived by `factor_source_id`
+    /// for entities of `kind` on `network_id`, looking at the derivation
+    /// paths of the factor instances of **all** accounts **and** personas,
+    /// including hidden and deleted ones, so that an entity created with a
+    /// path of the other entity kind is still detected.
+    pub fn used_derivation_indices(
+        &self,
+        kind: EntityKind,
+        network_id: NetworkID,
+        factor_source_id: &FactorSourceIDFromHash,
+        key_kind: CAP26KeyKind,
+    ) -> BTreeSet<HDPathValue> {
+        let entity_kind = match kind {
+            EntityKind::Accounts => CAP26EntityKind::Account,
+            EntityKind::Persona => CAP26EntityKind::Identity,
+        };
+        let Some(network) = self.networks.get(&network_id) else {
+            return BTreeSet::new();
+        };
+        network
+            .accounts
+            .iter()
+            .map(|a| &a.security_state)
+            .chain(network.personas.iter().map(|p| &p.security_state))
+            .flat_map(|security_state| {
+                let EntitySecurityState::Unsecured { value: control } =
+                    security_state;
+                [
+                    Some(control.transaction_signing.clone()),
+                    control.authentication_signing.clone(),
+                ]
+            })
+            .flatten()
+            .filter(|f| f.factor_source_id == *factor_source_id)
+            .filter_map(|f| cap26_entity_path_components(&f))
+            .filter(|(e, n, k, _)| {
+                *e == entity_kind && *n == network_id && *k == key_kind
+            })
+            .map(|(_, _, _, index)| index)
+            .collect()
+    }
+
+    /// The index following the highest CAP26 index used by `factor_source_id`
+    /// for transaction signing keys of entities of `kind` on `network_id`,
+    /// or `0` if none is used. Gaps - e.g. from imported accounts - are never
+    /// filled, so that a key in use is never derived again.
+    ///
+    /// Returns `Err` if the highest index used is the last one below
+    /// `DERIVATION_INDEX_LIMIT`.
+    pub fn next_derivation_index_for_entity_for_factor_source(
         &self,
         kind: EntityKind,
         network_id: NetworkID,
         factor_source_id: FactorSourceIDFromHash,
-    ) -> HDPathValue {
-        match kind {
-            EntityKind::Persona => panic!("Personas are not supported yet"),
-            EntityKind::Accounts => {}
+    ) -> Result<HDPathValue> {
+        let Some(last) = self
+            .used_derivation_indices(
+                kind.clone(),
+                network_id,
+                &factor_source_id,
+                CAP26KeyKind::TransactionSigning,
+            )
+            .last()
+            .cloned()
+        else {
+            return Ok(0);
         };
-        let index = self
-            .networks
-            .get(&network_id)
-            .map(|n| {
-                n.accounts
-                    .items()
-                    .into_iter()
-                    .filter(|a| match &a.security_state {
-                        EntitySecurityState::Unsecured { value } => {
-                            value.transaction_signing.factor_source_id
-                                == factor_source_id
-                        }
-                    })
-                    .collect_vec()
-                    .len()
+        last.checked_add(1)
+            .filter(|next| *next < Self::DERIVATION_INDEX_LIMIT)
+            .ok_or(CommonError::DerivationIndicesExhausted {
+                entity_kind: kind,
+                network_id,
+                factor_source_id,
             })
-            .unwrap_or(0);
+    }
+
+    /// Returns `Err` if the CAP26 index of the transaction signing key of
+    /// an entity with `security_state` is already in use, for the entity kind
+    /// of its derivation path, see `ensure_derivation_index_is_free`.
+    /// Entities with non CAP26 paths, e.g. imported from Olympia, are always
+    /// free.
+    pub fn ensure_entity_derivation_index_is_free(
+        &self,
+        security_state: &EntitySecurityState,
+    ) -> Result<()> {
+        let EntitySecurityState::Unsecured { value: control } = security_state;
+        let factor_instance = &control.transaction_signing;
+        let Some((entity_kind, network_id, _, index)) =
+            cap26_entity_path_components(factor_instance)
+        else {
+            return Ok(());
+        };
+        let kind = match entity_kind {
+            CAP26EntityKind::Account => EntityKind::Accounts,
+            CAP26EntityKind::Identity => EntityKind::Persona,
+        };
+        self.ensure_derivation_index_is_free(
+            kind,
+            network_id,
+            &factor_instance.factor_source_id,
+            index,
+        )
+    }
 
-        index as u32
+    /// Returns `Err` if `index` is already used by `factor_source_id` for a
+    /// transaction signing key of an entity of `kind` on `network_id`, since
+    /// an entity created at `index` would be a duplicate.
+    pub fn ensure_derivation_index_is_free(
+        &self,
+        kind: EntityKind,
+        network_id: NetworkID,
+        factor_source_id: &FactorSourceIDFromHash,
+        index: HDPathValue,
+    ) -> Result<()> {
+        if self
+            .used_derivation_indices(
+                kind.clone(),
+                network_id,
+                factor_source_id,
+                CAP26KeyKind::TransactionSigning,
+            )
+            .contains(&index)
+        {
+            return Err(CommonError::DerivationIndexAlreadyInUse {
+                index,
+                entity_kind: kind,
+                network_id,
+                factor_source_id: factor_source_id.clone(),
+            });
+        }
+        Ok(())
     }
 
     pub fn next_derivation_index_for_entity(
         &self,
         kind: EntityKind,
         network_id: NetworkID,
-    ) -> HDPathValue {
+    ) -> Result<HDPathValue> {
         self.next_derivation_index_for_entity_for_factor_source(
             kind,
             network_id,
@@ -101,6 +207,32 @@ impl Profile {
     }
 }
 
+/// The entity kind, network, key kind and index of the derivation path of
+/// `factor_instance`, if it is a CAP26 account or identity path.
+fn cap26_entity_path_components(
+    factor_instance: &HierarchicalDeterministicFactorInstance,
+) -> Option<(CAP26EntityKind, NetworkID, CAP26KeyKind, HDPathValue)> {
+    match factor_instance.derivation_path() {
+        DerivationPath::CAP26 {
+            value: CAP26Path::Account { value },
+        } => Some((
+            value.entity_kind,
+            value.network_id,
+            value.key_kind,
+            value.index,
+        )),
+        DerivationPath::CAP26 {
+            value: CAP26Path::Identity { value },
+        } => Some((
+            value.entity_kind,
+            value.network_id,
+            value.key_kind,
+            value.index,
+        )),
+        _ => None,
+    }
+}
+
 impl Profile {
     pub fn placeholder_no_device_factor_source() -> Self {
         let networks = ProfileNetworks::placeholder();
@@ -274,7 +406,7 @@ mod tests {
                 EntityKind::Accounts,
                 NetworkID::Mainnet
             ),
-            2
+            Ok(2)
         );
     }
 
@@ -286,7 +418,214 @@ mod tests {
                 EntityKind::Accounts,
                 NetworkID::Stokenet
             ),
-            2
+            Ok(2)
+        );
+    }
+
+    #[test]
+    fn next_derivation_index_for_entity_persona_bdfs_mainnet() {
+        let profile = Profile::placeholder();
+        assert_eq!(
+            profile.next_derivation_index_for_entity(
+                EntityKind::Persona,
+                NetworkID::Mainnet
+            ),
+            Ok(2)
+        );
+    }
+
+    #[test]
+    fn next_derivation_index_for_entity_counts_hidden_entities() {
+        let mut profile = Profile::placeholder();
+        profile.networks.update_with(&NetworkID::Mainnet, |n| {
+            n.accounts.update_with(
+                &Account::placeholder_mainnet_alice().address,
+                |a| _ = a.flags.insert_flag(EntityFlag::HiddenByUser),
+            );
+            n.personas.update_with(
+                &Persona::placeholder_mainnet_satoshi().address,
+                |p| _ = p.flags.insert_flag(EntityFlag::HiddenByUser),
+            );
+        });
+        assert_eq!(
+            profile.next_derivation_index_for_entity(
+                EntityKind::Accounts,
+                NetworkID::Mainnet
+            ),
+            Ok(2)
+        );
+        assert_eq!(
+            profile.next_derivation_index_for_entity(
+                EntityKind::Persona,
+                NetworkID::Mainnet
+            ),
+            Ok(2)
+        );
+    }
+
+    fn account_at_index(index: HDPathValue) -> Account {
+        Account::new(
+            PrivateHierarchicalDeterministicFactorSource::placeholder()
+                .derive_entity_creation_factor_instance(
+                    NetworkID::Mainnet,
+                    index,
+                ),
+            DisplayName::new("Imported").unwrap(),
+            AppearanceID::default(),
+        )
+    }
+
+    #[test]
+    fn next_derivation_index_for_entity_does_not_reuse_removed_index() {
+        let mut profile = Profile::placeholder();
+        profile.networks.update_with(&NetworkID::Mainnet, |n| {
+            _ = n
+                .accounts
+                .remove_by_id(&Account::placeholder_mainnet_alice().address)
+        });
+        assert_eq!(
+            profile.next_derivation_index_for_entity(
+                EntityKind::Accounts,
+                NetworkID::Mainnet
+            ),
+            Ok(2)
+        );
+    }
+
+    #[test]
+    fn next_derivation_index_for_entity_skips_gap() {
+        let mut profile = Profile::placeholder();
+        profile.add_accounts(vec![account_at_index(5)]).unwrap();
+        assert_eq!(
+            profile.next_derivation_index_for_entity(
+                EntityKind::Accounts,
+                NetworkID::Mainnet
+            ),
+            Ok(6)
+        );
+        // Personas are allocated separately.
+        assert_eq!(
+            profile.next_derivation_index_for_entity(
+                EntityKind::Persona,
+                NetworkID::Mainnet
+            ),
+            Ok(2)
+        );
+    }
+
+    #[test]
+    fn used_derivation_indices_per_key_kind() {
+        let profile = Profile::placeholder();
+        let id = profile.bdfs().id;
+        assert_eq!(
+            profile.used_derivation_indices(
+                EntityKind::Accounts,
+                NetworkID::Mainnet,
+                &id,
+                CAP26KeyKind::TransactionSigning
+            ),
+            BTreeSet::from_iter([0, 1])
+        );
+        assert!(profile
+            .used_derivation_indices(
+                EntityKind::Accounts,
+                NetworkID::Mainnet,
+                &id,
+                CAP26KeyKind::AuthenticationSigning
+            )
+            .is_empty());
+    }
+
+    #[test]
+    fn ensure_derivation_index_is_free() {
+        let profile = Profile::placeholder();
+        let id = profile.bdfs().id;
+        assert_eq!(
+            profile.ensure_derivation_index_is_free(
+                EntityKind::Accounts,
+                NetworkID::Mainnet,
+                &id,
+                2
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            profile.ensure_derivation_index_is_free(
+                EntityKind::Accounts,
+                NetworkID::Mainnet,
+                &id,
+                1
+            ),
+            Err(CommonError::DerivationIndexAlreadyInUse {
+                index: 1,
+                entity_kind: EntityKind::Accounts,
+                network_id: NetworkID::Mainnet,
+                factor_source_id: id
+            })
+        );
+    }
+
+    #[test]
+    fn ensure_entity_derivation_index_is_free() {
+        let profile = Profile::placeholder();
+        assert!(profile
+            .ensure_entity_derivation_index_is_free(
+                &account_at_index(1).security_state
+            )
+            .is_err());
+        assert_eq!(
+            profile.ensure_entity_derivation_index_is_free(
+                &account_at_index(2).security_state
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn ensure_entity_derivation_index_is_free_uses_kind_of_path() {
+        let profile = Profile::placeholder();
+        let satoshi = Persona::placeholder_mainnet_satoshi();
+        assert!(matches!(
+            profile.ensure_entity_derivation_index_is_free(
+                &satoshi.security_state
+            ),
+            Err(CommonError::DerivationIndexAlreadyInUse {
+                entity_kind: EntityKind::Persona,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn next_derivation_index_for_entity_fails_when_indices_exhausted() {
+        let mut profile = Profile::placeholder();
+        profile
+            .add_accounts(vec![account_at_index(
+                Profile::DERIVATION_INDEX_LIMIT - 1,
+            )])
+            .unwrap();
+        assert_eq!(
+            profile.next_derivation_index_for_entity(
+                EntityKind::Accounts,
+                NetworkID::Mainnet
+            ),
+            Err(CommonError::DerivationIndicesExhausted {
+                entity_kind: EntityKind::Accounts,
+                network_id: NetworkID::Mainnet,
+                factor_source_id: profile.bdfs().id
+            })
+        );
+    }
+
+    #[test]
+    fn next_derivation_index_for_entity_persona_bdfs_unknown_network() {
+        let profile = Profile::placeholder();
+        assert_eq!(
+            profile.next_derivation_index_for_entity(
+                EntityKind::Persona,
+                NetworkID::Nebunet
+            ),
+            Ok(0)
         );
     }
 
@@ -299,7 +638,7 @@ mod tests {
                 NetworkID::Mainnet,
                 DeviceFactorSource::placeholder_olympia().id
             ),
-            0
+            Ok(0)
         );
     }
 }
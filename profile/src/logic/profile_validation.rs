@@ -0,0 +1,398 @@
+use crate::prelude::*;
+
+/// A violation of an invariant of a `Profile`, found by `Profile::validate`.
+///
+/// A Profile with violations can still be used, but it indicates that it has
+/// been corrupted, e.g. by a bug in a previous version of a Wallet Client or
+/// by having been edited by hand.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, uniffi::Enum)]
+pub enum ProfileViolation {
+    /// The entity with `address` is on network `entity_network_id` but is
+    /// stored in the `ProfileNetwork` with ID `profile_network_id`.
+    EntityOnWrongNetwork {
+        address: AddressOfAccountOrPersona,
+        entity_network_id: NetworkID,
+        profile_network_id: NetworkID,
+    },
+
+    /// A factor instance of the entity with `address`, for transaction or
+    /// authentication signing, is from a FactorSource with ID
+    /// `factor_source_id` which is not in the Profile.
+    UnknownFactorSourceOfEntity {
+        address: AddressOfAccountOrPersona,
+        factor_source_id: FactorSourceIDFromHash,
+    },
+
+    /// All the entities with `addresses` are controlled by the same factor
+    /// instance, i.e. the same FactorSource, derivation path and network.
+    DuplicateFactorInstance {
+        factor_source_id: FactorSourceIDFromHash,
+        derivation_path: DerivationPath,
+        network_id: NetworkID,
+        addresses: Vec<AddressOfAccountOrPersona>,
+    },
+
+    /// The address of the entity is not the address derived from the public
+    /// key of its transaction signing factor instance.
+    AddressNotDerivedFromPublicKey { address: AddressOfAccountOrPersona },
+
+    /// The Dapp with `dapp_definition_address` references a Persona with
+    /// `identity_address` which is not in the Profile.
+    UnknownPersonaReferencedByDapp {
+        dapp_definition_address: DappDefinitionAddress,
+        identity_address: IdentityAddress,
+    },
+
+    /// The `ContentHint` of the header does not match the contents of the
+    /// Profile.
+    ContentHintMismatch {
+        in_header: ContentHint,
+        actual: ContentHint,
+    },
+
+    /// The Profile does not contain any `DeviceFactorSource` supporting
+    /// Babylon, which is needed to create new entities.
+    MissingBabylonDeviceFactorSource,
+}
+
+/// The parts of an Account or Persona relevant for validation.
+struct ValidatedEntity {
+    address: AddressOfAccountOrPersona,
+    network_id: NetworkID,
+    control: UnsecuredEntityControl,
+}
+
+impl From<&Account> for ValidatedEntity {
+    fn from(value: &Account) -> Self {
+        let EntitySecurityState::Unsecured { value: control } =
+            value.security_state.clone();
+        Self {
+            address: value.address.clone().into(),
+            network_id: value.network_id,
+            control,
+        }
+    }
+}
+
+impl From<&Persona> for ValidatedEntity {
+    fn from(value: &Persona) -> Self {
+        let EntitySecurityState::Unsecured { value: control } =
+            value.security_state.clone();
+        Self {
+            address: value.address.clone().into(),
+            network_id: value.network_id,
+            control,
+        }
+    }
+}
+
+/// Validates `profile`, returning all violations found, see
+/// `Profile::validate`.
+#[uniffi::export]
+pub fn validate_profile(profile: &Profile) -> Vec<ProfileViolation> {
+    profile.validate()
+}
+
+impl Profile {
+    /// Validates the invariants of this Profile, returning all violations
+    /// found, an empty list means that the Profile is valid.
+    pub fn validate(&self) -> Vec<ProfileViolation> {
+        let mut violations = Vec::new();
+        self.validate_entities(&mut violations);
+        self.validate_authorized_dapps(&mut violations);
+        self.validate_content_hint(&mut violations);
+        self.validate_babylon_device_factor_source(&mut violations);
+        violations
+    }
+
+    fn validate_entities(&self, violations: &mut Vec<ProfileViolation>) {
+        let mut addresses_of_instance = BTreeMap::<
+            (FactorSourceIDFromHash, DerivationPath, NetworkID),
+            Vec<AddressOfAccountOrPersona>,
+        >::new();
+
+        for network in self.networks.iter() {
+            let entities = network
+                .accounts
+                .iter()
+                .map(ValidatedEntity::from)
+                .chain(network.personas.iter().map(ValidatedEntity::from));
+
+            for entity in entities {
+                if entity.network_id != network.id {
+                    violations.push(ProfileViolation::EntityOnWrongNetwork {
+                        address: entity.address.clone(),
+                        entity_network_id: entity.network_id,
+                        profile_network_id: network.id,
+                    });
+                }
+
+                violations.extend(
+                    std::iter::once(&entity.control.transaction_signing)
+                        .chain(entity.control.authentication_signing.iter())
+                        .map(|instance| instance.factor_source_id.clone())
+                        .unique()
+                        .filter(|id| {
+                            !self.factor_sources.contains_id(&id.clone().into())
+                        })
+                        .map(|factor_source_id| {
+                            ProfileViolation::UnknownFactorSourceOfEntity {
+                                address: entity.address.clone(),
+                                factor_source_id,
+                            }
+                        }),
+                );
+
+                let instance = entity.control.transaction_signing;
+                let factor_source_id = instance.factor_source_id;
+                let public_key = instance.public_key;
+                if entity
+                    .address
+                    .derived_from(public_key.public_key, entity.network_id)
+                    != entity.address
+                {
+                    violations.push(
+                        ProfileViolation::AddressNotDerivedFromPublicKey {
+                            address: entity.address.clone(),
+                        },
+                    );
+                }
+
+                addresses_of_instance
+                    .entry((
+                        factor_source_id,
+                        public_key.derivation_path,
+                        entity.network_id,
+                    ))
+                    .or_default()
+                    .push(entity.address);
+            }
+        }
+
+        violations.extend(
+            addresses_of_instance
+                .into_iter()
+                .filter(|(_, addresses)| addresses.len() > 1)
+                .map(
+                    |(
+                        (factor_source_id, derivation_path, network_id),
+                        addresses,
+                    )| {
+                        ProfileViolation::DuplicateFactorInstance {
+                            factor_source_id,
+                            derivation_path,
+                            network_id,
+                            addresses,
+                        }
+                    },
+                ),
+        );
+    }
+
+    fn validate_authorized_dapps(
+        &self,
+        violations: &mut Vec<ProfileViolation>,
+    ) {
+        for network in self.networks.iter() {
+            for dapp in network.authorized_dapps.iter() {
+                violations.extend(
+                    dapp.references_to_authorized_personas
+                        .iter()
+                        .filter(|r| {
+                            !network.personas.contains_id(&r.identity_address)
+                        })
+                        .map(|r| {
+                            ProfileViolation::UnknownPersonaReferencedByDapp {
+                                dapp_definition_address: dapp
+                                    .dapp_definition_address
+                                    .clone(),
+                                identity_address: r.identity_address.clone(),
+                            }
+                        }),
+                );
+            }
+        }
+    }
+
+    fn validate_content_hint(&self, violations: &mut Vec<ProfileViolation>) {
+        let actual = self.networks.content_hint();
+        if self.header.content_hint != actual {
+            violations.push(ProfileViolation::ContentHintMismatch {
+                in_header: self.header.content_hint.clone(),
+                actual,
+            });
+        }
+    }
+
+    fn validate_babylon_device_factor_source(
+        &self,
+        violations: &mut Vec<ProfileViolation>,
+    ) {
+        let has_babylon_device_factor_source = self
+            .factor_sources
+            .iter()
+            .filter_map(|f| f.as_device().cloned())
+            .any(|d| d.common.supports_babylon());
+        if !has_babylon_device_factor_source {
+            violations.push(ProfileViolation::MissingBabylonDeviceFactorSource);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    fn alice() -> AccountAddress {
+        Account::placeholder_mainnet_alice().address
+    }
+
+    #[test]
+    fn placeholder_is_valid() {
+        assert_eq!(Profile::placeholder().validate(), Vec::new());
+    }
+
+    #[test]
+    fn validate_profile_global_function() {
+        assert_eq!(validate_profile(&Profile::placeholder()), Vec::new());
+    }
+
+    #[test]
+    fn entity_on_wrong_network() {
+        let mut sut = Profile::placeholder();
+        sut.update_account(&alice(), |a| a.network_id = NetworkID::Stokenet)
+            .unwrap();
+        assert_eq!(
+            sut.validate(),
+            vec![ProfileViolation::EntityOnWrongNetwork {
+                address: alice().into(),
+                entity_network_id: NetworkID::Stokenet,
+                profile_network_id: NetworkID::Mainnet,
+            }]
+        );
+    }
+
+    #[test]
+    fn unknown_factor_source() {
+        let sut = Profile::placeholder_no_device_factor_source();
+        let violations = sut.validate();
+        assert!(violations.contains(
+            &ProfileViolation::UnknownFactorSourceOfEntity {
+                address: alice().into(),
+                factor_source_id: FactorSourceIDFromHash::placeholder_device(),
+            }
+        ));
+        assert_eq!(
+            violations.last(),
+            Some(&ProfileViolation::MissingBabylonDeviceFactorSource)
+        );
+    }
+
+    #[test]
+    fn unknown_factor_source_of_authentication_signing() {
+        let mut sut = Profile::placeholder();
+        sut.update_account(&alice(), |a| {
+            let EntitySecurityState::Unsecured { value: control } =
+                &mut a.security_state;
+            let mut instance = control.transaction_signing.clone();
+            instance.factor_source_id =
+                FactorSourceIDFromHash::placeholder_ledger_other();
+            control.authentication_signing = Some(instance);
+        })
+        .unwrap();
+        assert_eq!(
+            sut.validate(),
+            vec![ProfileViolation::UnknownFactorSourceOfEntity {
+                address: alice().into(),
+                factor_source_id:
+                    FactorSourceIDFromHash::placeholder_ledger_other(),
+            }]
+        );
+    }
+
+    #[test]
+    fn duplicate_factor_instance() {
+        let mut sut = Profile::placeholder();
+        let alice_security_state =
+            Account::placeholder_mainnet_alice().security_state;
+        let bob = Account::placeholder_mainnet_bob().address;
+        sut.update_account(&bob, |a| {
+            a.security_state = alice_security_state.clone()
+        })
+        .unwrap();
+        let violations = sut.validate();
+        let EntitySecurityState::Unsecured { value: control } =
+            alice_security_state;
+        let instance = control.transaction_signing;
+        assert!(violations.contains(
+            &ProfileViolation::DuplicateFactorInstance {
+                factor_source_id: instance.factor_source_id,
+                derivation_path: instance.public_key.derivation_path,
+                network_id: NetworkID::Mainnet,
+                addresses: vec![alice().into(), bob.clone().into()],
+            }
+        ));
+        assert!(violations.contains(
+            &ProfileViolation::AddressNotDerivedFromPublicKey {
+                address: bob.into()
+            }
+        ));
+    }
+
+    #[test]
+    fn address_not_derived_from_public_key() {
+        let mut sut = Profile::placeholder();
+        let carol_security_state =
+            Account::placeholder_mainnet_carol().security_state;
+        sut.update_account(&alice(), |a| {
+            a.security_state = carol_security_state.clone()
+        })
+        .unwrap();
+        assert_eq!(
+            sut.validate(),
+            vec![ProfileViolation::AddressNotDerivedFromPublicKey {
+                address: alice().into()
+            }]
+        );
+    }
+
+    #[test]
+    fn unknown_persona_referenced_by_dapp() {
+        let mut sut = Profile::placeholder();
+        let satoshi = Persona::placeholder_mainnet_satoshi().address;
+        sut.networks.update_with(&NetworkID::Mainnet, |n| {
+            n.personas.remove_by_id(&satoshi);
+        });
+        sut.header.content_hint = sut.networks.content_hint();
+        assert_eq!(
+            sut.validate(),
+            vec![ProfileViolation::UnknownPersonaReferencedByDapp {
+                dapp_definition_address:
+                    AuthorizedDapp::placeholder_mainnet_dashboard()
+                        .dapp_definition_address,
+                identity_address: satoshi,
+            }]
+        );
+    }
+
+    #[test]
+    fn content_hint_mismatch() {
+        let mut sut = Profile::placeholder();
+        sut.header.content_hint = ContentHint::new();
+        assert_eq!(
+            sut.validate(),
+            vec![ProfileViolation::ContentHintMismatch {
+                in_header: ContentHint::new(),
+                actual: sut.networks.content_hint(),
+            }]
+        );
+    }
+
+    #[test]
+    fn missing_babylon_device_factor_source() {
+        let sut = Profile::placeholder_no_babylon_device_factor_source();
+        assert!(sut
+            .validate()
+            .contains(&ProfileViolation::MissingBabylonDeviceFactorSource));
+    }
+}
@@ -0,0 +1,5 @@
+mod profile_snapshot_migration;
+mod profile_snapshot_migrator;
+
+pub use profile_snapshot_migration::*;
+pub use profile_snapshot_migrator::*;
@@ -0,0 +1,22 @@
+use crate::prelude::*;
+
+/// A single step in the chain of migrations of Profile snapshots, which
+/// transforms the JSON of a snapshot of version `from_version` into the JSON
+/// of a snapshot of version `to_version`.
+///
+/// Migrations work on JSON rather than on typed models, so that the models
+/// of old versions do not need to be kept around once the schema changes.
+pub trait ProfileSnapshotMigration: Send + Sync + std::fmt::Debug {
+    /// The `snapshotVersion` of the snapshots this migration accepts.
+    fn from_version(&self) -> u16;
+
+    /// The `snapshotVersion` of the snapshots this migration produces, MUST
+    /// be greater than `from_version`.
+    fn to_version(&self) -> u16;
+
+    /// Transforms `snapshot`, being of `from_version`, into a snapshot of
+    /// `to_version`. Implementations need not update `snapshotVersion` in
+    /// the header, the `ProfileSnapshotMigrator` does that.
+    fn migrate(&self, snapshot: serde_json::Value)
+        -> Result<serde_json::Value>;
+}
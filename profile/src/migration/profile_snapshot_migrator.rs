@@ -0,0 +1,306 @@
+use crate::prelude::*;
+
+/// Reads Profile snapshots of any supported version, by running the chain of
+/// `ProfileSnapshotMigration`s from the version of the snapshot up to the
+/// current version, before deserializing the snapshot into a `Profile`.
+#[derive(Debug)]
+pub struct ProfileSnapshotMigrator {
+    /// The version all snapshots are migrated to.
+    current_version: u16,
+
+    /// The chain of migrations, each migrating from a distinct version.
+    migrations: Vec<Box<dyn ProfileSnapshotMigration>>,
+}
+
+impl Default for ProfileSnapshotMigrator {
+    /// The migrator of all released versions up to the current
+    /// `ProfileSnapshotVersion`. When a new version is introduced, append a
+    /// migration from the previous version here.
+    fn default() -> Self {
+        Self::new(ProfileSnapshotVersion::default().discriminant(), Vec::new())
+    }
+}
+
+impl ProfileSnapshotMigrator {
+    pub fn new(
+        current_version: u16,
+        migrations: Vec<Box<dyn ProfileSnapshotMigration>>,
+    ) -> Self {
+        Self {
+            current_version,
+            migrations,
+        }
+    }
+
+    /// Reads only the `snapshotVersion` of the header of the JSON snapshot
+    /// `json`, without deserializing the rest of it into a `Profile`.
+    ///
+    /// Returns `None` if `json` is not a JSON object with a header containing
+    /// a `snapshotVersion`.
+    pub fn snapshot_version_of(json: &serde_json::Value) -> Option<u16> {
+        json.get("header")
+            .and_then(|h| h.get("snapshotVersion"))
+            .and_then(|v| v.as_u64())
+            .and_then(|v| u16::try_from(v).ok())
+    }
+
+    /// Migrates the JSON snapshot `json` of version `from_version` to the
+    /// current version, one migration at a time.
+    ///
+    /// Fails with `UnsupportedProfileSnapshotVersion` if `from_version` is
+    /// newer than the current version or if no chain of migrations leads
+    /// from it to the current version.
+    pub fn migrate(
+        &self,
+        mut json: serde_json::Value,
+        from_version: u16,
+    ) -> Result<serde_json::Value> {
+        let mut version = from_version;
+        while version != self.current_version {
+            let migration = self
+                .migrations
+                .iter()
+                .find(|m| m.from_version() == version)
+                .filter(|_| version < self.current_version)
+                .ok_or(CommonError::UnsupportedProfileSnapshotVersion(
+                    from_version,
+                ))?;
+
+            let to_version = migration.to_version();
+            let failed = CommonError::FailedToMigrateProfileSnapshot {
+                from_version: version,
+                to_version,
+            };
+            if to_version <= version {
+                return Err(failed);
+            }
+
+            info!(
+                "Migrating Profile snapshot from version {} to {}",
+                version, to_version
+            );
+            json = migration.migrate(json).map_err(|e| {
+                error!("Failed to migrate Profile snapshot, error: {}", e);
+                failed.clone()
+            })?;
+            json.get_mut("header")
+                .and_then(|h| h.as_object_mut())
+                .ok_or(failed)?
+                .insert("snapshotVersion".to_owned(), json!(to_version));
+            version = to_version;
+        }
+        Ok(json)
+    }
+
+    /// Migrates the JSON snapshot `json` to the current version and
+    /// deserializes it into a `Profile`.
+    pub fn profile_from_json(
+        &self,
+        json: serde_json::Value,
+        json_byte_count: usize,
+    ) -> Result<Profile> {
+        let deserialize_failed =
+            || CommonError::FailedToDeserializeJSONToValue {
+                json_byte_count,
+                type_name: std::any::type_name::<Profile>().to_string(),
+            };
+        let version =
+            Self::snapshot_version_of(&json).ok_or_else(deserialize_failed)?;
+        let json = self.migrate(json, version)?;
+        serde_json::from_value(json).map_err(|_| deserialize_failed())
+    }
+
+    /// Migrates the JSON snapshot `bytes` to the current version and
+    /// deserializes it into a `Profile`.
+    pub fn profile_from_json_bytes(
+        &self,
+        bytes: impl AsRef<[u8]>,
+    ) -> Result<Profile> {
+        let bytes = bytes.as_ref();
+        let json = serde_json::from_slice(bytes).map_err(|_| {
+            CommonError::FailedToDeserializeJSONToValue {
+                json_byte_count: bytes.len(),
+                type_name: std::any::type_name::<Profile>().to_string(),
+            }
+        })?;
+        self.profile_from_json(json, bytes.len())
+    }
+}
+
+impl Profile {
+    /// Deserializes a JSON snapshot of any supported version into a
+    /// `Profile`, migrating it to the current version if needed.
+    pub fn from_snapshot_json_bytes(bytes: impl AsRef<[u8]>) -> Result<Self> {
+        ProfileSnapshotMigrator::default().profile_from_json_bytes(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    /// Renames the `networks` key to `nets` (98 -> 99) and back again
+    /// (99 -> 100), such that migrating a placeholder Profile snapshot
+    /// from 98 results in the placeholder Profile of version 100.
+    #[derive(Debug)]
+    struct RenameKey {
+        from_version: u16,
+        from_key: &'static str,
+        to_key: &'static str,
+    }
+
+    impl ProfileSnapshotMigration for RenameKey {
+        fn from_version(&self) -> u16 {
+            self.from_version
+        }
+
+        fn to_version(&self) -> u16 {
+            self.from_version + 1
+        }
+
+        fn migrate(
+            &self,
+            mut snapshot: serde_json::Value,
+        ) -> Result<serde_json::Value> {
+            let object =
+                snapshot.as_object_mut().ok_or(CommonError::Unknown)?;
+            let value =
+                object.remove(self.from_key).ok_or(CommonError::Unknown)?;
+            object.insert(self.to_key.to_owned(), value);
+            Ok(snapshot)
+        }
+    }
+
+    fn migrator() -> ProfileSnapshotMigrator {
+        ProfileSnapshotMigrator::new(
+            100,
+            vec![
+                Box::new(RenameKey {
+                    from_version: 99,
+                    from_key: "nets",
+                    to_key: "networks",
+                }),
+                Box::new(RenameKey {
+                    from_version: 98,
+                    from_key: "networks",
+                    to_key: "nets",
+                }),
+            ],
+        )
+    }
+
+    fn snapshot(version: u16) -> serde_json::Value {
+        let mut json = serde_json::to_value(Profile::placeholder()).unwrap();
+        json["header"]["snapshotVersion"] = json!(version);
+        json
+    }
+
+    #[test]
+    fn snapshot_version_of() {
+        assert_eq!(
+            ProfileSnapshotMigrator::snapshot_version_of(&snapshot(100)),
+            Some(100)
+        );
+        assert_eq!(
+            ProfileSnapshotMigrator::snapshot_version_of(
+                &json!({ "header": {} })
+            ),
+            None
+        );
+        assert_eq!(
+            ProfileSnapshotMigrator::snapshot_version_of(
+                &json!({ "header": { "snapshotVersion": 70000 } })
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn migrates_through_chain() {
+        let migrated = migrator().migrate(snapshot(98), 98).unwrap();
+        assert_eq!(migrated["header"]["snapshotVersion"], json!(100));
+        assert_eq!(migrated, snapshot(100));
+    }
+
+    #[test]
+    fn migrates_from_intermediate_version() {
+        let mut json = snapshot(99);
+        let networks = json.as_object_mut().unwrap().remove("networks");
+        json["nets"] = networks.unwrap();
+        assert_eq!(migrator().migrate(json, 99), Ok(snapshot(100)));
+    }
+
+    #[test]
+    fn current_version_is_not_migrated() {
+        assert_eq!(migrator().migrate(snapshot(100), 100), Ok(snapshot(100)));
+    }
+
+    #[test]
+    fn unsupported_future_version() {
+        assert_eq!(
+            migrator().migrate(snapshot(101), 101),
+            Err(CommonError::UnsupportedProfileSnapshotVersion(101))
+        );
+    }
+
+    #[test]
+    fn unsupported_past_version() {
+        assert_eq!(
+            migrator().migrate(snapshot(97), 97),
+            Err(CommonError::UnsupportedProfileSnapshotVersion(97))
+        );
+    }
+
+    #[test]
+    fn failing_migration() {
+        // Missing "nets" key
+        assert_eq!(
+            migrator().migrate(snapshot(99), 99),
+            Err(CommonError::FailedToMigrateProfileSnapshot {
+                from_version: 99,
+                to_version: 100
+            })
+        );
+    }
+
+    #[test]
+    fn profile_from_json_bytes_migrates_then_deserializes() {
+        let json = serde_json::to_vec(&snapshot(98)).unwrap();
+        assert_eq!(
+            migrator().profile_from_json_bytes(json),
+            Ok(Profile::placeholder())
+        );
+    }
+
+    #[test]
+    fn profile_from_snapshot_json_bytes_current_version() {
+        let profile = Profile::placeholder();
+        assert_eq!(
+            Profile::from_snapshot_json_bytes(
+                serde_json::to_vec(&profile).unwrap()
+            ),
+            Ok(profile)
+        );
+    }
+
+    #[test]
+    fn profile_from_snapshot_json_bytes_unsupported_version() {
+        assert_eq!(
+            Profile::from_snapshot_json_bytes(
+                serde_json::to_vec(&snapshot(101)).unwrap()
+            ),
+            Err(CommonError::UnsupportedProfileSnapshotVersion(101))
+        );
+    }
+
+    #[test]
+    fn profile_from_snapshot_json_bytes_not_json() {
+        assert_eq!(
+            Profile::from_snapshot_json_bytes([0xde, 0xad]),
+            Err(CommonError::FailedToDeserializeJSONToValue {
+                json_byte_count: 2,
+                type_name: std::any::type_name::<Profile>().to_string()
+            })
+        );
+    }
+}
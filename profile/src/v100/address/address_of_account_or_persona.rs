@@ -0,0 +1,89 @@
+use crate::prelude::*;
+
+/// The address of either an `Account` or a `Persona`, used to refer to an
+/// entity of a Profile regardless of its kind.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, EnumAsInner, uniffi::Enum)]
+pub enum AddressOfAccountOrPersona {
+    Account { address: AccountAddress },
+    Persona { address: IdentityAddress },
+}
+
+impl From<AccountAddress> for AddressOfAccountOrPersona {
+    fn from(address: AccountAddress) -> Self {
+        Self::Account { address }
+    }
+}
+
+impl From<IdentityAddress> for AddressOfAccountOrPersona {
+    fn from(address: IdentityAddress) -> Self {
+        Self::Persona { address }
+    }
+}
+
+impl AddressOfAccountOrPersona {
+    /// The network this address is tied to.
+    pub fn network_id(&self) -> NetworkID {
+        match self {
+            Self::Account { address } => address.network_id,
+            Self::Persona { address } => address.network_id,
+        }
+    }
+
+    /// The address of the same kind as this address, derived from
+    /// `public_key` on `network_id`.
+    pub fn derived_from(
+        &self,
+        public_key: PublicKey,
+        network_id: NetworkID,
+    ) -> Self {
+        match self {
+            Self::Account { .. } => {
+                AccountAddress::new(public_key, network_id).into()
+            }
+            Self::Persona { .. } => {
+                <IdentityAddress as EntityAddress>::from_public_key(
+                    public_key, network_id,
+                )
+                .into()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn from_account_address() {
+        let address = AccountAddress::placeholder();
+        assert_eq!(
+            AddressOfAccountOrPersona::from(address.clone()),
+            AddressOfAccountOrPersona::Account { address }
+        );
+    }
+
+    #[test]
+    fn network_id() {
+        assert_eq!(
+            AddressOfAccountOrPersona::from(
+                IdentityAddress::placeholder_stokenet()
+            )
+            .network_id(),
+            NetworkID::Stokenet
+        );
+    }
+
+    #[test]
+    fn derived_from() {
+        let account = Account::placeholder();
+        let EntitySecurityState::Unsecured { value: control } =
+            account.security_state;
+        let public_key = control.transaction_signing.public_key.public_key;
+        let address: AddressOfAccountOrPersona = account.address.into();
+        assert_eq!(
+            address.derived_from(public_key, NetworkID::Mainnet),
+            address
+        );
+    }
+}
@@ -1,4 +1,5 @@
 mod account_address;
+mod address_of_account_or_persona;
 mod decode_address_helper;
 mod entity_address;
 mod identity_address;
@@ -7,6 +8,7 @@ mod non_fungible_local_id;
 mod resource_address;
 
 pub use account_address::*;
+pub use address_of_account_or_persona::*;
 pub use decode_address_helper::*;
 pub use entity_address::*;
 pub use identity_address::*;
@@ -51,6 +51,20 @@ impl P2PLink {
     }
 }
 
+impl Redactable for P2PLink {
+    /// Replaces the connection password with the hash of its ID, which is
+    /// deterministic and unique per link but cannot be used to connect, and
+    /// the user chosen display name with a placeholder. The ID of the
+    /// redacted link is derived from the placeholder password, as for any
+    /// other link.
+    fn redacted(&self) -> Self {
+        Self::new(
+            RadixConnectPassword::new(hash(self.id().into_bytes()).into()),
+            "Redacted".to_string(),
+        )
+    }
+}
+
 impl P2PLink {
     pub fn to_obfuscated_string(&self) -> String {
         format!(
@@ -191,4 +205,28 @@ mod tests {
             format!("{:?}", sut.non_sensitive())
         );
     }
+
+    #[test]
+    fn redacted() {
+        let sut = P2PLink::placeholder();
+        let redacted = sut.redacted();
+        assert_ne!(redacted.connection_password, sut.connection_password);
+        assert_eq!(redacted.id(), redacted.connection_password.hash());
+        assert_ne!(redacted.id(), sut.id());
+        assert_eq!(redacted.display_name, "Redacted");
+        assert_eq!(redacted, sut.redacted());
+        assert_ne!(redacted, P2PLink::placeholder_other().redacted());
+    }
+
+    #[test]
+    fn redacted_json_roundtrip() {
+        let sut = P2PLink::placeholder().redacted();
+        assert_eq!(
+            serde_json::from_value::<P2PLink>(
+                serde_json::to_value(&sut).unwrap()
+            )
+            .unwrap(),
+            sut
+        );
+    }
 }
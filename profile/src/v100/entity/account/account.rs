@@ -97,6 +97,45 @@ impl Account {
             on_ledger_settings: OnLedgerSettings::default(),
         }
     }
+
+    /// Instantiates an account on `network_id` controlled by the factor
+    /// instance `transaction_signing`, which unlike `new` may have a BIP44
+    /// derivation path, as accounts created by the Olympia wallet have.
+    ///
+    /// Returns `Err` if `transaction_signing` has a CAP26 derivation path
+    /// with a key kind other than `TransactionSigning`.
+    pub fn with_transaction_signing_factor_instance(
+        network_id: NetworkID,
+        transaction_signing: HierarchicalDeterministicFactorInstance,
+        display_name: DisplayName,
+        appearance_id: AppearanceID,
+    ) -> Result<Self> {
+        let address = AccountAddress::new(
+            transaction_signing.public_key.public_key.clone(),
+            network_id,
+        );
+        let security_state =
+            UnsecuredEntityControl::with_transaction_signing_only(
+                transaction_signing,
+            )?;
+        Ok(Self {
+            network_id,
+            address,
+            display_name,
+            security_state: security_state.into(),
+            appearance_id,
+            flags: EntityFlags::default(),
+            on_ledger_settings: OnLedgerSettings::default(),
+        })
+    }
+}
+
+impl Redactable for Account {
+    fn redacted(&self) -> Self {
+        let mut redacted = self.clone();
+        redacted.display_name = self.display_name.redacted();
+        redacted
+    }
 }
 
 impl Identifiable for Account {
@@ -339,6 +378,22 @@ mod tests {
         assert_eq!(account.address, address);
     }
 
+    #[test]
+    fn with_transaction_signing_factor_instance() {
+        use crate::prelude::*;
+        let alice = Account::placeholder_mainnet_alice();
+        let EntitySecurityState::Unsecured { value: control } =
+            alice.security_state.clone();
+        let sut = Account::with_transaction_signing_factor_instance(
+            NetworkID::Mainnet,
+            control.transaction_signing,
+            alice.display_name.clone(),
+            alice.appearance_id,
+        )
+        .unwrap();
+        assert_eq!(sut, alice);
+    }
+
     #[test]
     fn display() {
         let account = Account::placeholder();
@@ -48,6 +48,13 @@ impl Default for DisplayName {
     }
 }
 
+impl Redactable for DisplayName {
+    /// Replaces the user chosen name with a placeholder.
+    fn redacted(&self) -> Self {
+        Self::new("Redacted").expect("Valid display name")
+    }
+}
+
 impl FromStr for DisplayName {
     type Err = CommonError;
 
@@ -71,6 +78,12 @@ mod tests {
         );
     }
 
+    #[test]
+    fn redacted() {
+        let sut = DisplayName::new("Satoshi's main").unwrap();
+        assert_eq!(sut.redacted().value, "Redacted");
+    }
+
     #[test]
     fn max_is_ok() {
         assert!(DisplayName::new("0|RDX|Dev Nano S|Some very lon").is_ok());
@@ -22,6 +22,10 @@ use crate::prelude::*;
 pub enum EntityFlag {
     /// The entity is marked as deleted by user. Entity should still be kept in Profile
     DeletedByUser,
+
+    /// The entity is hidden by user, it should not be shown in lists of
+    /// entities, but the user can unhide it at any time.
+    HiddenByUser,
 }
 
 #[cfg(test)]
@@ -35,11 +39,17 @@ mod tests {
             json!("deletedByUser"),
         );
         assert_json_roundtrip(&EntityFlag::DeletedByUser);
+        assert_json_value_eq_after_roundtrip(
+            &EntityFlag::HiddenByUser,
+            json!("hiddenByUser"),
+        );
+        assert_json_roundtrip(&EntityFlag::HiddenByUser);
     }
 
     #[test]
     fn display() {
         assert_eq!(format!("{}", EntityFlag::DeletedByUser), "DeletedByUser");
+        assert_eq!(format!("{}", EntityFlag::HiddenByUser), "HiddenByUser");
     }
 
     #[test]
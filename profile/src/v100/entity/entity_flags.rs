@@ -52,6 +52,24 @@ impl EntityFlags {
     }
 }
 
+impl EntityFlags {
+    /// Whether the entity is marked as deleted by the user.
+    pub fn is_deleted(&self) -> bool {
+        self.contains(&EntityFlag::DeletedByUser)
+    }
+
+    /// Whether the entity is hidden by the user, an entity which is deleted
+    /// by the user is not considered hidden, since it cannot be unhidden.
+    pub fn is_hidden(&self) -> bool {
+        self.contains(&EntityFlag::HiddenByUser) && !self.is_deleted()
+    }
+
+    /// Whether the entity is neither hidden nor deleted by the user.
+    pub fn is_visible(&self) -> bool {
+        !self.contains(&EntityFlag::HiddenByUser) && !self.is_deleted()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::prelude::*;
@@ -105,6 +123,27 @@ mod tests {
         assert!(sut.contains(&EntityFlag::DeletedByUser));
     }
 
+    #[test]
+    fn visibility() {
+        let visible = EntityFlags::default();
+        assert!(visible.is_visible());
+        assert!(!visible.is_hidden());
+        assert!(!visible.is_deleted());
+
+        let hidden = EntityFlags::with_flag(EntityFlag::HiddenByUser);
+        assert!(!hidden.is_visible());
+        assert!(hidden.is_hidden());
+        assert!(!hidden.is_deleted());
+
+        let deleted = EntityFlags::with_flags([
+            EntityFlag::HiddenByUser,
+            EntityFlag::DeletedByUser,
+        ]);
+        assert!(!deleted.is_visible());
+        assert!(!deleted.is_hidden());
+        assert!(deleted.is_deleted());
+    }
+
     #[test]
     fn json_roundtrip_non_empty() {
         let model = EntityFlags::with_flag(EntityFlag::DeletedByUser);
@@ -287,6 +287,15 @@ impl Persona {
     }
 }
 
+impl Redactable for Persona {
+    fn redacted(&self) -> Self {
+        let mut redacted = self.clone();
+        redacted.display_name = self.display_name.redacted();
+        redacted.persona_data = self.persona_data.redacted();
+        redacted
+    }
+}
+
 impl Ord for Persona {
     fn cmp(&self, other: &Self) -> Ordering {
         match (&self.security_state, &other.security_state) {
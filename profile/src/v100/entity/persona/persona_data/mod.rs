@@ -1,11 +1,13 @@
 mod entry_kinds;
 mod persona_data;
+mod persona_data_entry;
 mod persona_data_entry_id;
 mod persona_data_identified_collection_types;
 mod persona_data_identified_entry_types;
 
 pub use entry_kinds::*;
 pub use persona_data::*;
+pub use persona_data_entry::*;
 pub use persona_data_entry_id::*;
 pub use persona_data_identified_collection_types::*;
 pub use persona_data_identified_entry_types::*;
@@ -134,6 +134,56 @@ impl PersonaData {
     }
 }
 
+impl Redactable for PersonaData {
+    /// Replaces the name, phone numbers and email addresses with placeholders,
+    /// keeping the IDs of all entries, the variant of the name and whether
+    /// the nickname is empty or not.
+    fn redacted(&self) -> Self {
+        let name = self.name.as_ref().map(|n| {
+            let nickname = if n.value.nickname.is_empty() {
+                ""
+            } else {
+                "Redacted"
+            };
+            PersonaDataIdentifiedName::with_id(
+                n.id.clone(),
+                PersonaDataEntryName::new(
+                    n.value.variant.clone(),
+                    "Redacted",
+                    "Redacted",
+                    nickname,
+                )
+                .expect("Redacted name should be valid."),
+            )
+        });
+        let phone_numbers = CollectionOfPhoneNumbers::entries(
+            self.phone_numbers.iter().enumerate().map(|(i, p)| {
+                PersonaDataIdentifiedPhoneNumber::with_id(
+                    p.id.clone(),
+                    PersonaDataEntryPhoneNumber::new(format!(
+                        "+00 000 000 {:03}",
+                        i
+                    ))
+                    .expect("Redacted phone number should be valid."),
+                )
+            }),
+        );
+        let email_addresses = CollectionOfEmailAddresses::entries(
+            self.email_addresses.iter().enumerate().map(|(i, e)| {
+                PersonaDataIdentifiedEmailAddress::with_id(
+                    e.id.clone(),
+                    PersonaDataEntryEmailAddress::new(format!(
+                        "redacted{}@example.com",
+                        i
+                    ))
+                    .expect("Redacted email address should be valid."),
+                )
+            }),
+        );
+        Self::new(name, phone_numbers, email_addresses)
+    }
+}
+
 impl HasPlaceholder for PersonaData {
     fn placeholder() -> Self {
         Self::new(
@@ -265,4 +315,37 @@ mod tests {
     fn debug() {
         assert_eq!(format!("{:?}", PersonaData::placeholder()), "name: Bruce Batman Wayne\nphone: +46123456789 - 00000000-0000-0000-0000-000000000001\nphone: +44987654321 - 00000000-0000-0000-0000-000000000002\nemail: alan@turing.hero - 00000000-0000-0000-0000-000000000001\nemail: satoshi@nakamoto.btc - 00000000-0000-0000-0000-000000000002");
     }
+
+    #[test]
+    fn redacted() {
+        let sut = PersonaData::placeholder();
+        let redacted = sut.redacted();
+        assert_eq!(
+            redacted.name.as_ref().map(|n| n.id.clone()),
+            sut.name.as_ref().map(|n| n.id.clone())
+        );
+        assert_eq!(
+            redacted.name.unwrap().value.family_name,
+            "Redacted".to_owned()
+        );
+        assert_eq!(
+            redacted
+                .phone_numbers
+                .iter()
+                .map(|p| p.id.clone())
+                .collect_vec(),
+            sut.phone_numbers.iter().map(|p| p.id.clone()).collect_vec()
+        );
+        assert_eq!(
+            redacted
+                .email_addresses
+                .iter()
+                .map(|e| e.value.email.clone())
+                .collect_vec(),
+            (0..sut.email_addresses.len())
+                .map(|i| format!("redacted{}@example.com", i))
+                .collect_vec()
+        );
+        assert_eq!(sut.redacted(), sut.redacted().redacted());
+    }
 }
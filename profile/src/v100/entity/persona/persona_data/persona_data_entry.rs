@@ -0,0 +1,240 @@
+use crate::prelude::*;
+
+/// The value of a single PersonaData Entry of any kind, without its ID, used
+/// to add or update an entry of a Persona's `PersonaData`.
+#[derive(
+    Clone, Debug, PartialEq, Eq, Hash, derive_more::Display, uniffi::Enum,
+)]
+pub enum PersonaDataEntry {
+    #[display("{value}")]
+    Name { value: PersonaDataEntryName },
+
+    #[display("{value}")]
+    PhoneNumber { value: PersonaDataEntryPhoneNumber },
+
+    #[display("{value}")]
+    EmailAddress { value: PersonaDataEntryEmailAddress },
+}
+
+impl From<PersonaDataEntryName> for PersonaDataEntry {
+    fn from(value: PersonaDataEntryName) -> Self {
+        Self::Name { value }
+    }
+}
+
+impl From<PersonaDataEntryPhoneNumber> for PersonaDataEntry {
+    fn from(value: PersonaDataEntryPhoneNumber) -> Self {
+        Self::PhoneNumber { value }
+    }
+}
+
+impl From<PersonaDataEntryEmailAddress> for PersonaDataEntry {
+    fn from(value: PersonaDataEntryEmailAddress) -> Self {
+        Self::EmailAddress { value }
+    }
+}
+
+impl PersonaData {
+    /// Adds `entry` under a newly generated ID, which is returned.
+    ///
+    /// Returns `Err` if `entry` is a name and this PersonaData already has a
+    /// name, which instead should be updated using `update_entry`.
+    pub fn add_entry(
+        &mut self,
+        entry: impl Into<PersonaDataEntry>,
+    ) -> Result<PersonaDataEntryID> {
+        match entry.into() {
+            PersonaDataEntry::Name { value } => {
+                if self.name.is_some() {
+                    return Err(CommonError::PersonaDataNameAlreadyPresent);
+                }
+                let name = PersonaDataIdentifiedName::new(value);
+                let id = name.id.clone();
+                self.name = Some(name);
+                Ok(id)
+            }
+            PersonaDataEntry::PhoneNumber { value } => {
+                Ok(self.phone_numbers.add_value(value))
+            }
+            PersonaDataEntry::EmailAddress { value } => {
+                Ok(self.email_addresses.add_value(value))
+            }
+        }
+    }
+
+    /// Replaces the value of the entry with `id` with `entry`, keeping the
+    /// ID, so that dApps with ongoing access to the entry get the new value.
+    ///
+    /// Returns `Err` if there is no entry of the same kind as `entry` with `id`.
+    pub fn update_entry(
+        &mut self,
+        id: &PersonaDataEntryID,
+        entry: impl Into<PersonaDataEntry>,
+    ) -> Result<()> {
+        let is_updated = match entry.into() {
+            PersonaDataEntry::Name { value } => match self.name.as_mut() {
+                Some(name) if name.id == *id => {
+                    name.value = value;
+                    true
+                }
+                _ => false,
+            },
+            PersonaDataEntry::PhoneNumber { value } => {
+                self.phone_numbers.update_value(id, value)
+            }
+            PersonaDataEntry::EmailAddress { value } => {
+                self.email_addresses.update_value(id, value)
+            }
+        };
+        if is_updated {
+            Ok(())
+        } else {
+            Err(CommonError::UnknownPersonaDataEntry(id.clone()))
+        }
+    }
+
+    /// Removes the entry with `id`, of any kind. If the same value is later
+    /// added again it gets a new ID, see the doc comment of `PersonaData`.
+    ///
+    /// Returns `Err` if there is no entry with `id`.
+    pub fn remove_entry(&mut self, id: &PersonaDataEntryID) -> Result<()> {
+        if self.name.as_ref().is_some_and(|n| n.id == *id) {
+            self.name = None;
+            return Ok(());
+        }
+        if self.phone_numbers.remove_entry(id)
+            || self.email_addresses.remove_entry(id)
+        {
+            return Ok(());
+        }
+        Err(CommonError::UnknownPersonaDataEntry(id.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[allow(clippy::upper_case_acronyms)]
+    type SUT = PersonaData;
+
+    /// Unlike `PersonaData::placeholder()`, whose entries of different kinds
+    /// share IDs, every entry has a unique ID.
+    fn sut() -> SUT {
+        SUT::new(
+            PersonaDataIdentifiedName::new(PersonaDataEntryName::placeholder()),
+            CollectionOfPhoneNumbers::values([
+                PersonaDataEntryPhoneNumber::placeholder(),
+            ]),
+            CollectionOfEmailAddresses::values([
+                PersonaDataEntryEmailAddress::placeholder(),
+            ]),
+        )
+    }
+
+    #[test]
+    fn add_name() {
+        let mut sut = SUT::default();
+        let value = PersonaDataEntryName::placeholder();
+        let id = sut.add_entry(value.clone()).unwrap();
+        assert_eq!(sut.name.as_ref().unwrap().id, id);
+        assert_eq!(sut.name.unwrap().value, value);
+    }
+
+    #[test]
+    fn add_name_fails_if_present() {
+        let original = sut();
+        let mut sut = original.clone();
+        assert_eq!(
+            sut.add_entry(PersonaDataEntryName::placeholder_other()),
+            Err(CommonError::PersonaDataNameAlreadyPresent)
+        );
+        assert_eq!(sut, original);
+    }
+
+    #[test]
+    fn add_phone_number_and_email_address() {
+        let mut sut = sut();
+        let phone = PersonaDataEntryPhoneNumber::placeholder();
+        let email = PersonaDataEntryEmailAddress::placeholder();
+        let phone_id = sut.add_entry(phone.clone()).unwrap();
+        let email_id = sut.add_entry(email.clone()).unwrap();
+        assert_ne!(phone_id, email_id);
+        assert_eq!(sut.phone_numbers.get(&phone_id).unwrap().value, phone);
+        assert_eq!(sut.email_addresses.get(&email_id).unwrap().value, email);
+    }
+
+    #[test]
+    fn update_keeps_id() {
+        let mut sut = sut();
+        let name_id = sut.name.clone().unwrap().id;
+        let name = PersonaDataEntryName::placeholder_other();
+        sut.update_entry(&name_id, name.clone()).unwrap();
+        assert_eq!(
+            sut.name.clone().unwrap(),
+            PersonaDataIdentifiedName::with_id(name_id, name)
+        );
+
+        let phone_id = sut.phone_numbers.items()[0].id.clone();
+        let phone = PersonaDataEntryPhoneNumber::placeholder_other();
+        sut.update_entry(&phone_id, phone.clone()).unwrap();
+        assert_eq!(sut.phone_numbers.get(&phone_id).unwrap().value, phone);
+
+        let email_id = sut.email_addresses.items()[0].id.clone();
+        let email = PersonaDataEntryEmailAddress::placeholder_other();
+        sut.update_entry(&email_id, email.clone()).unwrap();
+        assert_eq!(sut.email_addresses.get(&email_id).unwrap().value, email);
+    }
+
+    #[test]
+    fn update_unknown_or_wrong_kind_fails() {
+        let original = sut();
+        let mut sut = original.clone();
+        let name_id = sut.name.clone().unwrap().id;
+        assert_eq!(
+            sut.update_entry(
+                &name_id,
+                PersonaDataEntryPhoneNumber::placeholder()
+            ),
+            Err(CommonError::UnknownPersonaDataEntry(name_id))
+        );
+        let unknown = PersonaDataEntryID::generate();
+        assert_eq!(
+            sut.update_entry(&unknown, PersonaDataEntryName::placeholder()),
+            Err(CommonError::UnknownPersonaDataEntry(unknown))
+        );
+        assert_eq!(sut, original);
+    }
+
+    #[test]
+    fn remove_then_add_again_gets_new_id() {
+        let mut sut = sut();
+        let phone = sut.phone_numbers.items()[0].clone();
+        sut.remove_entry(&phone.id).unwrap();
+        assert!(!sut.phone_numbers.contains_id(&phone.id));
+
+        let id = sut.add_entry(phone.value.clone()).unwrap();
+        assert_ne!(id, phone.id);
+    }
+
+    #[test]
+    fn remove_name() {
+        let mut sut = sut();
+        let name_id = sut.name.clone().unwrap().id;
+        sut.remove_entry(&name_id).unwrap();
+        assert_eq!(sut.name, None);
+        assert_eq!(
+            sut.remove_entry(&name_id),
+            Err(CommonError::UnknownPersonaDataEntry(name_id))
+        );
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            PersonaDataEntry::from(PersonaDataEntryPhoneNumber::placeholder())
+                .to_string(),
+            "+46123456789"
+        );
+    }
+}
@@ -70,6 +70,36 @@ macro_rules! declare_collection_of_identified_entry {
             }
         }
 
+        impl $struct_name {
+            /// Appends `value` in a new entry with a newly generated ID,
+            /// which is returned.
+            pub fn add_value(
+                &mut self,
+                value: <$id_ent_type as PersonaDataEntryValue>::Value,
+            ) -> PersonaDataEntryID {
+                let entry: $id_ent_type = value.into();
+                let id = entry.id.clone();
+                self.collection.append(entry);
+                id
+            }
+
+            /// Replaces the value of the entry with `id`, keeping its ID,
+            /// returns `false` if there is no entry with `id`.
+            pub fn update_value(
+                &mut self,
+                id: &PersonaDataEntryID,
+                value: <$id_ent_type as PersonaDataEntryValue>::Value,
+            ) -> bool {
+                self.collection.update_with(id, |e| e.value = value.clone())
+            }
+
+            /// Removes the entry with `id`, returns `false` if there is no
+            /// entry with `id`.
+            pub fn remove_entry(&mut self, id: &PersonaDataEntryID) -> bool {
+                self.collection.remove_by_id(id).is_some()
+            }
+        }
+
         impl $struct_name {
             fn display_string(&self) -> String {
                 let items =
@@ -159,6 +189,36 @@ mod collection_of_phone_numbers_tests {
         );
     }
 
+    #[test]
+    fn add_value() {
+        let mut sut = SUT::placeholder_other();
+        let value = PersonaDataEntryPhoneNumber::placeholder();
+        let id = sut.add_value(value.clone());
+        assert_eq!(sut.len(), 2);
+        assert_eq!(sut.get(&id).unwrap().value, value);
+    }
+
+    #[test]
+    fn update_value_keeps_id() {
+        let mut sut = SUT::placeholder();
+        let id = PersonaDataEntryID::placeholder_one();
+        let value = PersonaDataEntryPhoneNumber::placeholder_other();
+        assert!(sut.update_value(&id, value.clone()));
+        assert_eq!(sut.get(&id).unwrap().value, value);
+        assert_eq!(sut.len(), 2);
+        assert!(
+            !sut.update_value(&PersonaDataEntryID::placeholder_three(), value)
+        );
+    }
+
+    #[test]
+    fn remove_entry() {
+        let mut sut = SUT::placeholder();
+        assert!(sut.remove_entry(&PersonaDataEntryID::placeholder_one()));
+        assert_eq!(sut, SUT::placeholder_other());
+        assert!(!sut.remove_entry(&PersonaDataEntryID::placeholder_one()));
+    }
+
     #[test]
     fn display() {
         assert_eq!(
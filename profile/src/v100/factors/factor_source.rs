@@ -43,6 +43,39 @@ impl BaseIsFactorSource for FactorSource {
     }
 }
 
+impl Redactable for FactorSource {
+    /// Replaces the user chosen name of the device or Ledger with a placeholder.
+    fn redacted(&self) -> Self {
+        let mut redacted = self.clone();
+        match &mut redacted {
+            FactorSource::Device { value } => {
+                value.hint.name = "Redacted".to_string()
+            }
+            FactorSource::Ledger { value } => {
+                value.hint.name = "Redacted".to_string()
+            }
+        }
+        redacted
+    }
+}
+
+impl FactorSource {
+    /// The properties common to all kinds of FactorSources.
+    pub fn common_properties(&self) -> &FactorSourceCommon {
+        match self {
+            FactorSource::Device { value } => &value.common,
+            FactorSource::Ledger { value } => &value.common,
+        }
+    }
+
+    pub(crate) fn common_properties_mut(&mut self) -> &mut FactorSourceCommon {
+        match self {
+            FactorSource::Device { value } => &mut value.common,
+            FactorSource::Ledger { value } => &mut value.common,
+        }
+    }
+}
+
 impl From<DeviceFactorSource> for FactorSource {
     fn from(value: DeviceFactorSource) -> Self {
         FactorSource::Device { value }
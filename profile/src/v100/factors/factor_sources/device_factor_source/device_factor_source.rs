@@ -32,6 +32,12 @@ pub struct DeviceFactorSource {
 
     /// Properties describing a DeviceFactorSource to help user disambiguate between it and another one.
     pub hint: DeviceFactorSourceHint,
+
+    /// When the user confirmed having written down the mnemonic of this
+    /// factor source, by passing a `MnemonicBackupQuiz`, `None` if the user
+    /// has not yet done so.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mnemonic_backed_up_on: Option<Timestamp>,
 }
 
 impl TryFrom<FactorSource> for DeviceFactorSource {
@@ -65,7 +71,12 @@ impl DeviceFactorSource {
         common: FactorSourceCommon,
         hint: DeviceFactorSourceHint,
     ) -> Self {
-        Self { id, common, hint }
+        Self {
+            id,
+            common,
+            hint,
+            mnemonic_backed_up_on: None,
+        }
     }
 
     pub fn babylon(
@@ -92,6 +103,11 @@ impl DeviceFactorSource {
     pub fn is_main_bdfs(&self) -> bool {
         self.common.is_main_bdfs()
     }
+
+    /// Checks if the user has confirmed having written down the mnemonic.
+    pub fn is_mnemonic_backed_up(&self) -> bool {
+        self.mnemonic_backed_up_on.is_some()
+    }
 }
 
 impl HasPlaceholder for DeviceFactorSource {
@@ -190,6 +206,41 @@ mod tests {
         );
     }
 
+    #[test]
+    fn json_mnemonic_backed_up_on() {
+        let mut model = DeviceFactorSource::placeholder();
+        assert!(!model.is_mnemonic_backed_up());
+        model.mnemonic_backed_up_on =
+            Some(Timestamp::parse("2024-01-31T14:23:45.000Z").unwrap());
+        assert!(model.is_mnemonic_backed_up());
+        assert_eq_after_json_roundtrip(
+            &model,
+            r#"
+            {
+                "common": {
+                    "addedOn": "2023-09-11T16:05:56.000Z",
+                    "cryptoParameters": {
+                        "supportedCurves": ["curve25519"],
+                        "supportedDerivationPathSchemes": ["cap26"]
+                    },
+                    "flags": ["main"],
+                    "lastUsedOn": "2023-09-11T16:05:56.000Z"
+                },
+                "hint": {
+                    "mnemonicWordCount": 24,
+                    "model": "iPhone",
+                    "name": "Unknown Name"
+                },
+                "id": {
+                    "body": "3c986ebf9dcd9167a97036d3b2c997433e85e6cc4e4422ad89269dac7bfea240",
+                    "kind": "device"
+                },
+                "mnemonicBackedUpOn": "2024-01-31T14:23:45.000Z"
+            }
+            "#,
+        );
+    }
+
     #[test]
     fn factor_source_id() {
         let sut = DeviceFactorSource::placeholder();
@@ -0,0 +1,177 @@
+use crate::prelude::*;
+
+use rand::{rngs::OsRng, seq::index::sample};
+
+/// A quiz asking the user for the words at some random positions of the
+/// mnemonic of a `DeviceFactorSource`, used to confirm that the user has
+/// written down the mnemonic before marking it as backed up.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, uniffi::Record)]
+pub struct MnemonicBackupQuiz {
+    /// The ID of the `DeviceFactorSource` whose mnemonic the quiz is about.
+    pub factor_source_id: FactorSourceIDFromHash,
+
+    /// The zero-based positions of the words the user is asked for, distinct
+    /// and in ascending order.
+    pub word_indices: Vec<u16>,
+}
+
+impl MnemonicBackupQuiz {
+    /// The number of words the user is asked for.
+    pub const QUESTION_COUNT: usize = 4;
+
+    /// The number of words the user must answer correctly for the mnemonic
+    /// to be marked as backed up.
+    pub const REQUIRED_CORRECT_ANSWER_COUNT: usize = Self::QUESTION_COUNT;
+
+    /// Instantiates a new quiz with `word_indices`, which are sorted and
+    /// deduplicated.
+    pub fn with_word_indices<I>(
+        factor_source_id: FactorSourceIDFromHash,
+        word_indices: I,
+    ) -> Self
+    where
+        I: IntoIterator<Item = u16>,
+    {
+        Self {
+            factor_source_id,
+            word_indices: word_indices.into_iter().sorted().dedup().collect(),
+        }
+    }
+
+    /// Instantiates a new quiz about `mnemonic`, asking for the words at
+    /// `QUESTION_COUNT` positions picked using a cryptographically secure
+    /// random generator.
+    pub fn new(
+        factor_source_id: FactorSourceIDFromHash,
+        mnemonic: &Mnemonic,
+    ) -> Self {
+        let word_count = mnemonic.words.len();
+        let indices = sample(
+            &mut OsRng,
+            word_count,
+            Self::QUESTION_COUNT.min(word_count),
+        );
+        Self::with_word_indices(
+            factor_source_id,
+            indices.into_iter().map(|i| i as u16),
+        )
+    }
+
+    /// The number of `answers` matching the words of `mnemonic` at the
+    /// positions of this quiz, where `answers` are in the same order as
+    /// `word_indices`. Answers are compared ignoring case and surrounding
+    /// whitespace.
+    pub fn correct_answer_count(
+        &self,
+        mnemonic: &Mnemonic,
+        answers: &[String],
+    ) -> usize {
+        self.word_indices
+            .iter()
+            .unique()
+            .zip(answers)
+            .filter(|(index, answer)| {
+                mnemonic
+                    .words
+                    .get(**index as usize)
+                    .is_some_and(|w| w.word == answer.trim().to_lowercase())
+            })
+            .count()
+    }
+
+    /// Checks if enough of `answers` are correct for the mnemonic to be
+    /// marked as backed up, see `correct_answer_count`.
+    pub fn is_passed(&self, mnemonic: &Mnemonic, answers: &[String]) -> bool {
+        self.correct_answer_count(mnemonic, answers)
+            >= Self::REQUIRED_CORRECT_ANSWER_COUNT
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    fn mnemonic() -> Mnemonic {
+        Mnemonic::placeholder()
+    }
+
+    fn quiz() -> MnemonicBackupQuiz {
+        MnemonicBackupQuiz::with_word_indices(
+            FactorSourceIDFromHash::placeholder(),
+            [23, 0, 5, 0, 11],
+        )
+    }
+
+    fn answers(words: &[&str]) -> Vec<String> {
+        words.iter().map(|w| w.to_string()).collect()
+    }
+
+    #[test]
+    fn with_word_indices_sorted_and_distinct() {
+        assert_eq!(quiz().word_indices, vec![0, 5, 11, 23]);
+    }
+
+    #[test]
+    fn new_picks_distinct_indices_within_mnemonic() {
+        let sut = MnemonicBackupQuiz::new(
+            FactorSourceIDFromHash::placeholder(),
+            &mnemonic(),
+        );
+        assert_eq!(sut.word_indices.len(), MnemonicBackupQuiz::QUESTION_COUNT);
+        assert!(sut.word_indices.iter().all(|i| *i < 24));
+        assert_eq!(
+            sut.word_indices.iter().unique().count(),
+            MnemonicBackupQuiz::QUESTION_COUNT
+        );
+    }
+
+    #[test]
+    fn new_is_random() {
+        let n = 10;
+        let quizzes = (0..n)
+            .map(|_| {
+                MnemonicBackupQuiz::new(
+                    FactorSourceIDFromHash::placeholder(),
+                    &mnemonic(),
+                )
+            })
+            .collect::<HashSet<_>>();
+        assert!(quizzes.len() > 1);
+    }
+
+    #[test]
+    fn all_correct_passes() {
+        let answers = answers(&["bright", "pull", "woman", "mandate"]);
+        assert_eq!(quiz().correct_answer_count(&mnemonic(), &answers), 4);
+        assert!(quiz().is_passed(&mnemonic(), &answers));
+    }
+
+    #[test]
+    fn answers_ignore_case_and_whitespace() {
+        let answers = answers(&[" Bright", "PULL ", "woman\n", "mandate"]);
+        assert!(quiz().is_passed(&mnemonic(), &answers));
+    }
+
+    #[test]
+    fn wrong_answer_fails() {
+        let answers = answers(&["bright", "pull", "woman", "zoo"]);
+        assert_eq!(quiz().correct_answer_count(&mnemonic(), &answers), 3);
+        assert!(!quiz().is_passed(&mnemonic(), &answers));
+    }
+
+    #[test]
+    fn too_few_answers_fails() {
+        let answers = answers(&["bright", "pull", "woman"]);
+        assert!(!quiz().is_passed(&mnemonic(), &answers));
+    }
+
+    #[test]
+    fn index_out_of_bounds_is_incorrect() {
+        let sut = MnemonicBackupQuiz::with_word_indices(
+            FactorSourceIDFromHash::placeholder(),
+            [0, 1, 2, 99],
+        );
+        let answers = answers(&["bright", "club", "bacon", "zoo"]);
+        assert_eq!(sut.correct_answer_count(&mnemonic(), &answers), 3);
+    }
+}
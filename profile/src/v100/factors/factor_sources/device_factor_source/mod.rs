@@ -1,7 +1,9 @@
 mod device_factor_source;
 mod device_factor_source_hint;
+mod mnemonic_backup_quiz;
 mod wallet_client_model;
 
 pub use device_factor_source::*;
 pub use device_factor_source_hint::*;
+pub use mnemonic_backup_quiz::*;
 pub use wallet_client_model::*;
@@ -43,6 +43,23 @@ impl Accounts {
     pub fn get_all(&self) -> Vec<&Account> {
         self.elements()
     }
+
+    /// Returns references to the accounts which are neither hidden nor
+    /// deleted by the user.
+    pub fn get_visible(&self) -> Vec<&Account> {
+        self.get_all()
+            .into_iter()
+            .filter(|x| x.flags.is_visible())
+            .collect()
+    }
+
+    /// Returns references to the accounts hidden by the user.
+    pub fn get_hidden(&self) -> Vec<&Account> {
+        self.get_all()
+            .into_iter()
+            .filter(|x| x.flags.is_hidden())
+            .collect()
+    }
 }
 
 impl HasPlaceholder for Accounts {
@@ -119,6 +136,22 @@ mod tests {
         assert_eq!(Accounts::placeholder().get_all().len(), 2);
     }
 
+    #[test]
+    fn get_visible_and_hidden() {
+        let mut carol = Account::placeholder_mainnet_carol();
+        carol.flags.insert_flag(EntityFlag::HiddenByUser);
+        let alice = Account::placeholder_mainnet_alice();
+        let bob = Account::placeholder_mainnet_bob(); // deleted by user
+        let sut = Accounts::with_accounts([
+            alice.clone(),
+            bob.clone(),
+            carol.clone(),
+        ]);
+        assert_eq!(sut.get_visible(), vec![&alice]);
+        assert_eq!(sut.get_hidden(), vec![&carol]);
+        assert_eq!(sut.get_all(), vec![&alice, &bob, &carol]);
+    }
+
     #[test]
     fn get_by_address() {
         let address = AccountAddress::placeholder();
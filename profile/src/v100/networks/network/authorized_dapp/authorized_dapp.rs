@@ -135,6 +135,19 @@ impl AuthorizedDapp {
         Self::placeholder_stokenet_sandbox()
     }
 }
+impl Redactable for AuthorizedDapp {
+    /// Replaces the display name and the history of logins, keeping the
+    /// dapp definition address since it is needed to reproduce issues.
+    fn redacted(&self) -> Self {
+        let mut redacted = self.clone();
+        redacted.display_name =
+            self.display_name.as_ref().map(|_| "Redacted".to_owned());
+        redacted.references_to_authorized_personas =
+            self.references_to_authorized_personas.redacted();
+        redacted
+    }
+}
+
 impl HasPlaceholder for AuthorizedDapp {
     fn placeholder() -> Self {
         Self::placeholder_mainnet_dashboard()
@@ -447,4 +460,28 @@ mod tests {
             "#,
         );
     }
+
+    #[test]
+    fn redacted() {
+        let sut = AuthorizedDapp::placeholder();
+        let redacted = sut.redacted();
+        assert_eq!(redacted.id(), sut.id());
+        assert_eq!(redacted.display_name, Some("Redacted".to_owned()));
+        assert!(redacted
+            .references_to_authorized_personas
+            .iter()
+            .all(|r| r.last_login
+                == Timestamp::parse("1970-01-01T00:00:00Z").unwrap()));
+        assert_eq!(
+            redacted
+                .references_to_authorized_personas
+                .iter()
+                .map(|r| r.id())
+                .collect_vec(),
+            sut.references_to_authorized_personas
+                .iter()
+                .map(|r| r.id())
+                .collect_vec()
+        );
+    }
 }
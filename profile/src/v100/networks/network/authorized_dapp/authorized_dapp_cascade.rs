@@ -0,0 +1,402 @@
+use crate::prelude::*;
+
+/// A change made to an `AuthorizedDapp` to keep its references valid after
+/// a Persona, Account or PersonaData entry it referenced was deleted,
+/// reported back to the caller of the deleting operation.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, EnumAsInner, uniffi::Enum)]
+pub enum AuthorizedDappCascade {
+    /// The reference to the deleted Persona was removed from the Dapp.
+    PersonaReferenceRemoved {
+        dapp_definition_address: DappDefinitionAddress,
+        identity_address: IdentityAddress,
+    },
+
+    /// The deleted Account was removed from the Accounts the Persona with
+    /// `identity_address` shares with the Dapp.
+    SharedAccountRemoved {
+        dapp_definition_address: DappDefinitionAddress,
+        identity_address: IdentityAddress,
+        account_address: AccountAddress,
+    },
+
+    /// The removed PersonaData entry was removed from the entries the
+    /// Persona with `identity_address` shares with the Dapp.
+    SharedPersonaDataEntryRemoved {
+        dapp_definition_address: DappDefinitionAddress,
+        identity_address: IdentityAddress,
+        entry_id: PersonaDataEntryID,
+    },
+
+    /// A list of ids shared by the Persona with `identity_address` no longer
+    /// fulfilled the quantity requested by the Dapp, so the request was
+    /// downgraded from `from` to `to`.
+    RequestedQuantityDowngraded {
+        dapp_definition_address: DappDefinitionAddress,
+        identity_address: IdentityAddress,
+        from: RequestedQuantity,
+        to: RequestedQuantity,
+    },
+
+    /// The Dapp had no authorized Personas left and was removed.
+    AuthorizedDappRemoved {
+        dapp_definition_address: DappDefinitionAddress,
+    },
+}
+
+impl AuthorizedDappCascade {
+    /// The address of the Dapp which was changed.
+    pub fn dapp_definition_address(&self) -> &DappDefinitionAddress {
+        match self {
+            Self::PersonaReferenceRemoved {
+                dapp_definition_address,
+                ..
+            }
+            | Self::SharedAccountRemoved {
+                dapp_definition_address,
+                ..
+            }
+            | Self::SharedPersonaDataEntryRemoved {
+                dapp_definition_address,
+                ..
+            }
+            | Self::RequestedQuantityDowngraded {
+                dapp_definition_address,
+                ..
+            }
+            | Self::AuthorizedDappRemoved {
+                dapp_definition_address,
+            } => dapp_definition_address,
+        }
+    }
+}
+
+impl AuthorizedDapp {
+    /// Removes the reference to the Persona with `identity_address`, if any.
+    pub fn remove_persona_reference(
+        &mut self,
+        identity_address: &IdentityAddress,
+    ) -> Vec<AuthorizedDappCascade> {
+        if self
+            .references_to_authorized_personas
+            .remove_by_id(identity_address)
+            .is_none()
+        {
+            return Vec::new();
+        }
+        vec![AuthorizedDappCascade::PersonaReferenceRemoved {
+            dapp_definition_address: self.dapp_definition_address.clone(),
+            identity_address: identity_address.clone(),
+        }]
+    }
+
+    /// Removes the Account with `account_address` from the Accounts shared
+    /// by every Persona, downgrading requests no longer fulfilled.
+    pub fn remove_shared_account(
+        &mut self,
+        account_address: &AccountAddress,
+    ) -> Vec<AuthorizedDappCascade> {
+        let dapp_definition_address = self.dapp_definition_address.clone();
+        let identity_addresses = self
+            .references_to_authorized_personas
+            .iter()
+            .map(|p| p.id())
+            .collect_vec();
+
+        let mut cascades = Vec::new();
+        for identity_address in identity_addresses {
+            self.references_to_authorized_personas.update_with(
+                &identity_address,
+                |persona| {
+                    let Some(shared) = persona.shared_accounts.as_mut() else {
+                        return;
+                    };
+                    let from = shared.request.clone();
+                    if !shared.remove_id(account_address) {
+                        return;
+                    }
+                    cascades.push(
+                        AuthorizedDappCascade::SharedAccountRemoved {
+                            dapp_definition_address: dapp_definition_address
+                                .clone(),
+                            identity_address: identity_address.clone(),
+                            account_address: account_address.clone(),
+                        },
+                    );
+                    if shared.request != from {
+                        cascades.push(
+                            AuthorizedDappCascade::RequestedQuantityDowngraded {
+                                dapp_definition_address:
+                                    dapp_definition_address.clone(),
+                                identity_address: identity_address.clone(),
+                                from,
+                                to: shared.request.clone(),
+                            },
+                        );
+                    }
+                },
+            );
+        }
+        cascades
+    }
+
+    /// Removes the PersonaData entry with `entry_id` from the entries shared
+    /// by the Persona with `identity_address`, downgrading requests no
+    /// longer fulfilled.
+    pub fn remove_shared_persona_data_entry(
+        &mut self,
+        identity_address: &IdentityAddress,
+        entry_id: &PersonaDataEntryID,
+    ) -> Vec<AuthorizedDappCascade> {
+        let dapp_definition_address = self.dapp_definition_address.clone();
+        let mut cascades = Vec::new();
+        self.references_to_authorized_personas.update_with(
+            identity_address,
+            |persona| {
+                let shared = &mut persona.shared_persona_data;
+                let mut is_removed = false;
+                if shared.name.as_ref() == Some(entry_id) {
+                    shared.name = None;
+                    is_removed = true;
+                }
+
+                let mut downgrades = Vec::new();
+                for ids in [
+                    shared.email_addresses.as_mut(),
+                    shared.phone_numbers.as_mut(),
+                ]
+                .into_iter()
+                .flatten()
+                {
+                    let from = ids.request.clone();
+                    if ids.remove_id(entry_id) {
+                        is_removed = true;
+                        if ids.request != from {
+                            downgrades.push(
+                                AuthorizedDappCascade::RequestedQuantityDowngraded {
+                                    dapp_definition_address:
+                                        dapp_definition_address.clone(),
+                                    identity_address: identity_address.clone(),
+                                    from,
+                                    to: ids.request.clone(),
+                                },
+                            );
+                        }
+                    }
+                }
+
+                if is_removed {
+                    cascades.push(
+                        AuthorizedDappCascade::SharedPersonaDataEntryRemoved {
+                            dapp_definition_address: dapp_definition_address
+                                .clone(),
+                            identity_address: identity_address.clone(),
+                            entry_id: entry_id.clone(),
+                        },
+                    );
+                }
+                cascades.extend(downgrades);
+            },
+        );
+        cascades
+    }
+}
+
+impl ProfileNetwork {
+    /// Applies `cascade` to every AuthorizedDapp on this network, removing
+    /// the Dapps which `cascade` left without any authorized Personas.
+    pub fn cascade_authorized_dapps<F>(
+        &mut self,
+        mut cascade: F,
+    ) -> Vec<AuthorizedDappCascade>
+    where
+        F: FnMut(&mut AuthorizedDapp) -> Vec<AuthorizedDappCascade>,
+    {
+        let mut cascades = Vec::new();
+        let mut authorized_dapps = AuthorizedDapps::new();
+        for mut dapp in self.authorized_dapps.iter().cloned() {
+            let dapp_cascades = cascade(&mut dapp);
+            let is_emptied = !dapp_cascades.is_empty()
+                && dapp.references_to_authorized_personas.is_empty();
+            cascades.extend(dapp_cascades);
+            if is_emptied {
+                cascades.push(AuthorizedDappCascade::AuthorizedDappRemoved {
+                    dapp_definition_address: dapp.dapp_definition_address,
+                });
+            } else {
+                authorized_dapps.append(dapp);
+            }
+        }
+        self.authorized_dapps = authorized_dapps;
+        cascades
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[allow(clippy::upper_case_acronyms)]
+    type SUT = AuthorizedDapp;
+
+    fn satoshi() -> IdentityAddress {
+        IdentityAddress::placeholder_mainnet()
+    }
+
+    fn batman() -> IdentityAddress {
+        IdentityAddress::placeholder_mainnet_other()
+    }
+
+    #[test]
+    fn remove_persona_reference() {
+        let mut sut = SUT::placeholder_mainnet_dashboard();
+        assert_eq!(
+            sut.remove_persona_reference(&satoshi()),
+            vec![AuthorizedDappCascade::PersonaReferenceRemoved {
+                dapp_definition_address: sut.dapp_definition_address.clone(),
+                identity_address: satoshi(),
+            }]
+        );
+        assert!(!sut
+            .references_to_authorized_personas
+            .contains_id(&satoshi()));
+        assert!(sut.remove_persona_reference(&satoshi()).is_empty());
+    }
+
+    #[test]
+    fn remove_shared_account_from_every_persona() {
+        let mut sut = SUT::placeholder_mainnet_dashboard();
+        let dapp = sut.dapp_definition_address.clone();
+        let account = AccountAddress::placeholder_mainnet_other();
+        assert_eq!(
+            sut.remove_shared_account(&account),
+            vec![
+                AuthorizedDappCascade::SharedAccountRemoved {
+                    dapp_definition_address: dapp.clone(),
+                    identity_address: satoshi(),
+                    account_address: account.clone(),
+                },
+                AuthorizedDappCascade::RequestedQuantityDowngraded {
+                    dapp_definition_address: dapp.clone(),
+                    identity_address: satoshi(),
+                    from: RequestedQuantity::exactly(2),
+                    to: RequestedQuantity::exactly(1),
+                },
+                AuthorizedDappCascade::SharedAccountRemoved {
+                    dapp_definition_address: dapp.clone(),
+                    identity_address: batman(),
+                    account_address: account.clone(),
+                },
+                AuthorizedDappCascade::RequestedQuantityDowngraded {
+                    dapp_definition_address: dapp,
+                    identity_address: batman(),
+                    from: RequestedQuantity::at_least(1),
+                    to: RequestedQuantity::at_least(0),
+                },
+            ]
+        );
+        for persona in sut.references_to_authorized_personas.iter() {
+            let shared = persona.shared_accounts.clone().unwrap();
+            assert!(!shared.ids.contains_id(&account));
+            assert!(shared.request.is_fulfilled_by_ids(shared.ids.len()));
+        }
+        assert!(sut.remove_shared_account(&account).is_empty());
+    }
+
+    #[test]
+    fn remove_shared_persona_data_entry() {
+        let mut sut = SUT::placeholder_mainnet_dashboard();
+        let dapp = sut.dapp_definition_address.clone();
+        let shared = sut
+            .references_to_authorized_personas
+            .get(&satoshi())
+            .unwrap()
+            .shared_persona_data
+            .clone();
+        let name_id = shared.name.clone().unwrap();
+        let email_id =
+            shared.email_addresses.clone().unwrap().ids.items()[0].clone();
+
+        assert_eq!(
+            sut.remove_shared_persona_data_entry(&satoshi(), &name_id),
+            vec![AuthorizedDappCascade::SharedPersonaDataEntryRemoved {
+                dapp_definition_address: dapp.clone(),
+                identity_address: satoshi(),
+                entry_id: name_id.clone(),
+            }]
+        );
+        assert_eq!(
+            sut.remove_shared_persona_data_entry(&satoshi(), &email_id),
+            vec![
+                AuthorizedDappCascade::SharedPersonaDataEntryRemoved {
+                    dapp_definition_address: dapp.clone(),
+                    identity_address: satoshi(),
+                    entry_id: email_id.clone(),
+                },
+                AuthorizedDappCascade::RequestedQuantityDowngraded {
+                    dapp_definition_address: dapp,
+                    identity_address: satoshi(),
+                    from: RequestedQuantity::exactly(2),
+                    to: RequestedQuantity::exactly(1),
+                },
+            ]
+        );
+
+        let shared = sut
+            .references_to_authorized_personas
+            .get(&satoshi())
+            .unwrap()
+            .shared_persona_data
+            .clone();
+        assert_eq!(shared.name, None);
+        assert!(!shared.email_addresses.unwrap().ids.contains_id(&email_id));
+
+        // Only the Persona with `identity_address` is affected.
+        assert!(sut
+            .remove_shared_persona_data_entry(&satoshi(), &name_id)
+            .is_empty());
+        assert_eq!(
+            sut.references_to_authorized_personas.get(&batman()),
+            SUT::placeholder_mainnet_dashboard()
+                .references_to_authorized_personas
+                .get(&batman())
+        );
+    }
+
+    #[test]
+    fn cascade_authorized_dapps_removes_emptied_dapps() {
+        let mut sut = ProfileNetwork::placeholder_mainnet();
+        let dapps = sut.authorized_dapps.clone();
+        let mut cascades = sut.cascade_authorized_dapps(|d| {
+            d.remove_persona_reference(&satoshi())
+        });
+        cascades.extend(sut.cascade_authorized_dapps(|d| {
+            d.remove_persona_reference(&batman())
+        }));
+
+        assert!(sut.authorized_dapps.is_empty());
+        for dapp in dapps.iter() {
+            assert!(cascades.contains(
+                &AuthorizedDappCascade::AuthorizedDappRemoved {
+                    dapp_definition_address: dapp
+                        .dapp_definition_address
+                        .clone(),
+                }
+            ));
+        }
+        assert!(cascades
+            .iter()
+            .all(|c| dapps.contains_id(c.dapp_definition_address())));
+    }
+
+    #[test]
+    fn cascade_authorized_dapps_keeps_untouched_dapps() {
+        let mut sut = ProfileNetwork::placeholder_mainnet();
+        let before = sut.clone();
+        assert!(sut
+            .cascade_authorized_dapps(|d| d.remove_persona_reference(
+                &IdentityAddress::placeholder_stokenet()
+            ))
+            .is_empty());
+        assert_eq!(sut, before);
+    }
+}
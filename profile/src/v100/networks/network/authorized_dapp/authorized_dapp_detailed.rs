@@ -0,0 +1,166 @@
+use crate::prelude::*;
+
+/// Detailed data representation of an `AuthorizedDapp`, where every reference
+/// to an authorized Persona has been resolved into an
+/// `AuthorizedPersonaDetailed`, used to populate the settings views of a Dapp.
+#[derive(Clone, Debug, PartialEq, Hash, Eq, uniffi::Record)]
+pub struct AuthorizedDappDetailed {
+    /// The ID of the network the authorized Dapp is on.
+    pub network_id: NetworkID,
+
+    /// The address of the account which owns controls the Dapp.
+    pub dapp_definition_address: DappDefinitionAddress,
+
+    /// The Display name as sent by the Dapp in any interaction
+    /// request (CAP21), e.g. "Radix Dashboard".
+    pub display_name: Option<String>,
+
+    /// The resolved Personas the user has used to interact with this Dapp,
+    /// in the same order as `references_to_authorized_personas`.
+    pub detailed_authorized_personas: Vec<AuthorizedPersonaDetailed>,
+
+    /// Addresses of authorized Personas not found in Profile, or flagged as
+    /// deleted by the user, which thus could not be resolved.
+    pub unresolved_persona_addresses: Vec<IdentityAddress>,
+}
+
+impl AuthorizedDappDetailed {
+    /// Resolves every reference to an authorized Persona of `dapp`, looking
+    /// up the Personas and Accounts in `networks`.
+    pub fn resolving(
+        dapp: &AuthorizedDapp,
+        networks: &ProfileNetworks,
+    ) -> Self {
+        let mut unresolved_persona_addresses = Vec::new();
+        let detailed_authorized_personas = dapp
+            .references_to_authorized_personas
+            .iter()
+            .filter_map(|simple| {
+                let detailed = AuthorizedPersonaDetailed::resolving(
+                    simple,
+                    dapp.network_id,
+                    networks,
+                );
+                if detailed.is_none() {
+                    unresolved_persona_addresses
+                        .push(simple.identity_address.clone());
+                }
+                detailed
+            })
+            .collect_vec();
+
+        Self {
+            network_id: dapp.network_id,
+            dapp_definition_address: dapp.dapp_definition_address.clone(),
+            display_name: dapp.display_name.clone(),
+            detailed_authorized_personas,
+            unresolved_persona_addresses,
+        }
+    }
+
+    /// Returns `true` if any reference of the Dapp - to a Persona, Account
+    /// or PersonaData entry - could not be resolved.
+    pub fn has_unresolved_references(&self) -> bool {
+        !self.unresolved_persona_addresses.is_empty()
+            || self
+                .detailed_authorized_personas
+                .iter()
+                .any(|p| p.has_unresolved_references())
+    }
+}
+
+impl Identifiable for AuthorizedDappDetailed {
+    type ID = DappDefinitionAddress;
+
+    fn id(&self) -> Self::ID {
+        self.dapp_definition_address.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[allow(clippy::upper_case_acronyms)]
+    type SUT = AuthorizedDappDetailed;
+
+    #[test]
+    fn resolving_placeholder() {
+        let profile = Profile::placeholder();
+        let dapp = AuthorizedDapp::placeholder_mainnet_dashboard();
+        let sut = SUT::resolving(&dapp, &profile.networks);
+
+        assert_eq!(sut.id(), dapp.dapp_definition_address);
+        assert_eq!(sut.network_id, dapp.network_id);
+        assert_eq!(sut.display_name, dapp.display_name);
+        assert_eq!(
+            sut.detailed_authorized_personas
+                .iter()
+                .map(|p| p.display_name.value.clone())
+                .collect_vec(),
+            ["Satoshi", "Batman"]
+        );
+        assert!(sut.unresolved_persona_addresses.is_empty());
+
+        // The placeholder Accounts shared with the Dapp are not in Profile.
+        assert!(sut.has_unresolved_references());
+        assert_eq!(
+            sut.detailed_authorized_personas[0].unresolved_account_addresses,
+            vec![
+                AccountAddress::placeholder_mainnet(),
+                AccountAddress::placeholder_mainnet_other()
+            ]
+        );
+    }
+
+    #[test]
+    fn flags_unresolved_personas() {
+        let mut profile = Profile::placeholder();
+        let batman = Persona::placeholder_mainnet_batman().address;
+        profile.networks.update_with(&NetworkID::Mainnet, |n| {
+            _ = n.personas.remove_by_id(&batman)
+        });
+        let sut = SUT::resolving(
+            &AuthorizedDapp::placeholder_mainnet_dashboard(),
+            &profile.networks,
+        );
+        assert_eq!(sut.detailed_authorized_personas.len(), 1);
+        assert_eq!(sut.unresolved_persona_addresses, vec![batman]);
+        assert!(sut.has_unresolved_references());
+    }
+
+    #[test]
+    fn flags_deleted_personas_as_unresolved() {
+        let mut profile = Profile::placeholder();
+        let batman = Persona::placeholder_mainnet_batman().address;
+        profile.networks.update_with(&NetworkID::Mainnet, |n| {
+            n.personas.update_with(&batman, |p| {
+                _ = p.flags.insert_flag(EntityFlag::DeletedByUser)
+            });
+        });
+        let sut = SUT::resolving(
+            &AuthorizedDapp::placeholder_mainnet_dashboard(),
+            &profile.networks,
+        );
+        assert_eq!(sut.detailed_authorized_personas.len(), 1);
+        assert_eq!(sut.unresolved_persona_addresses, vec![batman]);
+    }
+
+    #[test]
+    fn no_unresolved_references() {
+        let dapp = AuthorizedDapp::new(
+            NetworkID::Mainnet,
+            AccountAddress::placeholder_mainnet(),
+            None,
+            IdentifiedVecVia::from_iter([AuthorizedPersonaSimple::new(
+                Persona::placeholder_mainnet_satoshi().address,
+                Timestamp::parse("2024-01-31T14:23:45Z").unwrap(),
+                None,
+                SharedPersonaData::new(None, None, None),
+            )]),
+        );
+        let sut = SUT::resolving(&dapp, &Profile::placeholder().networks);
+        assert_eq!(sut.detailed_authorized_personas.len(), 1);
+        assert!(!sut.has_unresolved_references());
+    }
+}
@@ -0,0 +1,399 @@
+use crate::prelude::*;
+
+/// Detailed data representation of a Persona the user has shared with a Dapp,
+/// reconstructed from an `AuthorizedPersonaSimple` by looking up the Persona,
+/// Accounts and PersonaData entries it references in Profile, used to
+/// populate views.
+///
+/// References which no longer resolve - e.g. an Account or PersonaData entry
+/// which since has been deleted - are not silently dropped, they are
+/// listed in `unresolved_account_addresses` and
+/// `unresolved_persona_data_entry_ids` respectively.
+#[derive(Clone, Debug, PartialEq, Hash, Eq, uniffi::Record)]
+pub struct AuthorizedPersonaDetailed {
+    /// The address of the Persona, identical to the `identity_address` of
+    /// the `AuthorizedPersonaSimple`.
+    pub identity_address: IdentityAddress,
+
+    /// The current display name of the Persona.
+    pub display_name: DisplayName,
+
+    /// Date of last login for this persona.
+    pub last_login: Timestamp,
+
+    /// The Accounts the user has given the dApp ongoing access to, in the
+    /// same order as they were shared, `None` if the dApp never requested
+    /// any accounts.
+    pub shared_accounts: Option<Accounts>,
+
+    /// The values of the PersonaData entries the user has shared with the
+    /// dApp, with the same IDs as in the PersonaData of the Persona.
+    pub shared_persona_data: PersonaData,
+
+    /// Addresses of shared Accounts not found in Profile.
+    pub unresolved_account_addresses: Vec<AccountAddress>,
+
+    /// IDs of shared PersonaData entries not found, of the shared kind, in
+    /// the PersonaData of the Persona.
+    pub unresolved_persona_data_entry_ids: Vec<PersonaDataEntryID>,
+}
+
+impl AuthorizedPersonaDetailed {
+    /// Resolves `simple`, of a Dapp on `network_id`, by looking up the
+    /// Persona it references and the shared Accounts in `networks`.
+    ///
+    /// Returns `None` if the Persona is not in `networks` or has been
+    /// flagged as deleted by the user. Shared Accounts flagged as deleted by
+    /// the user, or on another network than the Dapp or the Persona, are
+    /// listed in `unresolved_account_addresses`.
+    pub fn resolving(
+        simple: &AuthorizedPersonaSimple,
+        network_id: NetworkID,
+        networks: &ProfileNetworks,
+    ) -> Option<Self> {
+        let persona = networks
+            .get_persona(&simple.identity_address)
+            .filter(|p| !p.flags.is_deleted())?;
+
+        let mut unresolved_account_addresses = Vec::new();
+        let shared_accounts = simple.shared_accounts.as_ref().map(|shared| {
+            Accounts::from_iter(shared.ids.iter().filter_map(|address| {
+                let account = networks.get_account(address).filter(|a| {
+                    !a.flags.is_deleted()
+                        && a.network_id == network_id
+                        && a.network_id == persona.network_id
+                });
+                if account.is_none() {
+                    unresolved_account_addresses.push(address.clone());
+                }
+                account
+            }))
+        });
+
+        let (shared_persona_data, unresolved_persona_data_entry_ids) =
+            persona.persona_data.shared(&simple.shared_persona_data);
+
+        Some(Self {
+            identity_address: persona.address.clone(),
+            display_name: persona.display_name.clone(),
+            last_login: simple.last_login.clone(),
+            shared_accounts,
+            shared_persona_data,
+            unresolved_account_addresses,
+            unresolved_persona_data_entry_ids,
+        })
+    }
+
+    /// Returns `true` if any of the shared Accounts or PersonaData entries
+    /// could not be found.
+    pub fn has_unresolved_references(&self) -> bool {
+        !self.unresolved_account_addresses.is_empty()
+            || !self.unresolved_persona_data_entry_ids.is_empty()
+    }
+}
+
+impl Identifiable for AuthorizedPersonaDetailed {
+    type ID = IdentityAddress;
+
+    fn id(&self) -> Self::ID {
+        self.identity_address.clone()
+    }
+}
+
+impl PersonaData {
+    /// Returns the subset of this PersonaData referenced by `shared`, and the
+    /// IDs in `shared` without any entry of the referenced kind.
+    fn shared(
+        &self,
+        shared: &SharedPersonaData,
+    ) -> (PersonaData, Vec<PersonaDataEntryID>) {
+        let mut unresolved = Vec::new();
+
+        let name = shared.name.as_ref().and_then(|id| {
+            let name = self.name.clone().filter(|n| n.id == *id);
+            if name.is_none() {
+                unresolved.push(id.clone());
+            }
+            name
+        });
+
+        let ids_of = |shared: &Option<
+            SharedToDappWithPersonaIDsOfPersonaDataEntries,
+        >| {
+            shared
+                .iter()
+                .flat_map(|s| s.ids.iter().cloned())
+                .collect_vec()
+        };
+        let phone_number_ids = ids_of(&shared.phone_numbers);
+        let email_address_ids = ids_of(&shared.email_addresses);
+
+        let phone_numbers = CollectionOfPhoneNumbers::entries(
+            phone_number_ids.into_iter().filter_map(|id| {
+                let entry = self.phone_numbers.get(&id).cloned();
+                if entry.is_none() {
+                    unresolved.push(id);
+                }
+                entry
+            }),
+        );
+
+        let email_addresses = CollectionOfEmailAddresses::entries(
+            email_address_ids.into_iter().filter_map(|id| {
+                let entry = self.email_addresses.get(&id).cloned();
+                if entry.is_none() {
+                    unresolved.push(id);
+                }
+                entry
+            }),
+        );
+
+        (
+            PersonaData::new(name, phone_numbers, email_addresses),
+            unresolved,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[allow(clippy::upper_case_acronyms)]
+    type SUT = AuthorizedPersonaDetailed;
+
+    fn satoshi() -> Persona {
+        Persona::placeholder_mainnet_satoshi()
+    }
+
+    fn simple(
+        shared_accounts: impl Into<Option<SharedToDappWithPersonaAccountAddresses>>,
+        shared_persona_data: SharedPersonaData,
+    ) -> AuthorizedPersonaSimple {
+        AuthorizedPersonaSimple::new(
+            satoshi().address,
+            Timestamp::parse("2024-01-31T14:23:45Z").unwrap(),
+            shared_accounts,
+            shared_persona_data,
+        )
+    }
+
+    fn ids(
+        ids: impl IntoIterator<Item = PersonaDataEntryID>,
+    ) -> SharedToDappWithPersonaIDsOfPersonaDataEntries {
+        let ids = IdentifiedVecVia::from_iter(ids);
+        SharedToDappWithPersonaIDsOfPersonaDataEntries::new(
+            RequestedQuantity::at_least(0),
+            ids,
+        )
+    }
+
+    #[test]
+    fn resolves_accounts_and_persona_data() {
+        let persona = satoshi();
+        let data = &persona.persona_data;
+        let phone = data.phone_numbers.items()[1].clone();
+        let email = data.email_addresses.items()[0].clone();
+        let alice = Account::placeholder_mainnet_alice();
+        let bob = Account::placeholder_mainnet_bob();
+        let simple = simple(
+            SharedToDappWithPersonaAccountAddresses::new(
+                RequestedQuantity::exactly(2),
+                IdentifiedVecVia::from_iter([
+                    bob.address.clone(),
+                    alice.address.clone(),
+                ]),
+            ),
+            SharedPersonaData::new(
+                data.name.clone().unwrap().id,
+                ids([email.id.clone()]),
+                ids([phone.id.clone()]),
+            ),
+        );
+
+        let sut = SUT::resolving(
+            &simple,
+            NetworkID::Mainnet,
+            &Profile::placeholder().networks,
+        )
+        .unwrap();
+
+        assert_eq!(sut.identity_address, persona.address);
+        assert_eq!(sut.display_name, persona.display_name);
+        assert_eq!(sut.last_login, simple.last_login);
+        assert_eq!(
+            sut.shared_accounts,
+            Some(Accounts::from_iter([bob, alice]))
+        );
+        assert_eq!(
+            sut.shared_persona_data,
+            PersonaData::new(
+                data.name.clone(),
+                CollectionOfPhoneNumbers::new(phone),
+                CollectionOfEmailAddresses::new(email),
+            )
+        );
+        assert!(!sut.has_unresolved_references());
+    }
+
+    #[test]
+    fn nothing_shared() {
+        let sut = SUT::resolving(
+            &simple(None, SharedPersonaData::new(None, None, None)),
+            NetworkID::Mainnet,
+            &Profile::placeholder().networks,
+        )
+        .unwrap();
+        assert_eq!(sut.shared_accounts, None);
+        assert_eq!(sut.shared_persona_data, PersonaData::default());
+        assert!(!sut.has_unresolved_references());
+    }
+
+    #[test]
+    fn flags_unresolved_accounts() {
+        let alice = Account::placeholder_mainnet_alice();
+        let unknown = AccountAddress::placeholder_mainnet();
+        let sut = SUT::resolving(
+            &simple(
+                SharedToDappWithPersonaAccountAddresses::new(
+                    RequestedQuantity::at_least(1),
+                    IdentifiedVecVia::from_iter([
+                        unknown.clone(),
+                        alice.address.clone(),
+                    ]),
+                ),
+                SharedPersonaData::new(None, None, None),
+            ),
+            NetworkID::Mainnet,
+            &Profile::placeholder().networks,
+        )
+        .unwrap();
+        assert_eq!(sut.shared_accounts, Some(Accounts::from_iter([alice])));
+        assert_eq!(sut.unresolved_account_addresses, vec![unknown]);
+        assert!(sut.has_unresolved_references());
+    }
+
+    #[test]
+    fn deleted_and_other_network_accounts_are_unresolved() {
+        let alice = Account::placeholder_mainnet_alice();
+        let bob = Account::placeholder_mainnet_bob();
+        let stokenet = Accounts::placeholder_stokenet().items()[0].clone();
+        let mut networks = Profile::placeholder().networks;
+        networks.update_account(&bob.address, |a| {
+            _ = a.flags.insert_flag(EntityFlag::DeletedByUser)
+        });
+        let simple = simple(
+            SharedToDappWithPersonaAccountAddresses::new(
+                RequestedQuantity::at_least(1),
+                IdentifiedVecVia::from_iter([
+                    bob.address.clone(),
+                    stokenet.address.clone(),
+                    alice.address.clone(),
+                ]),
+            ),
+            SharedPersonaData::new(None, None, None),
+        );
+
+        let sut =
+            SUT::resolving(&simple, NetworkID::Mainnet, &networks).unwrap();
+        assert_eq!(
+            sut.shared_accounts,
+            Some(Accounts::from_iter([alice.clone()]))
+        );
+        assert_eq!(
+            sut.unresolved_account_addresses,
+            vec![bob.address.clone(), stokenet.address.clone()]
+        );
+
+        // Dapp on another network than the Persona
+        let sut =
+            SUT::resolving(&simple, NetworkID::Stokenet, &networks).unwrap();
+        assert_eq!(sut.shared_accounts, Some(Accounts::new()));
+        assert_eq!(
+            sut.unresolved_account_addresses,
+            vec![bob.address, stokenet.address, alice.address]
+        );
+    }
+
+    #[test]
+    fn flags_unresolved_persona_data_entries() {
+        let persona = satoshi();
+        let data = &persona.persona_data;
+        let phone_id = data.phone_numbers.items()[0].id.clone();
+        let removed = PersonaDataEntryID::generate();
+        let sut = SUT::resolving(
+            &simple(
+                None,
+                SharedPersonaData::new(
+                    removed.clone(),
+                    // an ID of a phone number is not an email address
+                    ids([phone_id.clone()]),
+                    ids([phone_id.clone()]),
+                ),
+            ),
+            NetworkID::Mainnet,
+            &Profile::placeholder().networks,
+        )
+        .unwrap();
+        assert_eq!(sut.shared_persona_data.name, None);
+        assert_eq!(
+            sut.shared_persona_data.phone_numbers,
+            CollectionOfPhoneNumbers::new(
+                data.phone_numbers.items()[0].clone()
+            )
+        );
+        assert_eq!(
+            sut.shared_persona_data.email_addresses,
+            CollectionOfEmailAddresses::default()
+        );
+        assert_eq!(
+            sut.unresolved_persona_data_entry_ids,
+            vec![removed, phone_id]
+        );
+    }
+
+    #[test]
+    fn unknown_persona_is_unresolved() {
+        let mut networks = Profile::placeholder().networks;
+        networks.update_with(&NetworkID::Mainnet, |n| {
+            _ = n.personas.remove_by_id(&satoshi().address)
+        });
+        assert_eq!(
+            SUT::resolving(
+                &simple(None, SharedPersonaData::new(None, None, None)),
+                NetworkID::Mainnet,
+                &networks,
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn deleted_persona_is_unresolved() {
+        let mut networks = Profile::placeholder().networks;
+        networks.update_with(&NetworkID::Mainnet, |n| {
+            n.personas.update_with(&satoshi().address, |p| {
+                _ = p.flags.insert_flag(EntityFlag::DeletedByUser)
+            });
+        });
+        assert_eq!(
+            SUT::resolving(
+                &simple(None, SharedPersonaData::new(None, None, None)),
+                NetworkID::Mainnet,
+                &networks,
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn id_is_identity_address() {
+        let sut = SUT::resolving(
+            &simple(None, SharedPersonaData::new(None, None, None)),
+            NetworkID::Mainnet,
+            &Profile::placeholder().networks,
+        )
+        .unwrap();
+        assert_eq!(sut.id(), satoshi().address);
+    }
+}
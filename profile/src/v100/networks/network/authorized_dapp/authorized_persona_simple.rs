@@ -3,12 +3,7 @@ use crate::prelude::*;
 /// Simple data representation of a Persona the user has shared with a Dapp.
 /// Simple meaning "the bare minimum amount of data" that enabled `Sargon` to
 /// be able to reconstruct a `AuthorizedPersonaDetailed` value, used to populate
-/// views.
-///
-/// N.B. as of 2024-01-31 of `Sargon` we have not yet implemented the struct
-/// `AuthorizedPersonaDetailed` since it is not JSON, but logic, and we have yet
-/// to migrate `Sargon` into iOS/Android clients, thus we will defer the work
-/// of mapping `AuthorizedPersonaSimple` -> `AuthorizedPersonaDetailed`.
+/// views, see `AuthorizedPersonaDetailed::resolving`.
 #[derive(
     Serialize, Deserialize, Clone, Debug, PartialEq, Hash, Eq, uniffi::Record,
 )]
@@ -93,6 +88,17 @@ impl AuthorizedPersonaSimple {
         )
     }
 }
+impl Redactable for AuthorizedPersonaSimple {
+    /// Replaces `last_login` with the UNIX epoch, keeping the IDs of shared
+    /// Accounts and PersonaData entries.
+    fn redacted(&self) -> Self {
+        let mut redacted = self.clone();
+        redacted.last_login =
+            Timestamp::parse("1970-01-01T00:00:00Z").expect("Valid timestamp.");
+        redacted
+    }
+}
+
 impl HasPlaceholder for AuthorizedPersonaSimple {
     fn placeholder() -> Self {
         Self::placeholder_mainnet()
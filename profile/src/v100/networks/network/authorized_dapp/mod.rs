@@ -1,4 +1,7 @@
 mod authorized_dapp;
+mod authorized_dapp_cascade;
+mod authorized_dapp_detailed;
+mod authorized_persona_detailed;
 mod authorized_persona_simple;
 mod requested_number_quantifier;
 mod requested_quantity;
@@ -6,6 +9,9 @@ mod shared_persona_data;
 mod shared_with_dapp;
 
 pub use authorized_dapp::*;
+pub use authorized_dapp_cascade::*;
+pub use authorized_dapp_detailed::*;
+pub use authorized_persona_detailed::*;
 pub use authorized_persona_simple::*;
 pub use requested_number_quantifier::*;
 pub use requested_quantity::*;
@@ -61,6 +61,19 @@ impl RequestedQuantity {
         value.assert_is_valid();
         value
     }
+
+    /// The quantity fulfilled by `len` ids, used when ids fulfilling this
+    /// quantity have been removed, keeping the quantifier unless `len` is
+    /// zero, since `Exactly: 0` is invalid.
+    pub fn downgraded_to(&self, len: usize) -> Self {
+        let quantity = u16::try_from(len).unwrap_or(u16::MAX);
+        match self.quantifier {
+            RequestedNumberQuantifier::Exactly if quantity > 0 => {
+                Self::exactly(quantity)
+            }
+            _ => Self::at_least(quantity),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -127,4 +140,12 @@ mod tests {
         assert_eq!(SUT::exactly(10).is_fulfilled_by_ids(9), false);
         assert_eq!(SUT::exactly(10).is_fulfilled_by_ids(11), false);
     }
+
+    #[test]
+    fn downgraded_to() {
+        assert_eq!(SUT::exactly(3).downgraded_to(2), SUT::exactly(2));
+        assert_eq!(SUT::exactly(1).downgraded_to(0), SUT::at_least(0));
+        assert_eq!(SUT::at_least(3).downgraded_to(1), SUT::at_least(1));
+        assert!(SUT::at_least(1).downgraded_to(0).is_fulfilled_by_ids(0));
+    }
 }
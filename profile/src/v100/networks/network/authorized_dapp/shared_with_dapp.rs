@@ -55,6 +55,20 @@ macro_rules! declare_shared_with_dapp {
                 let ids_str = self.ids.iter().map(|v| v.to_string()).join(", ");
                 format!("{} - shared ids: [{}]", self.request, ids_str)
             }
+
+            /// Removes `id` from the shared ids, returning `false` if it was
+            /// not shared. Should the remaining ids no longer fulfill
+            /// `request` it is downgraded, see [`RequestedQuantity::downgraded_to`].
+            pub fn remove_id(&mut self, id: &$id) -> bool {
+                if self.ids.remove_by_id(id).is_none() {
+                    return false;
+                }
+                let len = self.ids.len();
+                if !self.request.is_fulfilled_by_ids(len) {
+                    self.request = self.request.downgraded_to(len);
+                }
+                true
+            }
         }
     };
 }
@@ -221,6 +235,36 @@ mod shared_accounts_tests {
         );
     }
 
+    #[test]
+    fn remove_id_downgrades_request() {
+        let mut sut = SUT::placeholder();
+        assert!(sut.remove_id(&AccountAddress::placeholder_mainnet()));
+        assert_eq!(sut.request, RequestedQuantity::exactly(1));
+        assert_eq!(
+            sut.ids,
+            IdentifiedVecVia::from_iter([
+                AccountAddress::placeholder_mainnet_other()
+            ])
+        );
+        assert!(!sut.remove_id(&AccountAddress::placeholder_mainnet()));
+    }
+
+    #[test]
+    fn remove_id_keeps_fulfilled_request() {
+        let mut sut = SUT::new(
+            RequestedQuantity::at_least(1),
+            IdentifiedVecVia::from_iter([
+                AccountAddress::placeholder_mainnet(),
+                AccountAddress::placeholder_mainnet_other(),
+            ]),
+        );
+        assert!(sut.remove_id(&AccountAddress::placeholder_mainnet()));
+        assert_eq!(sut.request, RequestedQuantity::at_least(1));
+        assert!(sut.remove_id(&AccountAddress::placeholder_mainnet_other()));
+        assert_eq!(sut.request, RequestedQuantity::at_least(0));
+        assert!(sut.ids.is_empty());
+    }
+
     #[test]
     fn json_roundtrip_placeholder_other() {
         let model = SUT::placeholder_other();
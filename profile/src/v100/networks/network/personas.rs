@@ -41,6 +41,23 @@ impl Personas {
     pub fn get_all(&self) -> Vec<&Persona> {
         self.elements()
     }
+
+    /// Returns references to the personas which are neither hidden nor
+    /// deleted by the user.
+    pub fn get_visible(&self) -> Vec<&Persona> {
+        self.get_all()
+            .into_iter()
+            .filter(|x| x.flags.is_visible())
+            .collect()
+    }
+
+    /// Returns references to the personas hidden by the user.
+    pub fn get_hidden(&self) -> Vec<&Persona> {
+        self.get_all()
+            .into_iter()
+            .filter(|x| x.flags.is_hidden())
+            .collect()
+    }
 }
 
 impl HasPlaceholder for Personas {
@@ -117,6 +134,21 @@ mod tests {
         assert_eq!(Personas::placeholder().get_all().len(), 2);
     }
 
+    #[test]
+    fn get_visible_and_hidden() {
+        let mut sut = Personas::placeholder_mainnet(); // Batman is deleted by user
+        let satoshi = Persona::placeholder_mainnet_satoshi();
+        assert_eq!(sut.get_visible(), vec![&satoshi]);
+        assert!(sut.get_hidden().is_empty());
+
+        sut.update_with(&satoshi.address, |p| {
+            p.flags.insert_flag(EntityFlag::HiddenByUser);
+        });
+        assert!(sut.get_visible().is_empty());
+        assert_eq!(sut.get_hidden().len(), 1);
+        assert_eq!(sut.get_all().len(), 2);
+    }
+
     #[test]
     fn get_by_address() {
         let persona = Persona::placeholder();
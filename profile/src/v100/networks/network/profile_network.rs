@@ -92,6 +92,69 @@ impl ProfileNetwork {
             None
         }
     }
+
+    /// Returns a clone of the updated persona if found, else None.
+    pub fn update_persona<F>(
+        &mut self,
+        address: &IdentityAddress,
+        mutate: F,
+    ) -> Option<Persona>
+    where
+        F: FnMut(&mut Persona),
+    {
+        if self.personas.update_with(address, mutate) {
+            self.personas.get(address).cloned()
+        } else {
+            None
+        }
+    }
+}
+
+impl ProfileNetwork {
+    /// Returns references to the accounts on this network which are neither
+    /// hidden nor deleted by the user.
+    pub fn get_visible_accounts(&self) -> Vec<&Account> {
+        self.accounts.get_visible()
+    }
+
+    /// Returns references to the accounts on this network hidden by the user.
+    pub fn get_hidden_accounts(&self) -> Vec<&Account> {
+        self.accounts.get_hidden()
+    }
+
+    /// Returns references to **all** accounts on this network, including
+    /// hidden and deleted ones.
+    pub fn get_all_accounts(&self) -> Vec<&Account> {
+        self.accounts.get_all()
+    }
+
+    /// Returns references to the personas on this network which are neither
+    /// hidden nor deleted by the user.
+    pub fn get_visible_personas(&self) -> Vec<&Persona> {
+        self.personas.get_visible()
+    }
+
+    /// Returns references to the personas on this network hidden by the user.
+    pub fn get_hidden_personas(&self) -> Vec<&Persona> {
+        self.personas.get_hidden()
+    }
+
+    /// Returns references to **all** personas on this network, including
+    /// hidden and deleted ones.
+    pub fn get_all_personas(&self) -> Vec<&Persona> {
+        self.personas.get_all()
+    }
+}
+
+impl Redactable for ProfileNetwork {
+    fn redacted(&self) -> Self {
+        Self::new(
+            self.id,
+            self.accounts.redacted(),
+            self.personas.redacted(),
+            self.authorized_dapps.redacted(),
+        )
+    }
 }
 
 impl HasPlaceholder for ProfileNetwork {
@@ -151,6 +214,41 @@ mod tests {
         assert_eq!(sut.accounts, Accounts::placeholder());
     }
 
+    #[test]
+    fn get_visible_hidden_and_all_entities() {
+        let mut sut = ProfileNetwork::placeholder();
+        let alice = Account::placeholder_mainnet_alice().address;
+        let satoshi = Persona::placeholder_mainnet_satoshi().address;
+        assert_eq!(sut.get_visible_accounts().len(), 1);
+        assert_eq!(sut.get_visible_personas().len(), 1);
+
+        sut.update_account(&alice, |a| {
+            a.flags.insert_flag(EntityFlag::HiddenByUser);
+        });
+        sut.update_persona(&satoshi, |p| {
+            p.flags.insert_flag(EntityFlag::HiddenByUser);
+        });
+
+        assert!(sut.get_visible_accounts().is_empty());
+        assert!(sut.get_visible_personas().is_empty());
+        assert_eq!(
+            sut.get_hidden_accounts()
+                .into_iter()
+                .map(|a| a.address.clone())
+                .collect_vec(),
+            vec![alice]
+        );
+        assert_eq!(
+            sut.get_hidden_personas()
+                .into_iter()
+                .map(|p| p.address.clone())
+                .collect_vec(),
+            vec![satoshi]
+        );
+        assert_eq!(sut.get_all_accounts().len(), 2);
+        assert_eq!(sut.get_all_personas().len(), 2);
+    }
+
     #[test]
     fn duplicate_accounts_are_filtered_out() {
         assert_eq!(
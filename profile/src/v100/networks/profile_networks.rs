@@ -44,13 +44,40 @@ impl ProfileNetworks {
         });
         self.get_account(address)
     }
+
+    pub fn get_persona(&self, address: &IdentityAddress) -> Option<Persona> {
+        self.get(&address.network_id)
+            .and_then(|n| n.personas.get_persona_by_address(address))
+            .cloned()
+    }
+
+    /// Returns a clone of the updated persona if found, else None.
+    pub fn update_persona<F>(
+        &mut self,
+        address: &IdentityAddress,
+        mut mutate: F,
+    ) -> Option<Persona>
+    where
+        F: FnMut(&mut Persona),
+    {
+        self.update_with(&address.network_id, |n| {
+            _ = n.update_persona(address, |p| mutate(p))
+        });
+        self.get_persona(address)
+    }
 }
 
 impl ProfileNetworks {
     pub fn content_hint(&self) -> ContentHint {
         let number_of_accounts =
             self.iter().fold(0, |acc, x| acc + x.accounts.len());
-        ContentHint::with_counters(number_of_accounts, 0, self.len())
+        let number_of_personas =
+            self.iter().fold(0, |acc, x| acc + x.personas.len());
+        ContentHint::with_counters(
+            number_of_accounts,
+            number_of_personas,
+            self.len(),
+        )
     }
 }
 
@@ -196,6 +223,35 @@ mod tests {
         assert_eq!(sut, ProfileNetworks::placeholder());
     }
 
+    #[test]
+    fn update_persona() {
+        let mut sut = ProfileNetworks::placeholder();
+        let address = Persona::placeholder_mainnet_satoshi().address;
+        let updated = sut
+            .update_persona(&address, |p| {
+                p.display_name = DisplayName::new("Nakamoto").unwrap()
+            })
+            .unwrap();
+        assert_eq!(updated.display_name.value, "Nakamoto");
+        assert_eq!(sut.get_persona(&address), Some(updated));
+    }
+
+    #[test]
+    fn update_persona_unknown() {
+        let mut sut = ProfileNetworks::placeholder();
+        let address = Persona::placeholder_stokenet_hermione().address;
+        sut.update_with(&NetworkID::Stokenet, |n| {
+            _ = n.personas.remove_by_id(&address)
+        });
+        let before = sut.clone();
+        assert!(sut
+            .update_persona(&address, |p| {
+                p.display_name = DisplayName::new("will fail").unwrap()
+            })
+            .is_none());
+        assert_eq!(sut, before);
+    }
+
     #[test]
     fn update_account_unknown_account() {
         let mut sut = ProfileNetworks::placeholder();
@@ -228,7 +284,7 @@ mod tests {
     fn content_hint() {
         assert_eq!(
             ProfileNetworks::placeholder().content_hint(),
-            ContentHint::with_counters(4, 0, 2)
+            ContentHint::with_counters(4, 4, 2)
         );
     }
 
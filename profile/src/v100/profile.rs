@@ -89,6 +89,50 @@ impl Profile {
         self.header.id.clone()
     }
 
+    /// Updates the header after this Profile has been mutated, stamping
+    /// `last_modified` with the current time, setting `last_used_on_device`
+    /// and recomputing the `content_hint`.
+    pub fn update_header(&mut self, last_used_on_device: DeviceInfo) {
+        self.header.last_used_on_device = last_used_on_device;
+        self.header.last_modified = now();
+        self.header.content_hint = self.networks.content_hint();
+    }
+
+    /// Adds `accounts` to the networks they are on, adding networks not yet
+    /// present in this Profile.
+    ///
+    /// Returns `Err` if any of `accounts` is already present in this Profile,
+    /// or present more than once in `accounts`, in which case no account is
+    /// added.
+    pub fn add_accounts(&mut self, accounts: Vec<Account>) -> Result<()> {
+        let mut addresses = HashSet::new();
+        if let Some(duplicate) = accounts.iter().find(|a| {
+            !addresses.insert(a.address.clone())
+                || self.networks.get_account(&a.address).is_some()
+        }) {
+            return Err(CommonError::AccountAlreadyPresent(
+                duplicate.address.clone(),
+            ));
+        }
+
+        for account in accounts {
+            let network_id = account.network_id;
+            if self.networks.contains_id(&network_id) {
+                self.networks.update_with(&network_id, |n| {
+                    n.accounts.append(account.clone());
+                });
+            } else {
+                self.networks.append(ProfileNetwork::new(
+                    network_id,
+                    Accounts::from_iter([account]),
+                    Personas::default(),
+                    AuthorizedDapps::default(),
+                ));
+            }
+        }
+        Ok(())
+    }
+
     /// Returns a clone of the updated account if found, else None.
     pub fn update_account<F>(
         &mut self,
@@ -101,6 +145,18 @@ impl Profile {
         self.networks.update_account(address, mutate)
     }
 
+    /// Returns a clone of the updated persona if found, else None.
+    pub fn update_persona<F>(
+        &mut self,
+        address: &IdentityAddress,
+        mutate: F,
+    ) -> Option<Persona>
+    where
+        F: FnMut(&mut Persona),
+    {
+        self.networks.update_persona(address, mutate)
+    }
+
     pub fn update_factor_source<S, M>(
         &mut self,
         factor_source_id: &FactorSourceID,
@@ -123,6 +179,21 @@ impl Profile {
     }
 }
 
+impl Redactable for Profile {
+    /// Redacts the PersonaData of all Personas, the login history of all
+    /// AuthorizedDapps, the passwords of all P2PLinks and every user chosen
+    /// name, keeping everything else, including all IDs and addresses, so
+    /// that the redacted Profile can be used to reproduce issues.
+    fn redacted(&self) -> Self {
+        let mut redacted = self.clone();
+        redacted.factor_sources = self.factor_sources.redacted();
+        redacted.networks = self.networks.redacted();
+        redacted.app_preferences.p2p_links =
+            self.app_preferences.p2p_links.redacted();
+        redacted
+    }
+}
+
 impl HasPlaceholder for Profile {
     fn placeholder() -> Self {
         let networks = ProfileNetworks::placeholder();
@@ -164,6 +235,59 @@ mod tests {
         assert_eq!(Profile::placeholder_other(), Profile::placeholder_other());
     }
 
+    #[test]
+    fn update_header() {
+        let mut sut = Profile::placeholder();
+        let before = sut.header.clone();
+        sut.networks = ProfileNetworks::new();
+        let device = DeviceInfo::new_iphone();
+        sut.update_header(device.clone());
+
+        assert_eq!(sut.header.id, before.id);
+        assert_eq!(sut.header.creating_device, before.creating_device);
+        assert_eq!(sut.header.last_used_on_device, device);
+        assert!(sut.header.last_modified > before.last_modified);
+        assert_eq!(sut.header.content_hint, ContentHint::new());
+    }
+
+    #[test]
+    fn add_accounts() {
+        let mut sut = Profile::placeholder();
+        let carol = Account::placeholder_mainnet_carol();
+        let nebunet = Account::placeholder_nebunet();
+        sut.add_accounts(vec![carol.clone(), nebunet.clone()])
+            .unwrap();
+        assert_eq!(sut.networks.get_account(&carol.address), Some(carol));
+        assert_eq!(
+            sut.networks.get_account(&nebunet.address),
+            Some(nebunet.clone())
+        );
+        assert_eq!(
+            sut.networks
+                .get(&nebunet.network_id)
+                .unwrap()
+                .accounts
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn add_accounts_fails_if_already_present() {
+        let mut sut = Profile::placeholder();
+        let alice = Account::placeholder_mainnet_alice();
+        let carol = Account::placeholder_mainnet_carol();
+        assert_eq!(
+            sut.add_accounts(vec![carol.clone(), alice.clone()]),
+            Err(CommonError::AccountAlreadyPresent(alice.address))
+        );
+        assert_eq!(
+            sut.add_accounts(vec![carol.clone(), carol.clone()]),
+            Err(CommonError::AccountAlreadyPresent(carol.address))
+        );
+        assert_eq!(sut, Profile::placeholder());
+    }
+
     #[test]
     fn update_factor_source_not_update_when_factor_source_not_found() {
         let mut sut = Profile::placeholder();
@@ -367,7 +491,7 @@ mod tests {
 					"lastModified": "2023-09-11T16:05:56.000Z",
 					"contentHint": {
 						"numberOfAccountsOnAllNetworksInTotal": 4,
-						"numberOfPersonasOnAllNetworksInTotal": 0,
+						"numberOfPersonasOnAllNetworksInTotal": 4,
 						"numberOfNetworks": 2
 					}
 				},
@@ -1192,4 +1316,34 @@ mod uniffi_tests {
             private.factor_source.factor_source_id()
         );
     }
+
+    #[test]
+    fn redacted() {
+        let sut = Profile::placeholder();
+        let redacted = sut.redacted();
+        let json = serde_json::to_string(&redacted).unwrap();
+        for sensitive in [
+            "Nakamoto",
+            "sat@os.hi",
+            "+46123456789",
+            "bat@m.an",
+            "Radix Dashboard",
+            "2024-01-31T14:23:45",
+            &P2PLink::placeholder().connection_password.to_string(),
+            &P2PLink::placeholder().display_name,
+            "Alice",
+            "Satoshi",
+            "Orange, scratched",
+        ] {
+            assert!(!json.contains(sensitive), "contains: {}", sensitive);
+        }
+        assert_eq!(serde_json::from_str::<Profile>(&json).unwrap(), redacted);
+
+        let diff = sut.diff(&redacted);
+        assert!(diff.accounts.added.is_empty());
+        assert!(diff.accounts.removed.is_empty());
+        assert!(diff.personas.added.is_empty());
+        assert!(diff.personas.removed.is_empty());
+        assert_eq!(redacted.validate(), Vec::new());
+    }
 }
@@ -0,0 +1,504 @@
+use crate::prelude::*;
+
+/// Tells which accounts are active on ledger, implemented by Wallet Clients
+/// using the Radix Gateway, so that Sargon does not do any networking itself.
+#[uniffi::export]
+pub trait AccountActivityChecker: Send + Sync + std::fmt::Debug {
+    /// Returns those of `addresses` which are active on ledger, e.g. have
+    /// ever been used in a transaction or hold any assets.
+    fn active_addresses(
+        &self,
+        addresses: Vec<AccountAddress>,
+    ) -> Result<Vec<AccountAddress>>;
+}
+
+/// An Account derived from a mnemonic by an account recovery scan, which can
+/// be added to Profile using `wallet.add_account(candidate.account)`, after
+/// the `DeviceFactorSource` of the mnemonic has been added to Profile.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, uniffi::Record)]
+pub struct AccountRecoveryCandidate {
+    /// The derived account, named "Unnamed", which the user can rename.
+    pub account: Account,
+
+    /// The derivation scheme used, `Cap26` for accounts created by the
+    /// Babylon wallet and `Bip44Olympia` for accounts created by the Olympia
+    /// wallet.
+    pub scheme: DerivationPathScheme,
+
+    /// The last component of the derivation path of the account.
+    pub derivation_index: HDPathValue,
+}
+
+/// Derives the `count` accounts on `network_id` using `scheme` with the
+/// derivation indices starting at `start_index`, from
+/// `mnemonic_with_passphrase`.
+///
+/// Returns `Err` if `count` exceeds `AccountRecoveryScanner::MAX_COUNT` or if
+/// any index would be `2^31` or larger.
+#[uniffi::export]
+pub fn derive_account_recovery_candidates(
+    mnemonic_with_passphrase: MnemonicWithPassphrase,
+    network_id: NetworkID,
+    scheme: DerivationPathScheme,
+    start_index: HDPathValue,
+    count: HDPathValue,
+) -> Result<Vec<AccountRecoveryCandidate>> {
+    let indices = AccountRecoveryScanner::indices(start_index, count)?;
+    Ok(
+        AccountRecoveryScanner::new(mnemonic_with_passphrase, network_id)
+            .derive_candidates(scheme, indices),
+    )
+}
+
+/// Scans for accounts on `network_id` controlled by
+/// `mnemonic_with_passphrase`, created by either the Babylon or the Olympia
+/// wallet, returning the active ones, see `AccountRecoveryScanner::scan`.
+#[uniffi::export]
+pub fn scan_for_accounts_to_recover(
+    mnemonic_with_passphrase: MnemonicWithPassphrase,
+    network_id: NetworkID,
+    gap_limit: HDPathValue,
+    activity_checker: Arc<dyn AccountActivityChecker>,
+) -> Result<Vec<AccountRecoveryCandidate>> {
+    AccountRecoveryScanner::new(mnemonic_with_passphrase, network_id)
+        .scan(gap_limit, activity_checker.as_ref())
+}
+
+/// Derives accounts on a network from a mnemonic, for users who want to
+/// recover their accounts without a Profile backup, i.e. with only their
+/// mnemonic.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AccountRecoveryScanner {
+    mnemonic_with_passphrase: MnemonicWithPassphrase,
+    network_id: NetworkID,
+    factor_source_id: FactorSourceIDFromHash,
+}
+
+impl AccountRecoveryScanner {
+    /// The number of unhardened derivation indices, i.e. the first index
+    /// which cannot be used as the last component of a derivation path.
+    pub const INDEX_LIMIT: HDPathValue = Profile::DERIVATION_INDEX_LIMIT;
+
+    /// The maximum number of accounts derived by one call of
+    /// `derive_account_recovery_candidates`.
+    pub const MAX_COUNT: HDPathValue = 1000;
+
+    /// The maximum gap limit of a `scan`.
+    pub const MAX_GAP_LIMIT: HDPathValue = 100;
+
+    /// The `count` derivation indices starting at `start_index`.
+    ///
+    /// Returns `Err` if `count` exceeds `MAX_COUNT` or if any index would be
+    /// `INDEX_LIMIT` or larger.
+    pub fn indices(
+        start_index: HDPathValue,
+        count: HDPathValue,
+    ) -> Result<std::ops::Range<HDPathValue>> {
+        start_index
+            .checked_add(count)
+            .filter(|end| count <= Self::MAX_COUNT && *end <= Self::INDEX_LIMIT)
+            .map(|end| start_index..end)
+            .ok_or(CommonError::InvalidDerivationIndexRange {
+                start_index,
+                count,
+            })
+    }
+
+    pub fn new(
+        mnemonic_with_passphrase: MnemonicWithPassphrase,
+        network_id: NetworkID,
+    ) -> Self {
+        let factor_source_id = FactorSourceIDFromHash::new_for_device(
+            mnemonic_with_passphrase.clone(),
+        );
+        Self {
+            mnemonic_with_passphrase,
+            network_id,
+            factor_source_id,
+        }
+    }
+
+    /// Derives the account using `scheme` at derivation index `index`.
+    pub fn derive_candidate(
+        &self,
+        scheme: DerivationPathScheme,
+        index: HDPathValue,
+    ) -> AccountRecoveryCandidate {
+        let private_key = match scheme {
+            DerivationPathScheme::Cap26 => self
+                .mnemonic_with_passphrase
+                .derive_private_key(AccountPath::new(
+                    self.network_id,
+                    CAP26KeyKind::TransactionSigning,
+                    index,
+                )),
+            DerivationPathScheme::Bip44Olympia => self
+                .mnemonic_with_passphrase
+                .derive_private_key(BIP44LikePath::new(index)),
+        };
+        let factor_instance = HierarchicalDeterministicFactorInstance::new(
+            self.factor_source_id.clone(),
+            private_key.public_key(),
+        );
+        let account = Account::with_transaction_signing_factor_instance(
+            self.network_id,
+            factor_instance,
+            DisplayName::new("Unnamed").unwrap(),
+            AppearanceID::from_number_of_accounts_on_network(index as usize),
+        )
+        .expect("Derived transaction signing factor instance should be valid");
+        AccountRecoveryCandidate {
+            account,
+            scheme,
+            derivation_index: index,
+        }
+    }
+
+    /// Derives the accounts using `scheme` at all derivation indices in
+    /// `indices`.
+    pub fn derive_candidates(
+        &self,
+        scheme: DerivationPathScheme,
+        indices: std::ops::Range<HDPathValue>,
+    ) -> Vec<AccountRecoveryCandidate> {
+        indices
+            .map(|index| self.derive_candidate(scheme.clone(), index))
+            .collect()
+    }
+
+    /// Scans for active accounts using first the `Cap26` scheme and then the
+    /// `Bip44Olympia` scheme, from derivation index `0`, until `gap_limit`
+    /// consecutive indices after the last active account have been found
+    /// to be inactive, returning all active accounts found.
+    ///
+    /// Accounts are derived and checked in batches, so that
+    /// `activity_checker` can query the Gateway for many addresses at once.
+    ///
+    /// Returns `Err` if `gap_limit` exceeds `MAX_GAP_LIMIT`.
+    pub fn scan(
+        &self,
+        gap_limit: HDPathValue,
+        activity_checker: &dyn AccountActivityChecker,
+    ) -> Result<Vec<AccountRecoveryCandidate>> {
+        if gap_limit > Self::MAX_GAP_LIMIT {
+            return Err(CommonError::AccountRecoveryScanGapLimitTooLarge {
+                gap_limit,
+                max: Self::MAX_GAP_LIMIT,
+            });
+        }
+        let mut active = Vec::new();
+        for scheme in [
+            DerivationPathScheme::Cap26,
+            DerivationPathScheme::Bip44Olympia,
+        ] {
+            active.extend(self.scan_scheme(
+                scheme,
+                gap_limit,
+                activity_checker,
+            )?);
+        }
+        Ok(active)
+    }
+
+    fn scan_scheme(
+        &self,
+        scheme: DerivationPathScheme,
+        gap_limit: HDPathValue,
+        activity_checker: &dyn AccountActivityChecker,
+    ) -> Result<Vec<AccountRecoveryCandidate>> {
+        let mut active = Vec::new();
+        let mut next_index: HDPathValue = 0;
+        loop {
+            let gap_start = active
+                .last()
+                .map(|c: &AccountRecoveryCandidate| c.derivation_index + 1)
+                .unwrap_or(0);
+            let gap_end =
+                gap_start.saturating_add(gap_limit).min(Self::INDEX_LIMIT);
+            if next_index >= gap_end {
+                break;
+            }
+
+            let candidates =
+                self.derive_candidates(scheme.clone(), next_index..gap_end);
+            debug!(
+                "Checking activity of #{} {:?} accounts from index {}",
+                candidates.len(),
+                scheme,
+                next_index
+            );
+            let active_addresses = activity_checker
+                .active_addresses(
+                    candidates
+                        .iter()
+                        .map(|c| c.account.address.clone())
+                        .collect(),
+                )?
+                .into_iter()
+                .collect::<HashSet<_>>();
+            active.extend(
+                candidates
+                    .into_iter()
+                    .filter(|c| active_addresses.contains(&c.account.address)),
+            );
+            next_index = gap_end;
+        }
+        info!(
+            "Found #{} active {:?} accounts on {}",
+            active.len(),
+            scheme,
+            self.network_id
+        );
+        Ok(active)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use std::sync::RwLock;
+
+    /// Considers the accounts with `active` addresses active, recording all
+    /// addresses asked about.
+    #[derive(Debug, Default)]
+    struct Checker {
+        active: HashSet<AccountAddress>,
+        asked: RwLock<Vec<Vec<AccountAddress>>>,
+    }
+
+    impl AccountActivityChecker for Checker {
+        fn active_addresses(
+            &self,
+            addresses: Vec<AccountAddress>,
+        ) -> Result<Vec<AccountAddress>> {
+            self.asked.write().unwrap().push(addresses.clone());
+            Ok(addresses
+                .into_iter()
+                .filter(|a| self.active.contains(a))
+                .collect())
+        }
+    }
+
+    #[derive(Debug)]
+    struct FailingChecker;
+
+    impl AccountActivityChecker for FailingChecker {
+        fn active_addresses(
+            &self,
+            _addresses: Vec<AccountAddress>,
+        ) -> Result<Vec<AccountAddress>> {
+            Err(CommonError::Unknown)
+        }
+    }
+
+    fn scanner() -> AccountRecoveryScanner {
+        AccountRecoveryScanner::new(
+            MnemonicWithPassphrase::placeholder(),
+            NetworkID::Mainnet,
+        )
+    }
+
+    fn address(
+        scheme: DerivationPathScheme,
+        index: HDPathValue,
+    ) -> AccountAddress {
+        scanner().derive_candidate(scheme, index).account.address
+    }
+
+    #[test]
+    fn derives_babylon_accounts_of_placeholder_profile() {
+        let candidates =
+            scanner().derive_candidates(DerivationPathScheme::Cap26, 0..2);
+        assert_eq!(
+            candidates
+                .iter()
+                .map(|c| c.account.address.clone())
+                .collect_vec(),
+            vec![
+                Account::placeholder_mainnet_alice().address,
+                Account::placeholder_mainnet_bob().address
+            ]
+        );
+        assert_eq!(
+            candidates[0].account.security_state,
+            Account::placeholder_mainnet_alice().security_state
+        );
+    }
+
+    #[test]
+    fn derives_olympia_accounts_using_bip44() {
+        let candidate =
+            scanner().derive_candidate(DerivationPathScheme::Bip44Olympia, 3);
+        let EntitySecurityState::Unsecured { value: control } =
+            candidate.account.security_state.clone();
+        assert_eq!(
+            control.transaction_signing.derivation_path(),
+            BIP44LikePath::new(3).derivation_path()
+        );
+        assert_eq!(
+            control.transaction_signing.factor_source_id,
+            FactorSourceIDFromHash::new_for_device(
+                MnemonicWithPassphrase::placeholder()
+            )
+        );
+        assert_ne!(
+            candidate.account.address,
+            address(DerivationPathScheme::Cap26, 3)
+        );
+        assert_eq!(candidate.derivation_index, 3);
+    }
+
+    #[test]
+    fn derive_account_recovery_candidates_global_function() {
+        let candidates = derive_account_recovery_candidates(
+            MnemonicWithPassphrase::placeholder(),
+            NetworkID::Stokenet,
+            DerivationPathScheme::Cap26,
+            0,
+            2,
+        )
+        .unwrap();
+        assert_eq!(
+            candidates[1].account.address,
+            Account::placeholder_stokenet_diana().address
+        );
+    }
+
+    #[test]
+    fn derive_account_recovery_candidates_invalid_ranges() {
+        let limit = AccountRecoveryScanner::INDEX_LIMIT;
+        for (start_index, count) in [
+            (u32::MAX, 2),
+            (limit, 1),
+            (limit - 1, 2),
+            (0, AccountRecoveryScanner::MAX_COUNT + 1),
+        ] {
+            assert_eq!(
+                derive_account_recovery_candidates(
+                    MnemonicWithPassphrase::placeholder(),
+                    NetworkID::Mainnet,
+                    DerivationPathScheme::Cap26,
+                    start_index,
+                    count,
+                ),
+                Err(CommonError::InvalidDerivationIndexRange {
+                    start_index,
+                    count
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn derive_account_recovery_candidates_up_to_last_index() {
+        let candidates = derive_account_recovery_candidates(
+            MnemonicWithPassphrase::placeholder(),
+            NetworkID::Mainnet,
+            DerivationPathScheme::Bip44Olympia,
+            AccountRecoveryScanner::INDEX_LIMIT - 1,
+            1,
+        )
+        .unwrap();
+        assert_eq!(
+            candidates[0].derivation_index,
+            AccountRecoveryScanner::INDEX_LIMIT - 1
+        );
+    }
+
+    #[test]
+    fn scan_with_too_large_gap_limit_fails() {
+        let checker = Checker::default();
+        assert_eq!(
+            scanner().scan(u32::MAX, &checker),
+            Err(CommonError::AccountRecoveryScanGapLimitTooLarge {
+                gap_limit: u32::MAX,
+                max: AccountRecoveryScanner::MAX_GAP_LIMIT
+            })
+        );
+        assert!(checker.asked.read().unwrap().is_empty());
+    }
+
+    #[test]
+    fn scan_stops_after_gap_limit() {
+        let checker = Checker {
+            active: HashSet::from_iter([
+                address(DerivationPathScheme::Cap26, 0),
+                address(DerivationPathScheme::Cap26, 2),
+                // beyond the gap, not found
+                address(DerivationPathScheme::Cap26, 5),
+                address(DerivationPathScheme::Bip44Olympia, 1),
+            ]),
+            ..Default::default()
+        };
+        let found = scanner().scan(2, &checker).unwrap();
+        assert_eq!(
+            found
+                .iter()
+                .map(|c| (c.scheme.clone(), c.derivation_index))
+                .collect_vec(),
+            vec![
+                (DerivationPathScheme::Cap26, 0),
+                (DerivationPathScheme::Cap26, 2),
+                (DerivationPathScheme::Bip44Olympia, 1),
+            ]
+        );
+        // Cap26: [0, 1], [2], [3, 4] - Bip44: [0, 1], [2, 3]
+        assert_eq!(
+            checker
+                .asked
+                .read()
+                .unwrap()
+                .iter()
+                .map(|a| a.len())
+                .collect_vec(),
+            vec![2, 1, 2, 2, 2]
+        );
+    }
+
+    #[test]
+    fn scan_without_active_accounts() {
+        let checker = Checker::default();
+        assert_eq!(scanner().scan(3, &checker), Ok(Vec::new()));
+        assert_eq!(checker.asked.read().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn scan_with_zero_gap_limit_checks_nothing() {
+        let checker = Checker::default();
+        assert_eq!(scanner().scan(0, &checker), Ok(Vec::new()));
+        assert!(checker.asked.read().unwrap().is_empty());
+    }
+
+    #[test]
+    fn scan_fails_if_checker_fails() {
+        assert_eq!(
+            scan_for_accounts_to_recover(
+                MnemonicWithPassphrase::placeholder(),
+                NetworkID::Mainnet,
+                1,
+                Arc::new(FailingChecker)
+            ),
+            Err(CommonError::Unknown)
+        );
+    }
+
+    #[test]
+    fn recovered_account_can_be_added_to_wallet() {
+        let private =
+            PrivateHierarchicalDeterministicFactorSource::generate_new(
+                WalletClientModel::Unknown,
+            );
+        let (wallet, _) = Wallet::ephemeral(Profile::new(private, "Test"));
+        let candidate =
+            scanner().derive_candidate(DerivationPathScheme::Cap26, 0);
+        wallet
+            .add_private_device_factor_source(
+                PrivateHierarchicalDeterministicFactorSource::placeholder(),
+            )
+            .unwrap();
+        wallet.add_account(candidate.account.clone()).unwrap();
+        assert!(wallet.profile().networks[0]
+            .accounts
+            .contains_id(&candidate.account.address));
+    }
+}
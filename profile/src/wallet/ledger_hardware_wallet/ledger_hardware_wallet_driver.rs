@@ -0,0 +1,105 @@
+use crate::prelude::*;
+use radix_engine_common::crypto::Hash;
+
+/// Communicates with a Ledger hardware wallet, implemented by Wallet Clients
+/// using the Radix Connector Extension or USB, so that Sargon does not do
+/// any I/O itself.
+///
+/// Every request is sent to the Ledger device of the
+/// `LedgerHardwareWalletFactorSource` with `ledger_id`, implementations
+/// SHOULD fail with `WrongLedgerHardwareWallet` if another device is
+/// connected.
+#[uniffi::export]
+pub trait LedgerHardwareWalletDriver: Send + Sync + std::fmt::Debug {
+    /// Derives the public keys at `derivation_paths`, in the same order.
+    fn derive_public_keys(
+        &self,
+        ledger_id: FactorSourceIDFromHash,
+        derivation_paths: Vec<DerivationPath>,
+    ) -> Result<Vec<HierarchicalDeterministicPublicKey>>;
+
+    /// Signs `transaction_intent_hash` with the keys at `derivation_paths`,
+    /// returning the signatures in the same order.
+    fn sign_transaction(
+        &self,
+        ledger_id: FactorSourceIDFromHash,
+        transaction_intent_hash: Hex32Bytes,
+        derivation_paths: Vec<DerivationPath>,
+    ) -> Result<Vec<HierarchicalDeterministicSignature>>;
+
+    /// Signs the authentication `challenge` of the dApp with
+    /// `dapp_definition_address` at `origin` with the keys at
+    /// `derivation_paths`, returning the signatures - of the hash
+    /// `authentication_challenge_hash` - in the same order.
+    fn sign_authentication_challenge(
+        &self,
+        ledger_id: FactorSourceIDFromHash,
+        challenge: Hex32Bytes,
+        dapp_definition_address: AccountAddress,
+        origin: String,
+        derivation_paths: Vec<DerivationPath>,
+    ) -> Result<Vec<HierarchicalDeterministicSignature>>;
+}
+
+/// The prefix of the payload of an authentication challenge, `R` as in
+/// "Radix Off-Ledger Authentication".
+const AUTHENTICATION_CHALLENGE_PREFIX: u8 = 0x52;
+
+/// The hash actually signed when signing an authentication challenge, i.e.
+/// the hash of the concatenation of the prefix `R`, the 32 bytes of
+/// `challenge`, the length of `dapp_definition_address`, the
+/// `dapp_definition_address` and the `origin`.
+pub fn authentication_challenge_hash(
+    challenge: &Hex32Bytes,
+    dapp_definition_address: &AccountAddress,
+    origin: &str,
+) -> Hash {
+    let address = dapp_definition_address.address.as_bytes();
+    let mut payload = vec![AUTHENTICATION_CHALLENGE_PREFIX];
+    payload.extend(challenge.bytes());
+    payload.push(address.len() as u8);
+    payload.extend(address);
+    payload.extend(origin.as_bytes());
+    hash(payload)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn authentication_challenge_hash_depends_on_all_inputs() {
+        let challenge = Hex32Bytes::placeholder();
+        let address = AccountAddress::placeholder();
+        let origin = "https://dashboard.radixdlt.com";
+        let sut = authentication_challenge_hash(&challenge, &address, origin);
+        assert_eq!(
+            sut,
+            authentication_challenge_hash(&challenge, &address, origin)
+        );
+        assert_ne!(
+            sut,
+            authentication_challenge_hash(
+                &Hex32Bytes::placeholder_other(),
+                &address,
+                origin
+            )
+        );
+        assert_ne!(
+            sut,
+            authentication_challenge_hash(
+                &challenge,
+                &AccountAddress::placeholder_other(),
+                origin
+            )
+        );
+        assert_ne!(
+            sut,
+            authentication_challenge_hash(
+                &challenge,
+                &address,
+                "https://radixdlt.com"
+            )
+        );
+    }
+}
@@ -0,0 +1,284 @@
+use crate::prelude::*;
+use radix_engine_common::crypto::Hash;
+
+/// An in-process `LedgerHardwareWalletDriver` backed by a known mnemonic,
+/// used to test the Ledger flows without any hardware.
+#[derive(Debug, PartialEq, Eq)]
+pub struct LedgerHardwareWalletSimulator {
+    /// The mnemonic "stored" on the simulated device.
+    mnemonic_with_passphrase: MnemonicWithPassphrase,
+
+    /// The hint of the `LedgerHardwareWalletFactorSource` of the simulated
+    /// device.
+    pub hint: LedgerHardwareWalletHint,
+}
+
+impl LedgerHardwareWalletSimulator {
+    pub fn new(
+        mnemonic_with_passphrase: MnemonicWithPassphrase,
+        hint: LedgerHardwareWalletHint,
+    ) -> Self {
+        Self {
+            mnemonic_with_passphrase,
+            hint,
+        }
+    }
+
+    /// The ID of the `LedgerHardwareWalletFactorSource` of the simulated
+    /// device.
+    pub fn id(&self) -> FactorSourceIDFromHash {
+        FactorSourceIDFromHash::from_mnemonic_with_passphrase(
+            FactorSourceKind::LedgerHQHardwareWallet,
+            self.mnemonic_with_passphrase.clone(),
+        )
+    }
+
+    /// A new `LedgerHardwareWalletFactorSource` for the simulated device,
+    /// supporting both Babylon and Olympia, as a real Ledger does.
+    pub fn factor_source(&self) -> LedgerHardwareWalletFactorSource {
+        LedgerHardwareWalletFactorSource::new(
+            self.id(),
+            FactorSourceCommon::new(
+                FactorSourceCryptoParameters::babylon_olympia_compatible(),
+                [],
+            ),
+            self.hint.clone(),
+        )
+    }
+
+    fn ensure_connected(
+        &self,
+        ledger_id: &FactorSourceIDFromHash,
+    ) -> Result<()> {
+        let id = self.id();
+        if *ledger_id != id {
+            return Err(CommonError::WrongLedgerHardwareWallet {
+                expected: ledger_id.clone(),
+                found: id,
+            });
+        }
+        Ok(())
+    }
+
+    fn sign(
+        &self,
+        ledger_id: FactorSourceIDFromHash,
+        hash: &Hash,
+        derivation_paths: Vec<DerivationPath>,
+    ) -> Result<Vec<HierarchicalDeterministicSignature>> {
+        self.ensure_connected(&ledger_id)?;
+        Ok(derivation_paths
+            .into_iter()
+            .map(|path| {
+                self.mnemonic_with_passphrase
+                    .derive_private_key(path)
+                    .sign(hash)
+            })
+            .collect())
+    }
+}
+
+impl HasPlaceholder for LedgerHardwareWalletSimulator {
+    /// A placeholder used to facilitate unit tests, simulating the device of
+    /// `LedgerHardwareWalletFactorSource::placeholder()`.
+    fn placeholder() -> Self {
+        Self::new(
+            MnemonicWithPassphrase::placeholder(),
+            LedgerHardwareWalletHint::placeholder(),
+        )
+    }
+
+    /// A placeholder used to facilitate unit tests, simulating the device of
+    /// `LedgerHardwareWalletFactorSource::placeholder_other()`.
+    fn placeholder_other() -> Self {
+        Self::new(
+            MnemonicWithPassphrase::placeholder_other(),
+            LedgerHardwareWalletHint::placeholder_other(),
+        )
+    }
+}
+
+impl LedgerHardwareWalletDriver for LedgerHardwareWalletSimulator {
+    fn derive_public_keys(
+        &self,
+        ledger_id: FactorSourceIDFromHash,
+        derivation_paths: Vec<DerivationPath>,
+    ) -> Result<Vec<HierarchicalDeterministicPublicKey>> {
+        self.ensure_connected(&ledger_id)?;
+        Ok(derivation_paths
+            .into_iter()
+            .map(|path| {
+                self.mnemonic_with_passphrase
+                    .derive_private_key(path)
+                    .public_key()
+            })
+            .collect())
+    }
+
+    fn sign_transaction(
+        &self,
+        ledger_id: FactorSourceIDFromHash,
+        transaction_intent_hash: Hex32Bytes,
+        derivation_paths: Vec<DerivationPath>,
+    ) -> Result<Vec<HierarchicalDeterministicSignature>> {
+        self.sign(
+            ledger_id,
+            &Hash::from_bytes(transaction_intent_hash.bytes()),
+            derivation_paths,
+        )
+    }
+
+    fn sign_authentication_challenge(
+        &self,
+        ledger_id: FactorSourceIDFromHash,
+        challenge: Hex32Bytes,
+        dapp_definition_address: AccountAddress,
+        origin: String,
+        derivation_paths: Vec<DerivationPath>,
+    ) -> Result<Vec<HierarchicalDeterministicSignature>> {
+        self.sign(
+            ledger_id,
+            &authentication_challenge_hash(
+                &challenge,
+                &dapp_definition_address,
+                &origin,
+            ),
+            derivation_paths,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use radix_engine_common::crypto::Hash;
+
+    fn sut() -> LedgerHardwareWalletSimulator {
+        LedgerHardwareWalletSimulator::placeholder()
+    }
+
+    fn ledger_id() -> FactorSourceIDFromHash {
+        LedgerHardwareWalletFactorSource::placeholder().id
+    }
+
+    fn paths() -> Vec<DerivationPath> {
+        vec![
+            AccountPath::new(
+                NetworkID::Mainnet,
+                CAP26KeyKind::TransactionSigning,
+                0,
+            )
+            .into(),
+            BIP44LikePath::new(1).into(),
+        ]
+    }
+
+    #[test]
+    fn factor_source() {
+        let factor_source = sut().factor_source();
+        assert_eq!(factor_source.id, ledger_id());
+        assert_eq!(factor_source.hint, LedgerHardwareWalletHint::placeholder());
+        assert_eq!(
+            factor_source.common.crypto_parameters,
+            FactorSourceCryptoParameters::babylon_olympia_compatible()
+        );
+    }
+
+    #[test]
+    fn derive_public_keys() {
+        let mwp = MnemonicWithPassphrase::placeholder();
+        let keys = sut().derive_public_keys(ledger_id(), paths()).unwrap();
+        assert_eq!(
+            keys,
+            paths()
+                .into_iter()
+                .map(|p| mwp.derive_private_key(p).public_key())
+                .collect_vec()
+        );
+        assert_eq!(keys[0], HierarchicalDeterministicPublicKey::placeholder());
+    }
+
+    #[test]
+    fn sign_transaction() {
+        let intent_hash = Hex32Bytes::placeholder();
+        let signatures = sut()
+            .sign_transaction(ledger_id(), intent_hash.clone(), paths())
+            .unwrap();
+        let keys = sut().derive_public_keys(ledger_id(), paths()).unwrap();
+        assert_eq!(
+            signatures
+                .iter()
+                .map(|s| s.public_key.clone())
+                .collect_vec(),
+            keys
+        );
+        assert!(signatures
+            .iter()
+            .all(|s| s.is_valid_for(&Hash::from_bytes(intent_hash.bytes()))));
+    }
+
+    #[test]
+    fn sign_authentication_challenge() {
+        let challenge = Hex32Bytes::placeholder_other();
+        let dapp_definition_address = AccountAddress::placeholder();
+        let origin = "https://dashboard.radixdlt.com".to_owned();
+        let signatures = sut()
+            .sign_authentication_challenge(
+                ledger_id(),
+                challenge.clone(),
+                dapp_definition_address.clone(),
+                origin.clone(),
+                paths(),
+            )
+            .unwrap();
+        let signed_hash = authentication_challenge_hash(
+            &challenge,
+            &dapp_definition_address,
+            &origin,
+        );
+        assert_eq!(signatures.len(), 2);
+        assert!(signatures.iter().all(|s| s.is_valid_for(&signed_hash)));
+        assert!(
+            !signatures[0].is_valid_for(&Hash::from_bytes(challenge.bytes()))
+        );
+    }
+
+    #[test]
+    fn wrong_ledger_fails() {
+        let other = LedgerHardwareWalletFactorSource::placeholder_other().id;
+        let expected_error = CommonError::WrongLedgerHardwareWallet {
+            expected: other.clone(),
+            found: ledger_id(),
+        };
+        assert_eq!(
+            sut().derive_public_keys(other.clone(), paths()),
+            Err(expected_error.clone())
+        );
+        assert_eq!(
+            sut().sign_transaction(
+                other.clone(),
+                Hex32Bytes::placeholder(),
+                paths()
+            ),
+            Err(expected_error.clone())
+        );
+        assert_eq!(
+            sut().sign_authentication_challenge(
+                other,
+                Hex32Bytes::placeholder(),
+                AccountAddress::placeholder(),
+                "https://dashboard.radixdlt.com".to_owned(),
+                paths()
+            ),
+            Err(expected_error)
+        );
+    }
+
+    #[test]
+    fn placeholder_other_simulates_other_ledger() {
+        assert_eq!(
+            LedgerHardwareWalletSimulator::placeholder_other().id(),
+            LedgerHardwareWalletFactorSource::placeholder_other().id
+        );
+    }
+}
@@ -0,0 +1,5 @@
+mod ledger_hardware_wallet_driver;
+mod ledger_hardware_wallet_simulator;
+
+pub use ledger_hardware_wallet_driver::*;
+pub use ledger_hardware_wallet_simulator::*;
@@ -1,9 +1,35 @@
+mod account_recovery_scan;
+mod ledger_hardware_wallet;
+mod olympia_wallet_export;
+mod profile_backup;
 mod secure_storage;
 mod wallet;
 mod wallet_accounts;
+mod wallet_app_preferences;
+mod wallet_authorized_dapps;
+mod wallet_factor_sources;
+mod wallet_mnemonic_backup;
+mod wallet_observer;
+mod wallet_olympia_import;
+mod wallet_personas;
+mod wallet_profile_backup;
 mod wallet_profile_io;
+mod wallet_profiles;
 
+pub use account_recovery_scan::*;
+pub use ledger_hardware_wallet::*;
+pub use olympia_wallet_export::*;
+pub use profile_backup::*;
 pub use secure_storage::*;
 pub use wallet::*;
 pub use wallet_accounts::*;
+pub use wallet_app_preferences::*;
+pub use wallet_authorized_dapps::*;
+pub use wallet_factor_sources::*;
+pub use wallet_mnemonic_backup::*;
+pub use wallet_observer::*;
+pub use wallet_olympia_import::*;
+pub use wallet_personas::*;
+pub use wallet_profile_backup::*;
 pub use wallet_profile_io::*;
+pub use wallet_profiles::*;
@@ -0,0 +1,393 @@
+use crate::prelude::*;
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+
+/// The kind of an account exported by the Olympia wallet.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, uniffi::Enum)]
+pub enum OlympiaAccountType {
+    /// An account controlled by the mnemonic of the Olympia wallet, encoded
+    /// as `S` in the export.
+    Software,
+
+    /// An account controlled by a Ledger hardware wallet, encoded as `H` in
+    /// the export.
+    Hardware,
+}
+
+impl FromStr for OlympiaAccountType {
+    type Err = CommonError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "S" => Ok(Self::Software),
+            "H" => Ok(Self::Hardware),
+            _ => Err(CommonError::InvalidOlympiaWalletExportPayload(
+                s.to_owned(),
+            )),
+        }
+    }
+}
+
+/// An account exported by the Olympia wallet, which can be imported as an
+/// Olympia-scheme `Account` controlled by a `BIP44LikePath` secp256k1 key.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, uniffi::Record)]
+pub struct OlympiaAccountExport {
+    pub account_type: OlympiaAccountType,
+
+    /// The public key of the account, derived at `derivation_path`.
+    pub public_key: Secp256k1PublicKey,
+
+    pub derivation_path: BIP44LikePath,
+
+    /// The name of the account, "Unnamed" if it had no name and truncated
+    /// to `DisplayName::MAX_LEN` characters if longer.
+    pub display_name: DisplayName,
+}
+
+impl OlympiaAccountExport {
+    const INNER_SEPARATOR: char = '^';
+    const END_OF_ACCOUNT_NAME: char = '}';
+
+    /// Parses an account on the format `<type>^<public_key>^<index>^<name>}`,
+    /// where `public_key` is a base64 encoded compressed secp256k1 public key
+    /// and `index` is the BIP44 address index.
+    fn from_export_str(s: &str) -> Result<Self> {
+        let invalid =
+            || CommonError::InvalidOlympiaWalletExportPayload(s.to_owned());
+
+        let mut components = s.splitn(4, Self::INNER_SEPARATOR);
+        let mut next = || components.next().ok_or_else(invalid);
+        let account_type = next()?.parse::<OlympiaAccountType>()?;
+        let public_key = BASE64
+            .decode(next()?)
+            .map_err(|_| invalid())
+            .and_then(|bytes| Secp256k1PublicKey::try_from(bytes.as_slice()))?;
+        let index = next()?
+            .parse::<HDPathValue>()
+            .ok()
+            .filter(|i| *i < (1 << 31))
+            .ok_or_else(invalid)?;
+        let name = components
+            .next()
+            .unwrap_or_default()
+            .trim_end_matches(Self::END_OF_ACCOUNT_NAME);
+
+        Ok(Self {
+            account_type,
+            public_key,
+            derivation_path: BIP44LikePath::new(index),
+            display_name: Self::display_name_from(name),
+        })
+    }
+
+    fn display_name_from(name: &str) -> DisplayName {
+        let truncated = name
+            .trim()
+            .chars()
+            .take(DisplayName::MAX_LEN)
+            .collect::<String>();
+        DisplayName::new(&truncated).unwrap_or_default()
+    }
+
+    /// The address of the account on `network_id`.
+    pub fn address(&self, network_id: NetworkID) -> AccountAddress {
+        AccountAddress::new(self.public_key.clone().into(), network_id)
+    }
+
+    /// Converts this exported account into an `Account` on `network_id`
+    /// controlled by the FactorSource with `factor_source_id`.
+    pub fn to_account(
+        &self,
+        network_id: NetworkID,
+        factor_source_id: FactorSourceIDFromHash,
+        appearance_id: AppearanceID,
+    ) -> Account {
+        let public_key = HierarchicalDeterministicPublicKey::new(
+            self.public_key.clone().into(),
+            self.derivation_path.derivation_path(),
+        );
+        Account::with_transaction_signing_factor_instance(
+            network_id,
+            HierarchicalDeterministicFactorInstance::new(
+                factor_source_id,
+                public_key,
+            ),
+            self.display_name.clone(),
+            appearance_id,
+        )
+        .expect("BIP44 factor instance should be valid")
+    }
+}
+
+/// All accounts exported by the Olympia wallet, parsed from the payloads of
+/// the QR codes shown by it.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, uniffi::Record)]
+pub struct OlympiaWalletExport {
+    /// The number of words of the mnemonic of the Olympia wallet, used to
+    /// let the user input the mnemonic when importing software accounts.
+    pub mnemonic_word_count: BIP39WordCount,
+
+    pub accounts: Vec<OlympiaAccountExport>,
+}
+
+/// Parses the payloads of all QR codes shown by the Olympia wallet, in any
+/// order, into an `OlympiaWalletExport`.
+#[uniffi::export]
+pub fn new_olympia_wallet_export_from_payloads(
+    payloads: Vec<String>,
+) -> Result<OlympiaWalletExport> {
+    OlympiaWalletExport::from_payloads(payloads)
+}
+
+/// The header of a single payload of an Olympia wallet export.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct OlympiaExportPayloadHeader {
+    payload_count: u16,
+    payload_index: u16,
+    mnemonic_word_count: BIP39WordCount,
+}
+
+impl OlympiaWalletExport {
+    const HEADER_SEPARATOR: char = ']';
+    const OUTER_SEPARATOR: char = '~';
+
+    /// Parses the payloads of an Olympia wallet export, each on the format
+    /// `<payload_count>^<payload_index>^<mnemonic_word_count>]<accounts>`,
+    /// where `accounts` are separated by `~`, and accounts might span more
+    /// than one payload.
+    ///
+    /// Returns `Err` if not exactly all `payload_count` payloads are
+    /// present, or if any payload or account is invalid.
+    pub fn from_payloads<I, S>(payloads: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut parsed = payloads
+            .into_iter()
+            .map(|p| Self::parse_payload(p.as_ref()))
+            .collect::<Result<Vec<_>>>()?;
+        parsed.sort_by_key(|(header, _)| header.payload_index);
+
+        let first = parsed.first().map(|(h, _)| h.clone()).ok_or(
+            CommonError::IncompleteOlympiaWalletExport {
+                expected: 1,
+                found: 0,
+            },
+        )?;
+        let is_consistent = parsed.iter().all(|(h, _)| {
+            h.payload_count == first.payload_count
+                && h.mnemonic_word_count == first.mnemonic_word_count
+        });
+        if !is_consistent {
+            return Err(CommonError::InvalidOlympiaWalletExportPayload(
+                "Payloads of different exports".to_owned(),
+            ));
+        }
+
+        let is_complete = parsed
+            .iter()
+            .map(|(h, _)| h.payload_index)
+            .eq(0..first.payload_count);
+        if !is_complete {
+            return Err(CommonError::IncompleteOlympiaWalletExport {
+                expected: first.payload_count,
+                found: parsed.len() as u16,
+            });
+        }
+
+        let accounts = parsed
+            .into_iter()
+            .map(|(_, body)| body)
+            .join("")
+            .split(Self::OUTER_SEPARATOR)
+            .filter(|a| !a.is_empty())
+            .map(OlympiaAccountExport::from_export_str)
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            mnemonic_word_count: first.mnemonic_word_count,
+            accounts,
+        })
+    }
+
+    fn parse_payload(
+        payload: &str,
+    ) -> Result<(OlympiaExportPayloadHeader, String)> {
+        let invalid = || {
+            CommonError::InvalidOlympiaWalletExportPayload(payload.to_owned())
+        };
+        let (header, body) = payload
+            .split_once(Self::HEADER_SEPARATOR)
+            .ok_or_else(invalid)?;
+        let components = header
+            .split(OlympiaAccountExport::INNER_SEPARATOR)
+            .map(|c| c.parse::<u16>().map_err(|_| invalid()))
+            .collect::<Result<Vec<_>>>()?;
+        let &[payload_count, payload_index, word_count] = components.as_slice()
+        else {
+            return Err(invalid());
+        };
+        if payload_index >= payload_count {
+            return Err(invalid());
+        }
+        let header = OlympiaExportPayloadHeader {
+            payload_count,
+            payload_index,
+            mnemonic_word_count: BIP39WordCount::from_count(
+                word_count as usize,
+            )?,
+        };
+        Ok((header, body.to_owned()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    const ALICE_BASE64: &str = "AlF7iJFufzFbtoL5kmsUvGeg5CRvikGbmGJp4afmH/+n";
+
+    #[test]
+    fn parse_single_payload() {
+        let sut = OlympiaWalletExport::from_payloads([format!(
+            "1^0^12]S^{}^0^Savings}}~H^{}^7^}}",
+            ALICE_BASE64, ALICE_BASE64
+        )])
+        .unwrap();
+        assert_eq!(sut.mnemonic_word_count, BIP39WordCount::Twelve);
+        assert_eq!(
+            sut.accounts,
+            vec![
+                OlympiaAccountExport {
+                    account_type: OlympiaAccountType::Software,
+                    public_key: Secp256k1PublicKey::placeholder_alice(),
+                    derivation_path: BIP44LikePath::new(0),
+                    display_name: DisplayName::new("Savings").unwrap(),
+                },
+                OlympiaAccountExport {
+                    account_type: OlympiaAccountType::Hardware,
+                    public_key: Secp256k1PublicKey::placeholder_alice(),
+                    derivation_path: BIP44LikePath::new(7),
+                    display_name: DisplayName::default(),
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_account_spanning_payloads_in_any_order() {
+        let account = format!("S^{}^3^Spending}}", ALICE_BASE64);
+        let (head, tail) = account.split_at(10);
+        let sut = new_olympia_wallet_export_from_payloads(vec![
+            format!("2^1^24]{}", tail),
+            format!("2^0^24]{}", head),
+        ])
+        .unwrap();
+        assert_eq!(sut.mnemonic_word_count, BIP39WordCount::TwentyFour);
+        assert_eq!(sut.accounts.len(), 1);
+        assert_eq!(sut.accounts[0].derivation_path, BIP44LikePath::new(3));
+        assert_eq!(sut.accounts[0].display_name.value, "Spending");
+    }
+
+    #[test]
+    fn long_name_is_truncated() {
+        let sut = OlympiaWalletExport::from_payloads([format!(
+            "1^0^12]S^{}^0^{}}}",
+            ALICE_BASE64,
+            "a".repeat(40)
+        )])
+        .unwrap();
+        assert_eq!(sut.accounts[0].display_name.value, "a".repeat(30));
+    }
+
+    #[test]
+    fn missing_payload() {
+        assert_eq!(
+            OlympiaWalletExport::from_payloads(["3^0^12]", "3^2^12]"]),
+            Err(CommonError::IncompleteOlympiaWalletExport {
+                expected: 3,
+                found: 2
+            })
+        );
+        assert_eq!(
+            OlympiaWalletExport::from_payloads(Vec::<String>::new()),
+            Err(CommonError::IncompleteOlympiaWalletExport {
+                expected: 1,
+                found: 0
+            })
+        );
+    }
+
+    #[test]
+    fn duplicate_payload() {
+        assert_eq!(
+            OlympiaWalletExport::from_payloads(["2^0^12]", "2^0^12]"]),
+            Err(CommonError::IncompleteOlympiaWalletExport {
+                expected: 2,
+                found: 2
+            })
+        );
+    }
+
+    #[test]
+    fn payloads_of_different_exports() {
+        assert!(matches!(
+            OlympiaWalletExport::from_payloads(["2^0^12]", "2^1^24]"]),
+            Err(CommonError::InvalidOlympiaWalletExportPayload(_))
+        ));
+    }
+
+    #[test]
+    fn invalid_payloads() {
+        for payload in [
+            "no header",
+            "1^0]",
+            "1^1^12]",
+            "1^0^13]",
+            "1^0^12]X^AlF7^0^Name}",
+            "1^0^12]S^not base64^0^Name}",
+            "1^0^12]S^AAAA^0^Name}",
+        ] {
+            assert!(
+                OlympiaWalletExport::from_payloads([payload]).is_err(),
+                "{}",
+                payload
+            );
+        }
+        assert!(OlympiaWalletExport::from_payloads([format!(
+            "1^0^12]S^{}^2147483648^Name}}",
+            ALICE_BASE64
+        )])
+        .is_err());
+    }
+
+    #[test]
+    fn to_account() {
+        let export = OlympiaWalletExport::from_payloads([format!(
+            "1^0^12]S^{}^5^Savings}}",
+            ALICE_BASE64
+        )])
+        .unwrap();
+        let account = export.accounts[0].to_account(
+            NetworkID::Mainnet,
+            FactorSourceIDFromHash::placeholder_other(),
+            AppearanceID::gradient0(),
+        );
+        assert_eq!(
+            account.address,
+            export.accounts[0].address(NetworkID::Mainnet)
+        );
+        assert_eq!(account.display_name.value, "Savings");
+        let EntitySecurityState::Unsecured { value: control } =
+            account.security_state;
+        assert_eq!(
+            control.transaction_signing.derivation_path(),
+            BIP44LikePath::new(5).derivation_path()
+        );
+        assert_eq!(
+            control.transaction_signing.factor_source_id,
+            FactorSourceIDFromHash::placeholder_other()
+        );
+    }
+}
@@ -0,0 +1,329 @@
+use crate::prelude::*;
+
+/// The version of the encrypted Profile snapshot envelope format (JSON),
+/// unrelated to the `ProfileSnapshotVersion` of the encrypted Profile.
+#[derive(
+    Serialize_repr,
+    Deserialize_repr,
+    FromRepr,
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    Default,
+)]
+#[repr(u16)]
+pub enum EncryptedProfileSnapshotVersion {
+    /// Argon2id key derivation and AES-GCM-256 encryption.
+    #[default]
+    V1 = 1,
+}
+
+impl EncryptedProfileSnapshotVersion {
+    pub fn discriminant(&self) -> u16 {
+        *self as u16
+    }
+}
+
+/// A password encrypted Profile backup, a versioned envelope containing
+/// all information needed - apart from the password - to decrypt the
+/// JSON serialized Profile snapshot.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct EncryptedProfileSnapshot {
+    /// The version of this envelope format.
+    pub version: EncryptedProfileSnapshotVersion,
+
+    /// How the encryption key is derived from the password.
+    pub key_derivation_scheme: PasswordBasedKeyDerivationScheme,
+
+    /// The authenticated cipher used to encrypt the snapshot.
+    pub encryption_scheme: EncryptionScheme,
+
+    /// The sealed JSON serialized Profile.
+    pub encrypted_snapshot: BagOfBytes,
+}
+
+impl EncryptedProfileSnapshot {
+    /// Encrypts `profile` with a key derived from `password` using the
+    /// default (newest) schemes and a random salt and nonce.
+    pub fn encrypting(
+        profile: &Profile,
+        password: impl AsRef<str>,
+    ) -> Result<Self> {
+        let key_derivation_scheme = PasswordBasedKeyDerivationScheme::default();
+        let encryption_scheme = EncryptionScheme::default();
+        let key = key_derivation_scheme.derive_key(password.as_ref())?;
+        let json = serde_json::to_vec(profile)
+            .map_err(|_| CommonError::FailedToSerializeToJSON)?;
+        let encrypted_snapshot = encryption_scheme.encrypt(json, &key)?;
+        Ok(Self {
+            version: EncryptedProfileSnapshotVersion::default(),
+            key_derivation_scheme,
+            encryption_scheme,
+            encrypted_snapshot: encrypted_snapshot.into(),
+        })
+    }
+
+    /// Decrypts the sealed snapshot with a key derived from `password` and
+    /// deserializes it into a `Profile`.
+    pub fn decrypt(&self, password: impl AsRef<str>) -> Result<Profile> {
+        let key = self.key_derivation_scheme.derive_key(password.as_ref())?;
+        let json = self
+            .encryption_scheme
+            .decrypt(self.encrypted_snapshot.as_slice(), &key)?;
+        Profile::from_snapshot_json_bytes(json)
+    }
+}
+
+/// The decoded contents of a Profile backup file, which is either a
+/// plaintext Profile snapshot or a password encrypted one.
+#[derive(Clone, Debug, PartialEq, Eq, EnumAsInner)]
+pub enum ProfileBackupContents {
+    Plaintext(Profile),
+    Encrypted(EncryptedProfileSnapshot),
+}
+
+impl ProfileBackupContents {
+    /// Detects the format of `bytes`, without decrypting anything, failing
+    /// with distinct errors if the bytes are in an unknown format or the
+    /// format is known but of an unsupported version. Plaintext snapshots of
+    /// older versions are migrated to the current version.
+    pub fn decode(bytes: impl AsRef<[u8]>) -> Result<Self> {
+        let bytes = bytes.as_ref();
+        let json: serde_json::Value = serde_json::from_slice(bytes)
+            .map_err(|_| CommonError::UnsupportedProfileSnapshotFormat)?;
+
+        let parse_version = |v: &serde_json::Value| {
+            v.as_u64()
+                .and_then(|v| u16::try_from(v).ok())
+                .ok_or(CommonError::UnsupportedProfileSnapshotFormat)
+        };
+
+        if let Some(version) =
+            json.get("header").and_then(|h| h.get("snapshotVersion"))
+        {
+            parse_version(version)?;
+            return ProfileSnapshotMigrator::default()
+                .profile_from_json(json, bytes.len())
+                .map(Self::Plaintext);
+        }
+
+        if json.get("encryptedSnapshot").is_some() {
+            let version = json
+                .get("version")
+                .ok_or(CommonError::UnsupportedProfileSnapshotFormat)
+                .and_then(parse_version)?;
+            if EncryptedProfileSnapshotVersion::from_repr(version).is_none() {
+                return Err(
+                    CommonError::UnsupportedEncryptedProfileSnapshotVersion(
+                        version,
+                    ),
+                );
+            }
+            return Self::deserialize(json, bytes.len()).map(Self::Encrypted);
+        }
+
+        Err(CommonError::UnsupportedProfileSnapshotFormat)
+    }
+
+    /// Returns the Profile, decrypting it using `password` if needed.
+    pub fn into_profile(self, password: Option<String>) -> Result<Profile> {
+        match self {
+            Self::Plaintext(profile) => Ok(profile),
+            Self::Encrypted(encrypted) => password
+                .ok_or(
+                    CommonError::PasswordRequiredToDecryptEncryptedProfileSnapshot,
+                )
+                .and_then(|p| encrypted.decrypt(p)),
+        }
+    }
+
+    fn deserialize<T>(
+        json: serde_json::Value,
+        json_byte_count: usize,
+    ) -> Result<T>
+    where
+        T: for<'a> serde::Deserialize<'a>,
+    {
+        serde_json::from_value(json).map_err(|_| {
+            CommonError::FailedToDeserializeJSONToValue {
+                json_byte_count,
+                type_name: std::any::type_name::<T>().to_string(),
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn encrypt_decrypt_roundtrip() {
+        let profile = Profile::placeholder();
+        let sut =
+            EncryptedProfileSnapshot::encrypting(&profile, "babylon").unwrap();
+        assert_eq!(sut.decrypt("babylon").unwrap(), profile);
+    }
+
+    #[test]
+    fn wrong_password() {
+        let sut = EncryptedProfileSnapshot::encrypting(
+            &Profile::placeholder(),
+            "babylon",
+        )
+        .unwrap();
+        assert_eq!(
+            sut.decrypt("olympia"),
+            Err(CommonError::FailedToDecryptEncryptedProfileSnapshot)
+        );
+    }
+
+    #[test]
+    fn crafted_key_derivation_parameters_are_rejected() {
+        let mut sut = EncryptedProfileSnapshot::encrypting(
+            &Profile::placeholder(),
+            "babylon",
+        )
+        .unwrap();
+        let PasswordBasedKeyDerivationScheme::Argon2id(ref mut parameters) =
+            sut.key_derivation_scheme;
+        parameters.memory_cost_kib = u32::MAX;
+        assert!(matches!(
+            sut.decrypt("babylon"),
+            Err(
+                CommonError::PasswordBasedKeyDerivationParametersExceedLimits {
+                    memory_cost_kib: u32::MAX,
+                    ..
+                }
+            )
+        ));
+    }
+
+    #[test]
+    fn encrypted_snapshot_does_not_contain_plaintext() {
+        let profile = Profile::placeholder();
+        let sut =
+            EncryptedProfileSnapshot::encrypting(&profile, "babylon").unwrap();
+        let json = serde_json::to_string(&sut).unwrap();
+        assert!(!json.contains(&profile.id().to_string()));
+    }
+
+    #[test]
+    fn envelope_json_roundtrip() {
+        let sut = EncryptedProfileSnapshot::encrypting(
+            &Profile::placeholder(),
+            "babylon",
+        )
+        .unwrap();
+        assert_json_roundtrip(&sut);
+        let json = serde_json::to_value(&sut).unwrap();
+        assert_eq!(json["version"], json!(1));
+        assert_eq!(json["encryptionScheme"], json!("AES-GCM-256"));
+        assert_eq!(json["keyDerivationScheme"]["algorithm"], json!("argon2id"));
+    }
+
+    #[test]
+    fn decode_plaintext() {
+        let profile = Profile::placeholder();
+        let bytes = serde_json::to_vec(&profile).unwrap();
+        assert_eq!(
+            ProfileBackupContents::decode(bytes),
+            Ok(ProfileBackupContents::Plaintext(profile))
+        );
+    }
+
+    #[test]
+    fn decode_encrypted() {
+        let sut = EncryptedProfileSnapshot::encrypting(
+            &Profile::placeholder(),
+            "babylon",
+        )
+        .unwrap();
+        let bytes = serde_json::to_vec(&sut).unwrap();
+        assert_eq!(
+            ProfileBackupContents::decode(bytes),
+            Ok(ProfileBackupContents::Encrypted(sut))
+        );
+    }
+
+    #[test]
+    fn decode_not_json() {
+        assert_eq!(
+            ProfileBackupContents::decode(vec![0xde, 0xad, 0xbe, 0xef]),
+            Err(CommonError::UnsupportedProfileSnapshotFormat)
+        );
+    }
+
+    #[test]
+    fn decode_unknown_json() {
+        assert_eq!(
+            ProfileBackupContents::decode(
+                serde_json::to_vec(&json!({ "foo": "bar" })).unwrap()
+            ),
+            Err(CommonError::UnsupportedProfileSnapshotFormat)
+        );
+    }
+
+    #[test]
+    fn decode_unsupported_profile_snapshot_version() {
+        let mut json = serde_json::to_value(Profile::placeholder()).unwrap();
+        json["header"]["snapshotVersion"] = json!(999);
+        assert_eq!(
+            ProfileBackupContents::decode(serde_json::to_vec(&json).unwrap()),
+            Err(CommonError::UnsupportedProfileSnapshotVersion(999))
+        );
+    }
+
+    #[test]
+    fn decode_unsupported_encrypted_snapshot_version() {
+        let sut = EncryptedProfileSnapshot::encrypting(
+            &Profile::placeholder(),
+            "babylon",
+        )
+        .unwrap();
+        let mut json = serde_json::to_value(sut).unwrap();
+        json["version"] = json!(2);
+        assert_eq!(
+            ProfileBackupContents::decode(serde_json::to_vec(&json).unwrap()),
+            Err(CommonError::UnsupportedEncryptedProfileSnapshotVersion(2))
+        );
+    }
+
+    #[test]
+    fn decode_invalid_profile() {
+        let json = json!({ "header": { "snapshotVersion": 100 } });
+        assert!(matches!(
+            ProfileBackupContents::decode(serde_json::to_vec(&json).unwrap()),
+            Err(CommonError::FailedToDeserializeJSONToValue { .. })
+        ));
+    }
+
+    #[test]
+    fn into_profile_encrypted_without_password() {
+        let sut = EncryptedProfileSnapshot::encrypting(
+            &Profile::placeholder(),
+            "babylon",
+        )
+        .unwrap();
+        assert_eq!(
+            ProfileBackupContents::Encrypted(sut).into_profile(None),
+            Err(CommonError::PasswordRequiredToDecryptEncryptedProfileSnapshot)
+        );
+    }
+
+    #[test]
+    fn into_profile_plaintext_ignores_password() {
+        let profile = Profile::placeholder();
+        assert_eq!(
+            ProfileBackupContents::Plaintext(profile.clone())
+                .into_profile(Some("babylon".to_owned())),
+            Ok(profile)
+        );
+    }
+}
@@ -0,0 +1,193 @@
+use crate::prelude::*;
+use aes_gcm::{
+    aead::{Aead, KeyInit, Payload},
+    Aes256Gcm, Nonce,
+};
+
+/// The authenticated symmetric cipher used to encrypt a Profile snapshot.
+#[derive(
+    Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash, Default,
+)]
+pub enum EncryptionScheme {
+    /// AES-256 in Galois/Counter Mode, the sealed bytes are formed as
+    /// `nonce (12 bytes) || ciphertext || tag (16 bytes)`.
+    #[default]
+    #[serde(rename = "AES-GCM-256")]
+    AesGcm256,
+}
+
+impl EncryptionScheme {
+    pub const AES_GCM_NONCE_BYTE_COUNT: usize = 12;
+
+    /// Encrypts `plaintext` using `key`, with a newly generated random nonce.
+    pub fn encrypt(
+        &self,
+        plaintext: impl AsRef<[u8]>,
+        key: &[u8; 32],
+    ) -> Result<Vec<u8>> {
+        self.encrypt_with_associated_data(plaintext, key, b"")
+    }
+
+    /// Encrypts `plaintext` using `key`, with a newly generated random nonce,
+    /// authenticating - but not encrypting - `associated_data`, which must
+    /// be passed to `decrypt_with_associated_data` as well.
+    pub fn encrypt_with_associated_data(
+        &self,
+        plaintext: impl AsRef<[u8]>,
+        key: &[u8; 32],
+        associated_data: impl AsRef<[u8]>,
+    ) -> Result<Vec<u8>> {
+        match self {
+            Self::AesGcm256 => {
+                let cipher = Aes256Gcm::new_from_slice(key)
+                    .map_err(|_| CommonError::FailedToEncryptProfileSnapshot)?;
+                let nonce = generate_bytes::<
+                    { EncryptionScheme::AES_GCM_NONCE_BYTE_COUNT },
+                >();
+                let payload = Payload {
+                    msg: plaintext.as_ref(),
+                    aad: associated_data.as_ref(),
+                };
+                let ciphertext = cipher
+                    .encrypt(Nonce::from_slice(&nonce), payload)
+                    .map_err(|_| CommonError::FailedToEncryptProfileSnapshot)?;
+                Ok([nonce, ciphertext].concat())
+            }
+        }
+    }
+
+    /// Decrypts and authenticates `sealed`, as produced by `encrypt`,
+    /// using `key`.
+    pub fn decrypt(
+        &self,
+        sealed: impl AsRef<[u8]>,
+        key: &[u8; 32],
+    ) -> Result<Vec<u8>> {
+        self.decrypt_with_associated_data(sealed, key, b"")
+    }
+
+    /// Decrypts and authenticates `sealed`, as produced by
+    /// `encrypt_with_associated_data` with the same `associated_data`,
+    /// using `key`.
+    pub fn decrypt_with_associated_data(
+        &self,
+        sealed: impl AsRef<[u8]>,
+        key: &[u8; 32],
+        associated_data: impl AsRef<[u8]>,
+    ) -> Result<Vec<u8>> {
+        match self {
+            Self::AesGcm256 => {
+                let sealed = sealed.as_ref();
+                if sealed.len() < Self::AES_GCM_NONCE_BYTE_COUNT {
+                    return Err(
+                        CommonError::FailedToDecryptEncryptedProfileSnapshot,
+                    );
+                }
+                let (nonce, ciphertext) =
+                    sealed.split_at(Self::AES_GCM_NONCE_BYTE_COUNT);
+                let cipher = Aes256Gcm::new_from_slice(key).map_err(|_| {
+                    CommonError::FailedToDecryptEncryptedProfileSnapshot
+                })?;
+                let payload = Payload {
+                    msg: ciphertext,
+                    aad: associated_data.as_ref(),
+                };
+                cipher.decrypt(Nonce::from_slice(nonce), payload).map_err(
+                    |_| CommonError::FailedToDecryptEncryptedProfileSnapshot,
+                )
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn roundtrip() {
+        let sut = EncryptionScheme::default();
+        let key = [0xab; 32];
+        let sealed = sut.encrypt("Hello Radix", &key).unwrap();
+        assert_eq!(sut.decrypt(sealed, &key).unwrap(), b"Hello Radix");
+    }
+
+    #[test]
+    fn nonce_is_random() {
+        let sut = EncryptionScheme::default();
+        let key = [0xab; 32];
+        assert_ne!(
+            sut.encrypt("Hello Radix", &key).unwrap(),
+            sut.encrypt("Hello Radix", &key).unwrap()
+        );
+    }
+
+    #[test]
+    fn wrong_key() {
+        let sut = EncryptionScheme::default();
+        let sealed = sut.encrypt("Hello Radix", &[0xab; 32]).unwrap();
+        assert_eq!(
+            sut.decrypt(sealed, &[0xcd; 32]),
+            Err(CommonError::FailedToDecryptEncryptedProfileSnapshot)
+        );
+    }
+
+    #[test]
+    fn tampered() {
+        let sut = EncryptionScheme::default();
+        let key = [0xab; 32];
+        let mut sealed = sut.encrypt("Hello Radix", &key).unwrap();
+        let last = sealed.len() - 1;
+        sealed[last] ^= 0x01;
+        assert_eq!(
+            sut.decrypt(sealed, &key),
+            Err(CommonError::FailedToDecryptEncryptedProfileSnapshot)
+        );
+    }
+
+    #[test]
+    fn associated_data_roundtrip() {
+        let sut = EncryptionScheme::default();
+        let key = [0xab; 32];
+        let sealed = sut
+            .encrypt_with_associated_data("Hello Radix", &key, "a")
+            .unwrap();
+        assert_eq!(
+            sut.decrypt_with_associated_data(sealed, &key, "a").unwrap(),
+            b"Hello Radix"
+        );
+    }
+
+    #[test]
+    fn wrong_associated_data() {
+        let sut = EncryptionScheme::default();
+        let key = [0xab; 32];
+        let sealed = sut
+            .encrypt_with_associated_data("Hello Radix", &key, "a")
+            .unwrap();
+        assert_eq!(
+            sut.decrypt_with_associated_data(sealed.clone(), &key, "b"),
+            Err(CommonError::FailedToDecryptEncryptedProfileSnapshot)
+        );
+        assert_eq!(
+            sut.decrypt(sealed, &key),
+            Err(CommonError::FailedToDecryptEncryptedProfileSnapshot)
+        );
+    }
+
+    #[test]
+    fn too_short() {
+        assert_eq!(
+            EncryptionScheme::default().decrypt([0xde, 0xad], &[0xab; 32]),
+            Err(CommonError::FailedToDecryptEncryptedProfileSnapshot)
+        );
+    }
+
+    #[test]
+    fn json_roundtrip() {
+        assert_json_value_eq_after_roundtrip(
+            &EncryptionScheme::AesGcm256,
+            json!("AES-GCM-256"),
+        );
+    }
+}
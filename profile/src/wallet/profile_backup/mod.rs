@@ -0,0 +1,7 @@
+mod encrypted_profile_snapshot;
+mod encryption_scheme;
+mod password_based_key_derivation_scheme;
+
+pub use encrypted_profile_snapshot::*;
+pub use encryption_scheme::*;
+pub use password_based_key_derivation_scheme::*;
@@ -0,0 +1,237 @@
+use crate::prelude::*;
+
+/// The scheme used to derive a symmetric encryption key from a user
+/// chosen password, stored in the `EncryptedProfileSnapshot` so that
+/// the key can be re-derived during import.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
+#[serde(tag = "algorithm")]
+pub enum PasswordBasedKeyDerivationScheme {
+    /// Memory-hard Argon2id (RFC 9106) key derivation.
+    #[serde(rename = "argon2id")]
+    Argon2id(Argon2idParameters),
+}
+
+/// Parameters of the Argon2id key derivation, the salt is randomly
+/// generated for every export.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct Argon2idParameters {
+    pub salt: BagOfBytes,
+
+    /// Memory cost in KiB.
+    #[serde(rename = "memoryCostKiB")]
+    pub memory_cost_kib: u32,
+
+    pub iterations: u32,
+
+    pub parallelism: u32,
+}
+
+impl Argon2idParameters {
+    pub const SALT_BYTE_COUNT: usize = 16;
+
+    /// The highest memory cost accepted when deriving a key, since the
+    /// parameters are read from untrusted encrypted backups, which could
+    /// otherwise make the wallet allocate gigabytes of memory.
+    pub const MAX_MEMORY_COST_KIB: u32 = 4 * argon2::Params::DEFAULT_M_COST;
+
+    /// The highest number of iterations accepted when deriving a key.
+    pub const MAX_ITERATIONS: u32 = 4 * argon2::Params::DEFAULT_T_COST;
+
+    /// The highest degree of parallelism accepted when deriving a key.
+    pub const MAX_PARALLELISM: u32 = 4 * argon2::Params::DEFAULT_P_COST;
+
+    /// Recommended (OWASP) Argon2id parameters with a newly generated
+    /// random salt.
+    pub fn new_with_random_salt() -> Self {
+        Self {
+            salt: generate_bytes::<{ Argon2idParameters::SALT_BYTE_COUNT }>()
+                .into(),
+            memory_cost_kib: argon2::Params::DEFAULT_M_COST,
+            iterations: argon2::Params::DEFAULT_T_COST,
+            parallelism: argon2::Params::DEFAULT_P_COST,
+        }
+    }
+
+    /// Returns `Err` if any parameter exceeds its limit, e.g.
+    /// `MAX_MEMORY_COST_KIB`.
+    pub fn ensure_within_limits(&self) -> Result<()> {
+        if self.memory_cost_kib > Self::MAX_MEMORY_COST_KIB
+            || self.iterations > Self::MAX_ITERATIONS
+            || self.parallelism > Self::MAX_PARALLELISM
+        {
+            return Err(
+                CommonError::PasswordBasedKeyDerivationParametersExceedLimits {
+                    memory_cost_kib: self.memory_cost_kib,
+                    iterations: self.iterations,
+                    parallelism: self.parallelism,
+                },
+            );
+        }
+        Ok(())
+    }
+}
+
+impl Default for PasswordBasedKeyDerivationScheme {
+    fn default() -> Self {
+        Self::Argon2id(Argon2idParameters::new_with_random_salt())
+    }
+}
+
+impl PasswordBasedKeyDerivationScheme {
+    /// Derives a 32 bytes symmetric key from `password`.
+    pub fn derive_key(&self, password: &str) -> Result<[u8; 32]> {
+        match self {
+            Self::Argon2id(parameters) => {
+                parameters.ensure_within_limits()?;
+                let params = argon2::Params::new(
+                    parameters.memory_cost_kib,
+                    parameters.iterations,
+                    parameters.parallelism,
+                    Some(32),
+                )
+                .map_err(|_| {
+                    CommonError::FailedToDeriveEncryptionKeyFromPassword
+                })?;
+                let argon2 = argon2::Argon2::new(
+                    argon2::Algorithm::Argon2id,
+                    argon2::Version::V0x13,
+                    params,
+                );
+                let mut key = [0u8; 32];
+                argon2
+                    .hash_password_into(
+                        password.as_bytes(),
+                        parameters.salt.as_slice(),
+                        &mut key,
+                    )
+                    .map_err(|_| {
+                        CommonError::FailedToDeriveEncryptionKeyFromPassword
+                    })?;
+                Ok(key)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    fn fixed_salt() -> PasswordBasedKeyDerivationScheme {
+        PasswordBasedKeyDerivationScheme::Argon2id(Argon2idParameters {
+            salt: BagOfBytes::placeholder_dead(),
+            memory_cost_kib: 19456,
+            iterations: 2,
+            parallelism: 1,
+        })
+    }
+
+    #[test]
+    fn derive_key_is_deterministic() {
+        let sut = fixed_salt();
+        assert_eq!(
+            sut.derive_key("babylon").unwrap(),
+            sut.derive_key("babylon").unwrap()
+        );
+    }
+
+    #[test]
+    fn derive_key_differs_for_different_passwords() {
+        let sut = fixed_salt();
+        assert_ne!(
+            sut.derive_key("babylon").unwrap(),
+            sut.derive_key("olympia").unwrap()
+        );
+    }
+
+    #[test]
+    fn derive_key_differs_for_different_salts() {
+        assert_ne!(
+            PasswordBasedKeyDerivationScheme::default()
+                .derive_key("babylon")
+                .unwrap(),
+            PasswordBasedKeyDerivationScheme::default()
+                .derive_key("babylon")
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn invalid_parameters() {
+        let sut =
+            PasswordBasedKeyDerivationScheme::Argon2id(Argon2idParameters {
+                salt: BagOfBytes::placeholder_dead(),
+                memory_cost_kib: 0,
+                iterations: 0,
+                parallelism: 0,
+            });
+        assert_eq!(
+            sut.derive_key("babylon"),
+            Err(CommonError::FailedToDeriveEncryptionKeyFromPassword)
+        );
+    }
+
+    #[test]
+    fn default_parameters_are_within_limits() {
+        assert_eq!(
+            Argon2idParameters::new_with_random_salt().ensure_within_limits(),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn parameters_exceeding_limits() {
+        let within = Argon2idParameters {
+            salt: BagOfBytes::placeholder_dead(),
+            memory_cost_kib: Argon2idParameters::MAX_MEMORY_COST_KIB,
+            iterations: Argon2idParameters::MAX_ITERATIONS,
+            parallelism: Argon2idParameters::MAX_PARALLELISM,
+        };
+        assert_eq!(within.ensure_within_limits(), Ok(()));
+
+        let exceeding = [
+            Argon2idParameters {
+                memory_cost_kib: 1 << 30, // 1 TiB
+                ..within.clone()
+            },
+            Argon2idParameters {
+                iterations: u32::MAX,
+                ..within.clone()
+            },
+            Argon2idParameters {
+                parallelism: Argon2idParameters::MAX_PARALLELISM + 1,
+                ..within.clone()
+            },
+        ];
+        for parameters in exceeding {
+            let expected_error =
+                CommonError::PasswordBasedKeyDerivationParametersExceedLimits {
+                    memory_cost_kib: parameters.memory_cost_kib,
+                    iterations: parameters.iterations,
+                    parallelism: parameters.parallelism,
+                };
+            assert_eq!(
+                PasswordBasedKeyDerivationScheme::Argon2id(parameters)
+                    .derive_key("babylon"),
+                Err(expected_error)
+            );
+        }
+    }
+
+    #[test]
+    fn json_roundtrip() {
+        assert_eq_after_json_roundtrip(
+            &fixed_salt(),
+            r#"
+            {
+                "algorithm": "argon2id",
+                "salt": "deadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeef",
+                "memoryCostKiB": 19456,
+                "iterations": 2,
+                "parallelism": 1
+            }
+            "#,
+        );
+    }
+}
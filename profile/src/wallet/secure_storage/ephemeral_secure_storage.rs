@@ -19,7 +19,7 @@ impl EphemeralSecureStorage {
 impl SecureStorage for EphemeralSecureStorage {
     fn load_data(&self, key: SecureStorageKey) -> Result<Option<Vec<u8>>> {
         self.storage
-            .try_read()
+            .read()
             .map_err(|_| CommonError::SecureStorageReadError)
             .map(|s| s.get(&key).cloned())
     }
@@ -27,7 +27,7 @@ impl SecureStorage for EphemeralSecureStorage {
     fn save_data(&self, key: SecureStorageKey, value: Vec<u8>) -> Result<()> {
         let mut storage = self
             .storage
-            .try_write()
+            .write()
             .map_err(|_| CommonError::SecureStorageWriteError)?;
 
         storage.insert(key, value);
@@ -37,7 +37,7 @@ impl SecureStorage for EphemeralSecureStorage {
     fn delete_data_for_key(&self, key: SecureStorageKey) -> Result<()> {
         let mut storage = self
             .storage
-            .try_write()
+            .write()
             .map_err(|_| CommonError::SecureStorageWriteError)?;
 
         storage.remove_entry(&key);
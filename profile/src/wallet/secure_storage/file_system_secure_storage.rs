@@ -0,0 +1,473 @@
+use crate::prelude::*;
+
+use std::{
+    fs, io,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+/// A `SecureStorage` which persists the data of each `SecureStorageKey` in a
+/// file, named after the hex encoding of `SecureStorageKey::identifier`,
+/// inside of `directory`.
+///
+/// Intended for hosts without a keychain, e.g. CLI tools and CI bots running
+/// on Linux. Files are written atomically - to a temporary file which is then
+/// renamed - and on Unix the directory and files are only accessible by the
+/// owner. Optionally the data is encrypted at rest using a host supplied
+/// encryption key, bound to the identifier of its key, so that the file of
+/// one key cannot be passed off as the data of another.
+pub struct FileSystemSecureStorage {
+    /// The directory in which all files are stored.
+    directory: PathBuf,
+
+    /// If present, all data is encrypted with this key before it is written
+    /// and decrypted after it has been read.
+    encryption_key: Option<Hex32Bytes>,
+}
+
+impl std::fmt::Debug for FileSystemSecureStorage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Never print the encryption key.
+        f.debug_struct("FileSystemSecureStorage")
+            .field("directory", &self.directory)
+            .field("is_encrypted", &self.encryption_key.is_some())
+            .finish()
+    }
+}
+
+impl FileSystemSecureStorage {
+    /// Creates a new `FileSystemSecureStorage` storing plaintext files in
+    /// `directory`, which is created if it does not exist.
+    pub fn new(directory: impl AsRef<Path>) -> Result<Self> {
+        Self::with(directory, None)
+    }
+
+    /// Creates a new `FileSystemSecureStorage` storing files in `directory`,
+    /// which is created if it does not exist, encrypted with `encryption_key`
+    /// using `EncryptionScheme::AesGcm256`.
+    pub fn with_encryption_key(
+        directory: impl AsRef<Path>,
+        encryption_key: Hex32Bytes,
+    ) -> Result<Self> {
+        Self::with(directory, Some(encryption_key))
+    }
+
+    fn with(
+        directory: impl AsRef<Path>,
+        encryption_key: Option<Hex32Bytes>,
+    ) -> Result<Self> {
+        let directory = directory.as_ref().to_path_buf();
+        Self::create_directory(&directory).map_err(|e| {
+            error!(
+                "Failed to create SecureStorage directory: {:?}, error: {}",
+                directory, e
+            );
+            CommonError::FailedToCreateSecureStorageDirectory(
+                directory.display().to_string(),
+            )
+        })?;
+        Ok(Self {
+            directory,
+            encryption_key,
+        })
+    }
+
+    /// The path of the file in which the data of `key` is stored, see
+    /// `file_name_for_identifier`.
+    pub fn path_for_key(&self, key: &SecureStorageKey) -> PathBuf {
+        self.directory
+            .join(Self::file_name_for_identifier(&key.identifier()))
+    }
+
+    /// The hex encoding of the bytes of `identifier`, which is a valid file
+    /// name on all platforms - unlike e.g. `:` on Windows - and unique per
+    /// identifier, since the encoding is reversible.
+    fn file_name_for_identifier(identifier: &str) -> String {
+        hex_encode(identifier.as_bytes())
+    }
+}
+
+impl FileSystemSecureStorage {
+    #[cfg(unix)]
+    fn create_directory(directory: &Path) -> io::Result<()> {
+        use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+        fs::DirBuilder::new()
+            .recursive(true)
+            .mode(0o700)
+            .create(directory)?;
+        fs::set_permissions(directory, fs::Permissions::from_mode(0o700))
+    }
+
+    #[cfg(not(unix))]
+    fn create_directory(directory: &Path) -> io::Result<()> {
+        fs::create_dir_all(directory)
+    }
+
+    #[cfg(unix)]
+    fn create_file(path: &Path) -> io::Result<fs::File> {
+        use std::os::unix::fs::OpenOptionsExt;
+        fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .mode(0o600)
+            .open(path)
+    }
+
+    #[cfg(not(unix))]
+    fn create_file(path: &Path) -> io::Result<fs::File> {
+        fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(path)
+    }
+
+    /// Flushes the entries of `directory`, so that a file renamed into it
+    /// is still present after a crash.
+    #[cfg(unix)]
+    fn sync_directory(directory: &Path) -> io::Result<()> {
+        fs::File::open(directory)?.sync_all()
+    }
+
+    /// Directories cannot be opened as files on Windows, where a rename is
+    /// durable once it has returned.
+    #[cfg(not(unix))]
+    fn sync_directory(_directory: &Path) -> io::Result<()> {
+        Ok(())
+    }
+
+    /// Writes `data` to a new temporary file in the same directory as `path`
+    /// which is then renamed to `path`, so that `path` either contains the
+    /// old data or all of the new data, never partially written data.
+    fn write_atomically(path: &Path, data: &[u8]) -> io::Result<()> {
+        let file_name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let temp_path =
+            path.with_file_name(format!(".{}.{}.tmp", file_name, id()));
+        let result = Self::create_file(&temp_path)
+            .and_then(|mut file| {
+                file.write_all(data)?;
+                file.sync_all()
+            })
+            .and_then(|_| fs::rename(&temp_path, path));
+        if result.is_err() {
+            _ = fs::remove_file(&temp_path);
+            return result;
+        }
+        match path.parent() {
+            Some(directory) => Self::sync_directory(directory),
+            None => Ok(()),
+        }
+    }
+
+    fn encrypt(
+        &self,
+        key: &SecureStorageKey,
+        data: Vec<u8>,
+    ) -> Result<Vec<u8>> {
+        match &self.encryption_key {
+            None => Ok(data),
+            Some(encryption_key) => EncryptionScheme::default()
+                .encrypt_with_associated_data(
+                    data,
+                    &encryption_key.bytes(),
+                    key.identifier(),
+                )
+                .map_err(|_| CommonError::SecureStorageWriteError),
+        }
+    }
+
+    fn decrypt(
+        &self,
+        key: &SecureStorageKey,
+        data: Vec<u8>,
+    ) -> Result<Vec<u8>> {
+        match &self.encryption_key {
+            None => Ok(data),
+            Some(encryption_key) => EncryptionScheme::default()
+                .decrypt_with_associated_data(
+                    data,
+                    &encryption_key.bytes(),
+                    key.identifier(),
+                )
+                .map_err(|_| CommonError::FailedToDecryptSecureStorageData),
+        }
+    }
+}
+
+impl SecureStorage for FileSystemSecureStorage {
+    fn load_data(&self, key: SecureStorageKey) -> Result<Option<Vec<u8>>> {
+        match fs::read(self.path_for_key(&key)) {
+            Ok(data) => self.decrypt(&key, data).map(Some),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => {
+                error!("Failed to read data for key: {:?}, error: {}", key, e);
+                Err(CommonError::SecureStorageReadError)
+            }
+        }
+    }
+
+    fn save_data(&self, key: SecureStorageKey, data: Vec<u8>) -> Result<()> {
+        let data = self.encrypt(&key, data)?;
+        Self::write_atomically(&self.path_for_key(&key), &data).map_err(|e| {
+            error!("Failed to write data for key: {:?}, error: {}", key, e);
+            CommonError::SecureStorageWriteError
+        })
+    }
+
+    fn delete_data_for_key(&self, key: SecureStorageKey) -> Result<()> {
+        match fs::remove_file(self.path_for_key(&key)) {
+            Ok(_) => Ok(()),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+            Err(e) => {
+                error!(
+                    "Failed to delete data for key: {:?}, error: {}",
+                    key, e
+                );
+                Err(CommonError::SecureStorageWriteError)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use std::{fs, path::PathBuf};
+
+    /// A unique directory in the temp dir of the OS, removed when dropped.
+    struct TempDir(PathBuf);
+    impl TempDir {
+        fn new() -> Self {
+            Self(std::env::temp_dir().join(format!("sargon_test_{}", id())))
+        }
+    }
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn key() -> SecureStorageKey {
+        SecureStorageKey::ProfileSnapshot {
+            profile_id: ProfileID::placeholder(),
+        }
+    }
+
+    #[test]
+    fn load_missing_is_none() {
+        let dir = TempDir::new();
+        let sut = FileSystemSecureStorage::new(&dir.0).unwrap();
+        assert_eq!(sut.load_data(key()), Ok(None));
+    }
+
+    #[test]
+    fn save_then_load() {
+        let dir = TempDir::new();
+        let sut = FileSystemSecureStorage::new(&dir.0).unwrap();
+        sut.save_data(key(), vec![0xde, 0xad]).unwrap();
+        assert_eq!(sut.load_data(key()), Ok(Some(vec![0xde, 0xad])));
+    }
+
+    #[test]
+    fn save_replaces() {
+        let dir = TempDir::new();
+        let sut = FileSystemSecureStorage::new(&dir.0).unwrap();
+        sut.save_data(key(), vec![0xde, 0xad]).unwrap();
+        sut.save_data(key(), vec![0xbe, 0xef]).unwrap();
+        assert_eq!(sut.load_data(key()), Ok(Some(vec![0xbe, 0xef])));
+    }
+
+    #[test]
+    fn delete() {
+        let dir = TempDir::new();
+        let sut = FileSystemSecureStorage::new(&dir.0).unwrap();
+        sut.save_data(key(), vec![0xde, 0xad]).unwrap();
+        sut.delete_data_for_key(key()).unwrap();
+        assert_eq!(sut.load_data(key()), Ok(None));
+        assert_eq!(sut.delete_data_for_key(key()), Ok(()));
+    }
+
+    #[test]
+    fn file_is_named_after_key_identifier_and_no_temp_files_remain() {
+        let dir = TempDir::new();
+        let sut = FileSystemSecureStorage::new(&dir.0).unwrap();
+        sut.save_data(key(), vec![0xde, 0xad]).unwrap();
+        sut.save_data(key(), vec![0xbe, 0xef]).unwrap();
+        let file_names = fs::read_dir(&dir.0)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(file_names, vec![hex_encode(key().identifier())]);
+        assert_eq!(fs::read(sut.path_for_key(&key())).unwrap(), [0xbe, 0xef]);
+    }
+
+    #[test]
+    fn file_names_are_sanitised() {
+        let dir = TempDir::new();
+        let sut = FileSystemSecureStorage::new(&dir.0).unwrap();
+        let mnemonic_key = SecureStorageKey::DeviceFactorSourceMnemonic {
+            factor_source_id: FactorSourceIDFromHash::placeholder(),
+        };
+        assert!(mnemonic_key.identifier().contains(':'));
+        let file_name = sut
+            .path_for_key(&mnemonic_key)
+            .file_name()
+            .unwrap()
+            .to_string_lossy()
+            .to_string();
+        assert!(file_name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-'));
+        sut.save_data(mnemonic_key.clone(), vec![0xde, 0xad])
+            .unwrap();
+        assert_eq!(sut.load_data(mnemonic_key), Ok(Some(vec![0xde, 0xad])));
+    }
+
+    #[test]
+    fn file_names_do_not_collide() {
+        assert_ne!(
+            FileSystemSecureStorage::file_name_for_identifier("a:b"),
+            FileSystemSecureStorage::file_name_for_identifier("a.b")
+        );
+        assert_ne!(
+            FileSystemSecureStorage::file_name_for_identifier("a:b"),
+            FileSystemSecureStorage::file_name_for_identifier("a_b")
+        );
+
+        let dir = TempDir::new();
+        let sut = FileSystemSecureStorage::new(&dir.0).unwrap();
+        let keys = [
+            SecureStorageKey::SnapshotHeadersList,
+            SecureStorageKey::SnapshotHeadersListIsComplete,
+            SecureStorageKey::ActiveProfileID,
+            SecureStorageKey::DeviceInfo,
+            SecureStorageKey::DeviceFactorSourceMnemonic {
+                factor_source_id: FactorSourceIDFromHash::placeholder_device(),
+            },
+            SecureStorageKey::DeviceFactorSourceMnemonic {
+                factor_source_id: FactorSourceIDFromHash::placeholder_ledger(),
+            },
+            key(),
+        ];
+        for (i, key) in keys.iter().enumerate() {
+            sut.save_data(key.clone(), vec![i as u8]).unwrap();
+        }
+        for (i, key) in keys.iter().enumerate() {
+            assert_eq!(sut.load_data(key.clone()), Ok(Some(vec![i as u8])));
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn permissions_are_restrictive() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = TempDir::new();
+        let sut = FileSystemSecureStorage::new(&dir.0).unwrap();
+        sut.save_data(key(), vec![0xde, 0xad]).unwrap();
+        let mode =
+            |p: &PathBuf| fs::metadata(p).unwrap().permissions().mode() & 0o777;
+        assert_eq!(mode(&dir.0), 0o700);
+        assert_eq!(mode(&sut.path_for_key(&key())), 0o600);
+    }
+
+    #[test]
+    fn encrypted_at_rest() {
+        let dir = TempDir::new();
+        let sut = FileSystemSecureStorage::with_encryption_key(
+            &dir.0,
+            Hex32Bytes::placeholder(),
+        )
+        .unwrap();
+        let data = b"Hello Radix".to_vec();
+        sut.save_data(key(), data.clone()).unwrap();
+        assert_eq!(sut.load_data(key()), Ok(Some(data.clone())));
+        let on_disk = fs::read(sut.path_for_key(&key())).unwrap();
+        assert!(!on_disk.windows(data.len()).any(|w| w == data.as_slice()));
+    }
+
+    #[test]
+    fn encrypted_wrong_key() {
+        let dir = TempDir::new();
+        FileSystemSecureStorage::with_encryption_key(
+            &dir.0,
+            Hex32Bytes::placeholder(),
+        )
+        .unwrap()
+        .save_data(key(), vec![0xde, 0xad])
+        .unwrap();
+        let sut = FileSystemSecureStorage::with_encryption_key(
+            &dir.0,
+            Hex32Bytes::placeholder_other(),
+        )
+        .unwrap();
+        assert_eq!(
+            sut.load_data(key()),
+            Err(CommonError::FailedToDecryptSecureStorageData)
+        );
+    }
+
+    #[test]
+    fn encrypted_data_is_bound_to_its_key() {
+        let dir = TempDir::new();
+        let sut = FileSystemSecureStorage::with_encryption_key(
+            &dir.0,
+            Hex32Bytes::placeholder(),
+        )
+        .unwrap();
+        let other_key = SecureStorageKey::ProfileSnapshot {
+            profile_id: ProfileID::placeholder_other(),
+        };
+        sut.save_data(key(), vec![0xde, 0xad]).unwrap();
+        fs::copy(sut.path_for_key(&key()), sut.path_for_key(&other_key))
+            .unwrap();
+        assert_eq!(
+            sut.load_data(other_key),
+            Err(CommonError::FailedToDecryptSecureStorageData)
+        );
+    }
+
+    #[test]
+    fn debug_does_not_contain_encryption_key() {
+        let dir = TempDir::new();
+        let sut = FileSystemSecureStorage::with_encryption_key(
+            &dir.0,
+            Hex32Bytes::placeholder(),
+        )
+        .unwrap();
+        assert!(!format!("{:?}", sut)
+            .contains(&Hex32Bytes::placeholder().to_string()));
+    }
+
+    #[test]
+    fn fails_if_directory_is_a_file() {
+        let dir = TempDir::new();
+        fs::write(&dir.0, [0xde, 0xad]).unwrap();
+        assert_eq!(
+            FileSystemSecureStorage::new(&dir.0).map(|_| ()),
+            Err(CommonError::FailedToCreateSecureStorageDirectory(
+                dir.0.display().to_string()
+            ))
+        );
+        _ = fs::remove_file(&dir.0);
+    }
+
+    #[test]
+    fn wallet_profile_persisted_across_instances() {
+        let dir = TempDir::new();
+        let storage = || {
+            Arc::new(
+                FileSystemSecureStorage::with_encryption_key(
+                    &dir.0,
+                    Hex32Bytes::placeholder(),
+                )
+                .unwrap(),
+            )
+        };
+        let profile = Profile::placeholder();
+        _ = Wallet::by_importing_profile(profile.clone(), storage());
+        let wallet = Wallet::by_loading_profile(storage()).unwrap();
+        assert_eq!(wallet.profile(), profile);
+    }
+}
@@ -0,0 +1,377 @@
+use crate::prelude::*;
+
+use std::sync::{
+    atomic::{AtomicU64, Ordering as AtomicOrdering},
+    RwLock,
+};
+
+/// A fault which an `InMemorySecureStorage` can be scripted to inject.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, uniffi::Enum)]
+pub enum SecureStorageFault {
+    /// The `n`th call to `save_data` - counting all calls from `1`, including
+    /// those made before this fault was added - fails with
+    /// `SecureStorageWriteError`.
+    FailNthSave { n: u64 },
+
+    /// All calls for keys of `kind` fail, `load_data` with
+    /// `SecureStorageReadError`, `save_data` and `delete_data_for_key` with
+    /// `SecureStorageWriteError`.
+    FailKeyKind { kind: SecureStorageKeyKind },
+
+    /// `load_data` for keys of `kind` returns `CORRUPTED_BYTES`, instead of
+    /// the saved bytes, if any.
+    CorruptLoadsOfKeyKind { kind: SecureStorageKeyKind },
+
+    /// `load_data` for keys of `kind` returns `Ok(None)`, even if bytes have
+    /// been saved.
+    MissingLoadsOfKeyKind { kind: SecureStorageKeyKind },
+}
+
+/// A call made to an `InMemorySecureStorage`, recorded regardless of whether
+/// it succeeded or not.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, uniffi::Enum)]
+pub enum SecureStorageCall {
+    Load {
+        key: SecureStorageKey,
+    },
+    Save {
+        key: SecureStorageKey,
+        data: Vec<u8>,
+    },
+    Delete {
+        key: SecureStorageKey,
+    },
+}
+
+/// A `SecureStorage` which saves into memory, intended as a test double both
+/// for Rust crates and for Wallet Clients, since it can be scripted to inject
+/// faults, see `SecureStorageFault`, and records every call it receives.
+#[derive(Debug, Default, uniffi::Object)]
+pub struct InMemorySecureStorage {
+    storage: RwLock<HashMap<SecureStorageKey, Vec<u8>>>,
+    faults: RwLock<Vec<SecureStorageFault>>,
+    calls: RwLock<Vec<SecureStorageCall>>,
+    number_of_saves: AtomicU64,
+}
+
+impl InMemorySecureStorage {
+    /// The bytes returned by `load_data` for keys with a
+    /// `SecureStorageFault::CorruptLoadsOfKeyKind` fault, not valid JSON.
+    pub const CORRUPTED_BYTES: [u8; 4] = [0xde, 0xad, 0xbe, 0xef];
+}
+
+#[uniffi::export]
+impl InMemorySecureStorage {
+    /// Creates a new empty `InMemorySecureStorage` without any faults.
+    #[uniffi::constructor]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns this storage as a `SecureStorage`, e.g. to be passed to the
+    /// constructors of `Wallet`.
+    pub fn as_secure_storage(self: Arc<Self>) -> Arc<dyn SecureStorage> {
+        self
+    }
+
+    /// Adds `fault` which will be injected from now on.
+    pub fn add_fault(&self, fault: SecureStorageFault) {
+        self.faults
+            .write()
+            .expect("Should be able to add fault.")
+            .push(fault);
+    }
+
+    /// Removes all faults, calls made after this will succeed.
+    pub fn remove_all_faults(&self) {
+        self.faults
+            .write()
+            .expect("Should be able to remove faults.")
+            .clear();
+    }
+
+    /// Every call received, in the order they were received.
+    pub fn calls(&self) -> Vec<SecureStorageCall> {
+        self.calls
+            .read()
+            .expect("Should be able to read calls.")
+            .clone()
+    }
+
+    /// Forgets all recorded calls.
+    pub fn clear_calls(&self) {
+        self.calls
+            .write()
+            .expect("Should be able to clear calls.")
+            .clear();
+    }
+
+    /// Returns the saved bytes for `key`, without injecting any faults and
+    /// without recording a call.
+    pub fn saved_data(&self, key: SecureStorageKey) -> Option<Vec<u8>> {
+        self.storage
+            .read()
+            .expect("Should be able to read storage.")
+            .get(&key)
+            .cloned()
+    }
+}
+
+impl InMemorySecureStorage {
+    fn record(&self, call: SecureStorageCall) {
+        self.calls
+            .write()
+            .expect("Should be able to record call.")
+            .push(call);
+    }
+
+    fn has_fault(
+        &self,
+        predicate: impl Fn(&SecureStorageFault) -> bool,
+    ) -> bool {
+        self.faults
+            .read()
+            .expect("Should be able to read faults.")
+            .iter()
+            .any(predicate)
+    }
+
+    fn fails(&self, key: &SecureStorageKey) -> bool {
+        self.has_fault(|f| {
+            *f == SecureStorageFault::FailKeyKind { kind: key.kind() }
+        })
+    }
+}
+
+impl SecureStorage for InMemorySecureStorage {
+    fn load_data(&self, key: SecureStorageKey) -> Result<Option<Vec<u8>>> {
+        self.record(SecureStorageCall::Load { key: key.clone() });
+        let kind = key.kind();
+        if self.fails(&key) {
+            return Err(CommonError::SecureStorageReadError);
+        }
+        if self.has_fault(|f| {
+            *f == SecureStorageFault::MissingLoadsOfKeyKind { kind }
+        }) {
+            return Ok(None);
+        }
+        if self.has_fault(|f| {
+            *f == SecureStorageFault::CorruptLoadsOfKeyKind { kind }
+        }) {
+            return Ok(Some(Self::CORRUPTED_BYTES.to_vec()));
+        }
+        Ok(self.saved_data(key))
+    }
+
+    fn save_data(&self, key: SecureStorageKey, data: Vec<u8>) -> Result<()> {
+        self.record(SecureStorageCall::Save {
+            key: key.clone(),
+            data: data.clone(),
+        });
+        let n = self.number_of_saves.fetch_add(1, AtomicOrdering::SeqCst) + 1;
+        if self.fails(&key)
+            || self.has_fault(|f| *f == SecureStorageFault::FailNthSave { n })
+        {
+            return Err(CommonError::SecureStorageWriteError);
+        }
+        self.storage
+            .write()
+            .map_err(|_| CommonError::SecureStorageWriteError)?
+            .insert(key, data);
+        Ok(())
+    }
+
+    fn delete_data_for_key(&self, key: SecureStorageKey) -> Result<()> {
+        self.record(SecureStorageCall::Delete { key: key.clone() });
+        if self.fails(&key) {
+            return Err(CommonError::SecureStorageWriteError);
+        }
+        self.storage
+            .write()
+            .map_err(|_| CommonError::SecureStorageWriteError)?
+            .remove(&key);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    fn key() -> SecureStorageKey {
+        SecureStorageKey::ProfileSnapshot {
+            profile_id: ProfileID::placeholder(),
+        }
+    }
+
+    #[test]
+    fn save_load_delete() {
+        let sut = InMemorySecureStorage::new();
+        assert_eq!(sut.load_data(key()), Ok(None));
+        sut.save_data(key(), vec![0xab]).unwrap();
+        assert_eq!(sut.load_data(key()), Ok(Some(vec![0xab])));
+        sut.delete_data_for_key(key()).unwrap();
+        assert_eq!(sut.load_data(key()), Ok(None));
+    }
+
+    #[test]
+    fn records_calls() {
+        let sut = InMemorySecureStorage::new();
+        sut.add_fault(SecureStorageFault::FailKeyKind {
+            kind: SecureStorageKeyKind::ProfileSnapshot,
+        });
+        _ = sut.save_data(key(), vec![0xab]);
+        _ = sut.load_data(key());
+        _ = sut.delete_data_for_key(key());
+        assert_eq!(
+            sut.calls(),
+            vec![
+                SecureStorageCall::Save {
+                    key: key(),
+                    data: vec![0xab]
+                },
+                SecureStorageCall::Load { key: key() },
+                SecureStorageCall::Delete { key: key() },
+            ]
+        );
+        sut.clear_calls();
+        assert!(sut.calls().is_empty());
+    }
+
+    #[test]
+    fn fail_nth_save() {
+        let sut = InMemorySecureStorage::new();
+        sut.add_fault(SecureStorageFault::FailNthSave { n: 2 });
+        assert_eq!(sut.save_data(key(), vec![0x01]), Ok(()));
+        assert_eq!(
+            sut.save_data(key(), vec![0x02]),
+            Err(CommonError::SecureStorageWriteError)
+        );
+        assert_eq!(sut.save_data(key(), vec![0x03]), Ok(()));
+        assert_eq!(sut.saved_data(key()), Some(vec![0x03]));
+    }
+
+    #[test]
+    fn fail_key_kind() {
+        let sut = InMemorySecureStorage::new();
+        sut.save_data(key(), vec![0xab]).unwrap();
+        sut.add_fault(SecureStorageFault::FailKeyKind {
+            kind: SecureStorageKeyKind::ProfileSnapshot,
+        });
+        assert_eq!(
+            sut.load_data(key()),
+            Err(CommonError::SecureStorageReadError)
+        );
+        assert_eq!(
+            sut.save_data(key(), vec![0xcd]),
+            Err(CommonError::SecureStorageWriteError)
+        );
+        assert_eq!(
+            sut.delete_data_for_key(key()),
+            Err(CommonError::SecureStorageWriteError)
+        );
+        assert_eq!(
+            sut.save_data(SecureStorageKey::ActiveProfileID, vec![0xcd]),
+            Ok(())
+        );
+        sut.remove_all_faults();
+        assert_eq!(sut.load_data(key()), Ok(Some(vec![0xab])));
+    }
+
+    #[test]
+    fn corrupt_and_missing_loads() {
+        let sut = InMemorySecureStorage::new();
+        sut.save_data(key(), vec![0xab]).unwrap();
+        sut.add_fault(SecureStorageFault::CorruptLoadsOfKeyKind {
+            kind: SecureStorageKeyKind::ProfileSnapshot,
+        });
+        assert_eq!(
+            sut.load_data(key()),
+            Ok(Some(InMemorySecureStorage::CORRUPTED_BYTES.to_vec()))
+        );
+        sut.add_fault(SecureStorageFault::MissingLoadsOfKeyKind {
+            kind: SecureStorageKeyKind::ProfileSnapshot,
+        });
+        assert_eq!(sut.load_data(key()), Ok(None));
+    }
+
+    #[test]
+    fn wallet_unable_to_save_mnemonic() {
+        let storage = Arc::new(InMemorySecureStorage::new());
+        let wallet = Wallet::by_importing_profile(
+            Profile::placeholder(),
+            storage.clone().as_secure_storage(),
+        );
+        storage.add_fault(SecureStorageFault::FailKeyKind {
+            kind: SecureStorageKeyKind::DeviceFactorSourceMnemonic,
+        });
+        let new = PrivateHierarchicalDeterministicFactorSource::generate_new(
+            WalletClientModel::Unknown,
+        );
+        assert_eq!(
+            wallet.add_private_device_factor_source(new.clone()),
+            Err(CommonError::UnableToSaveMnemonicToSecureStorage(
+                new.factor_source.id
+            ))
+        );
+    }
+
+    #[test]
+    fn wallet_unable_to_save_headers_list_keeps_memory_and_storage_in_sync() {
+        let storage = Arc::new(InMemorySecureStorage::new());
+        let wallet = Wallet::by_importing_profile(
+            Profile::placeholder(),
+            storage.clone().as_secure_storage(),
+        );
+        storage.add_fault(SecureStorageFault::FailKeyKind {
+            kind: SecureStorageKeyKind::SnapshotHeadersList,
+        });
+
+        assert_eq!(
+            wallet.add_account(Account::placeholder_mainnet_carol()),
+            Ok(())
+        );
+
+        let saved: Profile = serde_json::from_slice(
+            &storage
+                .saved_data(SecureStorageKey::ProfileSnapshot {
+                    profile_id: wallet.profile().id(),
+                })
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(saved, wallet.profile());
+        assert_eq!(
+            storage.saved_data(SecureStorageKey::SnapshotHeadersListIsComplete),
+            None
+        );
+    }
+
+    #[test]
+    fn wallet_profile_snapshot_not_found() {
+        let storage = Arc::new(InMemorySecureStorage::new());
+        let profile = Profile::placeholder();
+        _ = Wallet::by_importing_profile(profile.clone(), storage.clone());
+        storage.add_fault(SecureStorageFault::MissingLoadsOfKeyKind {
+            kind: SecureStorageKeyKind::ProfileSnapshot,
+        });
+        assert_eq!(
+            Wallet::by_loading_profile(storage).map(|w| w.profile()),
+            Err(CommonError::ProfileSnapshotNotFound(profile.id()))
+        );
+    }
+
+    #[test]
+    fn wallet_corrupt_profile_snapshot() {
+        let storage = Arc::new(InMemorySecureStorage::new());
+        let profile = Profile::placeholder();
+        _ = Wallet::by_importing_profile(profile.clone(), storage.clone());
+        storage.add_fault(SecureStorageFault::CorruptLoadsOfKeyKind {
+            kind: SecureStorageKeyKind::ProfileSnapshot,
+        });
+        assert!(matches!(
+            Wallet::by_loading_profile(storage).map(|w| w.profile()),
+            Err(CommonError::FailedToDeserializeJSONToValue { .. })
+        ));
+    }
+}
@@ -1,7 +1,11 @@
+mod file_system_secure_storage;
+mod in_memory_secure_storage;
 mod secure_storage;
 mod secure_storage_key;
 mod wallet_client_storage;
 
+pub use file_system_secure_storage::*;
+pub use in_memory_secure_storage::*;
 pub use secure_storage::*;
 pub use secure_storage_key::*;
 pub use wallet_client_storage::*;
@@ -3,7 +3,14 @@ use crate::prelude::*;
 #[derive(Debug, Clone, PartialEq, Eq, Hash, uniffi::Enum)]
 pub enum SecureStorageKey {
     SnapshotHeadersList,
+    /// Marks `SnapshotHeadersList` as containing **every** Profile saved in
+    /// SecureStorage, which is not the case for Profiles saved before the
+    /// list was introduced, until `complete_profile_headers_list` is called.
+    SnapshotHeadersListIsComplete,
     ActiveProfileID,
+    /// The `DeviceInfo` of the host device, stamped as `last_used_on_device`
+    /// in the header of the active Profile whenever it is mutated.
+    DeviceInfo,
     DeviceFactorSourceMnemonic {
         factor_source_id: FactorSourceIDFromHash,
     },
@@ -20,6 +27,9 @@ impl SecureStorageKey {
                 SecureStorageKey::ActiveProfileID =>
                     "activeProfileID".to_string(),
                 SecureStorageKey::SnapshotHeadersList => "headers".to_string(),
+                SecureStorageKey::SnapshotHeadersListIsComplete =>
+                    "headers_is_complete".to_string(),
+                SecureStorageKey::DeviceInfo => "device_info".to_string(),
                 SecureStorageKey::DeviceFactorSourceMnemonic {
                     factor_source_id,
                 } => format!("device_factor_source_{}", factor_source_id),
@@ -30,6 +40,42 @@ impl SecureStorageKey {
     }
 }
 
+/// The kind of a `SecureStorageKey`, i.e. the key without any associated
+/// values, e.g. used to refer to all `ProfileSnapshot` keys at once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, uniffi::Enum)]
+pub enum SecureStorageKeyKind {
+    SnapshotHeadersList,
+    SnapshotHeadersListIsComplete,
+    ActiveProfileID,
+    DeviceInfo,
+    DeviceFactorSourceMnemonic,
+    ProfileSnapshot,
+}
+
+impl SecureStorageKey {
+    /// The kind of this key, without any associated values.
+    pub fn kind(&self) -> SecureStorageKeyKind {
+        match self {
+            SecureStorageKey::SnapshotHeadersList => {
+                SecureStorageKeyKind::SnapshotHeadersList
+            }
+            SecureStorageKey::SnapshotHeadersListIsComplete => {
+                SecureStorageKeyKind::SnapshotHeadersListIsComplete
+            }
+            SecureStorageKey::ActiveProfileID => {
+                SecureStorageKeyKind::ActiveProfileID
+            }
+            SecureStorageKey::DeviceInfo => SecureStorageKeyKind::DeviceInfo,
+            SecureStorageKey::DeviceFactorSourceMnemonic { .. } => {
+                SecureStorageKeyKind::DeviceFactorSourceMnemonic
+            }
+            SecureStorageKey::ProfileSnapshot { .. } => {
+                SecureStorageKeyKind::ProfileSnapshot
+            }
+        }
+    }
+}
+
 #[uniffi::export]
 pub fn secure_storage_key_identifier(key: &SecureStorageKey) -> String {
     key.identifier()
@@ -49,6 +95,14 @@ mod tests {
             SecureStorageKey::SnapshotHeadersList.identifier(),
             "secure_storage_key_headers"
         );
+        assert_eq!(
+            SecureStorageKey::SnapshotHeadersListIsComplete.identifier(),
+            "secure_storage_key_headers_is_complete"
+        );
+        assert_eq!(
+            SecureStorageKey::DeviceInfo.identifier(),
+            "secure_storage_key_device_info"
+        );
         assert_eq!(
             SecureStorageKey::DeviceFactorSourceMnemonic {
                 factor_source_id: FactorSourceIDFromHash::placeholder()
@@ -64,6 +118,28 @@ mod tests {
             "secure_storage_key_profile_snapshot_ffffffff-ffff-ffff-ffff-ffffffffffff"
         );
     }
+
+    #[test]
+    fn kind() {
+        assert_eq!(
+            SecureStorageKey::DeviceInfo.kind(),
+            SecureStorageKeyKind::DeviceInfo
+        );
+        assert_eq!(
+            SecureStorageKey::DeviceFactorSourceMnemonic {
+                factor_source_id: FactorSourceIDFromHash::placeholder()
+            }
+            .kind(),
+            SecureStorageKeyKind::DeviceFactorSourceMnemonic
+        );
+        assert_eq!(
+            SecureStorageKey::ProfileSnapshot {
+                profile_id: ProfileID::placeholder()
+            }
+            .kind(),
+            SecureStorageKeyKind::ProfileSnapshot
+        );
+    }
 }
 
 #[cfg(test)]
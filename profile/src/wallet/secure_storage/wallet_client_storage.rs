@@ -97,6 +97,48 @@ impl WalletClientStorage {
     }
 }
 
+//======
+// Delete
+//======
+impl WalletClientStorage {
+    /// Deletes the bytes saved under `key` from SecureStorage.
+    pub fn delete(&self, key: SecureStorageKey) -> Result<()> {
+        self.interface.delete_data_for_key(key)
+    }
+}
+
+//======
+// Profile snapshot
+//======
+impl WalletClientStorage {
+    /// Loads the Profile snapshot with `profile_id`, migrating it to the
+    /// current `ProfileSnapshotVersion` if it was saved using an older one.
+    ///
+    /// Returns `Ok(None)` if no snapshot was found.
+    pub fn load_profile_snapshot(
+        &self,
+        profile_id: &ProfileID,
+    ) -> Result<Option<Profile>> {
+        self.interface
+            .load_data(SecureStorageKey::ProfileSnapshot {
+                profile_id: profile_id.clone(),
+            })?
+            .map(Profile::from_snapshot_json_bytes)
+            .transpose()
+    }
+
+    /// Like `load_profile_snapshot` but fails with `ProfileSnapshotNotFound`
+    /// if no snapshot was found.
+    pub fn load_profile_snapshot_or_fail(
+        &self,
+        profile_id: &ProfileID,
+    ) -> Result<Profile> {
+        self.load_profile_snapshot(profile_id).and_then(|p| {
+            p.ok_or(CommonError::ProfileSnapshotNotFound(profile_id.clone()))
+        })
+    }
+}
+
 //======
 // Mnemonic CR(U)D
 //======
@@ -131,6 +173,19 @@ impl WalletClientStorage {
         )
     }
 
+    /// Returns `true` if a MnemonicWithPassphrase with a `FactorSourceIDFromHash`
+    /// is saved, e.g. by any of the stored Profiles.
+    pub fn contains_mnemonic(
+        &self,
+        id: &FactorSourceIDFromHash,
+    ) -> Result<bool> {
+        self.interface
+            .load_data(SecureStorageKey::DeviceFactorSourceMnemonic {
+                factor_source_id: id.clone(),
+            })
+            .map(|d| d.is_some())
+    }
+
     /// Deletes a MnemonicWithPassphrase with a `FactorSourceIDFromHash`
     pub fn delete_mnemonic(&self, id: &FactorSourceIDFromHash) -> Result<()> {
         self.interface.delete_data_for_key(
@@ -318,4 +373,45 @@ mod tests {
             Err(CommonError::FailedToSerializeToJSON)
         );
     }
+
+    #[test]
+    fn load_profile_snapshot() {
+        let (sut, _) = WalletClientStorage::ephemeral();
+        let profile = Profile::placeholder();
+        assert_eq!(sut.load_profile_snapshot(&profile.id()), Ok(None));
+        assert_eq!(
+            sut.load_profile_snapshot_or_fail(&profile.id()),
+            Err(CommonError::ProfileSnapshotNotFound(profile.id()))
+        );
+        sut.save(
+            SecureStorageKey::ProfileSnapshot {
+                profile_id: profile.id(),
+            },
+            &profile,
+        )
+        .unwrap();
+        assert_eq!(
+            sut.load_profile_snapshot_or_fail(&profile.id()),
+            Ok(profile)
+        );
+    }
+
+    #[test]
+    fn load_profile_snapshot_unsupported_version() {
+        let (sut, _) = WalletClientStorage::ephemeral();
+        let profile = Profile::placeholder();
+        let mut json = serde_json::to_value(&profile).unwrap();
+        json["header"]["snapshotVersion"] = json!(999);
+        sut.save(
+            SecureStorageKey::ProfileSnapshot {
+                profile_id: profile.id(),
+            },
+            &json,
+        )
+        .unwrap();
+        assert_eq!(
+            sut.load_profile_snapshot(&profile.id()),
+            Err(CommonError::UnsupportedProfileSnapshotVersion(999))
+        );
+    }
 }
@@ -1,13 +1,15 @@
 use crate::prelude::*;
-use std::sync::{Once, RwLock, RwLockReadGuard, RwLockWriteGuard};
+use std::sync::{Once, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
 
 pub type HeadersList = IdentifiedVecVia<Header>;
 
 #[derive(Debug, uniffi::Object)]
 pub struct Wallet {
-    // This is pub(crate) for testing purposes only, i.e. causing the RwLock to be poisoned.
+    // This is pub(crate) for testing purposes only, always access it using
+    // `read_profile` or `write_profile`.
     pub(crate) profile: RwLock<Profile>,
     pub(crate) wallet_client_storage: WalletClientStorage,
+    pub(crate) observers: RwLock<Vec<Arc<dyn WalletObserver>>>,
 }
 
 impl Wallet {
@@ -33,11 +35,21 @@ impl Wallet {
         let wallet = Self {
             profile: RwLock::new(profile.clone()),
             wallet_client_storage,
+            observers: RwLock::new(Vec::new()),
         };
 
+        // A headers list started on an empty storage contains every Profile
+        let is_storage_without_profiles = wallet.is_storage_without_profiles();
+
         // Save new profile (also sets activeProfileID)
         wallet.save_new_profile_or_panic(&profile);
 
+        if is_storage_without_profiles {
+            if let Err(e) = wallet.mark_headers_list_complete() {
+                error!("Failed to mark headers list as complete, error: {}", e);
+            }
+        }
+
         wallet
     }
 
@@ -45,21 +57,15 @@ impl Wallet {
         profile_id: ProfileID,
         wallet_client_storage: WalletClientStorage,
     ) -> Result<Self> {
-        // Form storage key
-        let profile_key = SecureStorageKey::ProfileSnapshot {
-            profile_id: profile_id.clone(),
-        };
-
-        // Load Profile from storage with key
-        let profile: Profile = wallet_client_storage.load_or(
-            profile_key,
-            CommonError::ProfileSnapshotNotFound(profile_id.clone()),
-        )?;
+        // Load Profile from storage, migrating it if needed
+        let profile =
+            wallet_client_storage.load_profile_snapshot_or_fail(&profile_id)?;
 
         // Create wallet
         let wallet = Self {
             profile: RwLock::new(profile),
             wallet_client_storage,
+            observers: RwLock::new(Vec::new()),
         };
 
         // Set active profile ID
@@ -99,7 +105,9 @@ impl Wallet {
             private_hd_factor_source.clone(),
             wallet_client_name.as_str(),
         );
+        let creating_device = profile.header.creating_device.clone();
         let wallet = Self::with_imported_profile(profile, secure_storage);
+        wallet.save_host_device_info_if_absent(&creating_device)?;
         wallet.wallet_client_storage.save(
             SecureStorageKey::DeviceFactorSourceMnemonic {
                 factor_source_id: private_hd_factor_source
@@ -180,6 +188,23 @@ impl Wallet {
             storage,
         )
     }
+
+    /// An ephemeral Wallet with `Profile::placeholder()`, with the mnemonic of
+    /// its BDFS saved in SecureStorage, so that entities can be created.
+    pub(crate) fn ephemeral_with_bdfs_mnemonic(
+    ) -> (Self, Arc<EphemeralSecureStorage>) {
+        let private =
+            PrivateHierarchicalDeterministicFactorSource::placeholder();
+        let (wallet, storage) = Self::ephemeral(Profile::placeholder());
+        wallet
+            .wallet_client_storage
+            .save_mnemonic_with_passphrase(
+                &private.mnemonic_with_passphrase,
+                &private.factor_source.id,
+            )
+            .expect("Should always be able to save to EphemeralSecureStorage");
+        (wallet, storage)
+    }
 }
 #[cfg(test)]
 impl HasPlaceholder for Wallet {
@@ -203,6 +228,14 @@ impl Wallet {
             .expect("Should always be able to JSON serialize a Profile.")
     }
 
+    /// Takes a snapshot of the profile with all personal and secret values
+    /// replaced by placeholders, see `Profile::redacted`, and serialize it
+    /// as a String of JSON, suitable to be attached to a support ticket.
+    pub fn redacted_json_snapshot(&self) -> String {
+        serde_json::to_string(&self.profile().redacted())
+            .expect("Should always be able to JSON serialize a Profile.")
+    }
+
     /// Clone the profile and return it.
     pub fn profile(&self) -> Profile {
         self.access_profile_with(|p| p.clone())
@@ -210,46 +243,79 @@ impl Wallet {
 }
 
 impl Wallet {
+    /// Acquires a read lock on the Profile, blocking the current thread until
+    /// any writer has committed, so Wallet clients may read and write the
+    /// Profile from multiple threads.
+    ///
+    /// A poisoned lock is recovered from, since the Profile behind it is only
+    /// ever replaced with an already saved copy, see `try_update_profile_with`,
+    /// it is always in a consistent state, even if a thread panicked.
+    pub(crate) fn read_profile(&self) -> RwLockReadGuard<'_, Profile> {
+        self.profile.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Acquires a write lock on the Profile, blocking the current thread until
+    /// all other readers and writers have released theirs, see `read_profile`.
+    pub(crate) fn write_profile(&self) -> RwLockWriteGuard<'_, Profile> {
+        self.profile.write().unwrap_or_else(PoisonError::into_inner)
+    }
+
     pub(crate) fn access_profile_with<T: Clone, F>(&self, access: F) -> T
     where
         F: Fn(RwLockReadGuard<'_, Profile>) -> T,
     {
-        self.profile
-            .try_read()
-            .map(access)
-            .expect("Implementing Wallet clients should not read and write Profile from Wallet from multiple threads.")
+        access(self.read_profile())
     }
 
-    pub(crate) fn update_profile_with<F, R>(&self, mutate: F) -> R
+    /// Mutates the Profile using `mutate` and saves it to SecureStorage, see
+    /// `try_update_profile_with`.
+    pub(crate) fn update_profile_with<F, R>(&self, mutate: F) -> Result<R>
     where
-        F: Fn(RwLockWriteGuard<'_, Profile>) -> R,
+        F: FnOnce(&mut Profile) -> R,
     {
-        let value = self.profile
-            .try_write()
-            .map(mutate)
-            .expect("Implementing Wallet clients should not read and write Profile from Wallet from multiple threads.");
-
-        self.save_existing_profile()
-            .expect("Failed to save Profile to secure storage.");
-
-        value
+        self.try_update_profile_with(|p| Ok(mutate(p)))
     }
 
+    /// Commits a mutation of the Profile as a transaction: `mutate` is run
+    /// on a copy of the Profile, which is then saved to SecureStorage and
+    /// only if saving succeeded does the copy replace the in-memory Profile.
+    /// Before saving, the header of the copy is updated, see
+    /// `Profile::update_header`, using the `DeviceInfo` of the host device.
+    ///
+    /// If either `mutate` or saving fails the error is returned and the
+    /// in-memory Profile remains unchanged.
+    ///
+    /// The write lock is held from copying the Profile until the copy has
+    /// replaced it, so concurrent updates are applied one after another and
+    /// none of them is lost. `mutate` MUST NOT access the Profile of this
+    /// Wallet, since that would deadlock.
     #[cfg(not(tarpaulin_include))] // false negative
     pub(crate) fn try_update_profile_with<F, R>(&self, mutate: F) -> Result<R>
     where
-        F: Fn(RwLockWriteGuard<'_, Profile>) -> Result<R>,
+        F: FnOnce(&mut Profile) -> Result<R>,
     {
-        let res = self
-            .profile
-            .try_write()
-            .map_err(|_| CommonError::UnableToAcquireWriteLockForProfile)
-            .and_then(mutate)?;
+        let mut profile = self.write_profile();
 
-        self.save_existing_profile()?;
+        let mut updated = profile.clone();
+        let res = mutate(&mut updated)?;
+        let device_info = self
+            .host_device_info()?
+            .unwrap_or_else(|| updated.header.last_used_on_device.clone());
+        updated.update_header(device_info);
+
+        self.save_profile(&updated)?;
+        let profile_id = updated.id();
+        *profile = updated;
+        drop(profile);
+
+        self.notify_profile_saved(profile_id);
 
         Ok(res)
     }
+
+    fn notify_profile_saved(&self, profile_id: ProfileID) {
+        self.notify_observers(WalletEvent::ProfileSaved { profile_id })
+    }
 }
 
 #[cfg(test)]
@@ -257,6 +323,7 @@ mod tests {
     use radix_engine_toolkit_json::models::transaction::header;
 
     use crate::prelude::*;
+    use std::sync::atomic::{AtomicBool, Ordering as AtomicOrdering};
     #[test]
     fn read_header() {
         let wallet = Wallet::placeholder();
@@ -270,6 +337,238 @@ mod tests {
         let wallet = Wallet::placeholder();
         assert_eq!(wallet.profile(), Profile::placeholder())
     }
+
+    #[derive(Debug)]
+    struct FailSavingProfileStorage {
+        inner: Arc<EphemeralSecureStorage>,
+        fail: AtomicBool,
+    }
+
+    impl SecureStorage for FailSavingProfileStorage {
+        fn load_data(&self, key: SecureStorageKey) -> Result<Option<Vec<u8>>> {
+            self.inner.load_data(key)
+        }
+
+        fn save_data(
+            &self,
+            key: SecureStorageKey,
+            data: Vec<u8>,
+        ) -> Result<()> {
+            if self.fail.load(AtomicOrdering::SeqCst) {
+                return Err(CommonError::SecureStorageWriteError);
+            }
+            self.inner.save_data(key, data)
+        }
+
+        fn delete_data_for_key(&self, key: SecureStorageKey) -> Result<()> {
+            self.inner.delete_data_for_key(key)
+        }
+    }
+
+    fn wallet_failing_to_save() -> (Wallet, Arc<EphemeralSecureStorage>) {
+        let inner = EphemeralSecureStorage::new();
+        let storage = Arc::new(FailSavingProfileStorage {
+            inner: inner.clone(),
+            fail: AtomicBool::new(false),
+        });
+        let wallet = Wallet::by_importing_profile(
+            Profile::placeholder(),
+            storage.clone(),
+        );
+        storage.fail.store(true, AtomicOrdering::SeqCst);
+        (wallet, inner)
+    }
+
+    fn saved_profile(storage: &EphemeralSecureStorage) -> Profile {
+        let data = storage
+            .load_data(SecureStorageKey::ProfileSnapshot {
+                profile_id: Profile::placeholder().id(),
+            })
+            .unwrap()
+            .unwrap();
+        serde_json::from_slice(&data).unwrap()
+    }
+
+    #[test]
+    fn try_update_profile_with_rolls_back_when_save_fails() {
+        let (wallet, storage) = wallet_failing_to_save();
+        let address = wallet
+            .access_profile_with(|p| p.networks[0].accounts[0].address.clone());
+        assert_eq!(
+            wallet.change_name_of_account(
+                address,
+                DisplayName::new("Stella").unwrap()
+            ),
+            Err(CommonError::SecureStorageWriteError)
+        );
+        assert_eq!(wallet.profile(), Profile::placeholder());
+        assert_eq!(saved_profile(&storage), Profile::placeholder());
+    }
+
+    #[test]
+    fn update_profile_with_returns_err_instead_of_panic_when_save_fails() {
+        let (wallet, storage) = wallet_failing_to_save();
+        assert_eq!(
+            wallet.update_profile_with(|p| p.networks = ProfileNetworks::new()),
+            Err(CommonError::SecureStorageWriteError)
+        );
+        assert_eq!(wallet.profile(), Profile::placeholder());
+        assert_eq!(saved_profile(&storage), Profile::placeholder());
+    }
+
+    #[test]
+    fn try_update_profile_with_updates_header() {
+        let (wallet, storage) = Wallet::ephemeral(Profile::placeholder());
+        let before = wallet.profile().header;
+        wallet
+            .add_account(Account::placeholder_mainnet_carol())
+            .unwrap();
+        let header = wallet.profile().header;
+
+        assert!(header.last_modified > before.last_modified);
+        assert_eq!(header.last_used_on_device, before.last_used_on_device);
+        assert_eq!(header.content_hint, ContentHint::with_counters(5, 4, 2));
+        assert_eq!(header.creating_device, before.creating_device);
+        assert_eq!(saved_profile(&storage).header, header);
+    }
+
+    #[test]
+    fn try_update_profile_with_uses_same_host_device_info() {
+        let (wallet, _) = Wallet::ephemeral(Profile::placeholder());
+        wallet
+            .update_app_preferences(AppPreferences::placeholder_other())
+            .unwrap();
+        let first = wallet.profile().header.last_used_on_device;
+        wallet
+            .update_app_preferences(AppPreferences::placeholder())
+            .unwrap();
+        assert_eq!(wallet.profile().header.last_used_on_device, first);
+    }
+
+    fn counter_account_address(wallet: &Wallet) -> AccountAddress {
+        wallet
+            .access_profile_with(|p| p.networks[0].accounts[0].address.clone())
+    }
+
+    fn counter(wallet: &Wallet) -> usize {
+        wallet.access_profile_with(|p| {
+            p.networks[0].accounts[0]
+                .display_name
+                .value
+                .parse()
+                .unwrap()
+        })
+    }
+
+    /// Increments a counter stored as the display name of the first account,
+    /// reading the current value inside of the transaction.
+    fn increment_counter(wallet: &Wallet) -> Result<Account> {
+        let address = counter_account_address(wallet);
+        wallet.try_update_profile_with(|p| {
+            p.update_account(&address, |a| {
+                let n: usize = a.display_name.value.parse().unwrap();
+                a.display_name =
+                    DisplayName::new(&(n + 1).to_string()).unwrap();
+            })
+            .ok_or(CommonError::UnknownAccount)
+        })
+    }
+
+    fn counter_wallet() -> (Wallet, Arc<EphemeralSecureStorage>) {
+        let (wallet, storage) = Wallet::ephemeral(Profile::placeholder());
+        wallet
+            .change_name_of_account(
+                counter_account_address(&wallet),
+                DisplayName::new("0").unwrap(),
+            )
+            .unwrap();
+        (wallet, storage)
+    }
+
+    #[test]
+    fn concurrent_writes_are_never_lost() {
+        let (wallet, storage) = counter_wallet();
+        let writers = 8;
+        let writes_per_writer = 25;
+        std::thread::scope(|s| {
+            for _ in 0..writers {
+                s.spawn(|| {
+                    for _ in 0..writes_per_writer {
+                        increment_counter(&wallet).unwrap();
+                    }
+                });
+            }
+        });
+        assert_eq!(counter(&wallet), writers * writes_per_writer);
+        assert_eq!(saved_profile(&storage), wallet.profile());
+    }
+
+    #[test]
+    fn concurrent_reads_and_writes_do_not_panic() {
+        let (wallet, _) = counter_wallet();
+        let iterations = 50;
+        std::thread::scope(|s| {
+            for _ in 0..4 {
+                s.spawn(|| {
+                    for _ in 0..iterations {
+                        increment_counter(&wallet).unwrap();
+                    }
+                });
+                s.spawn(|| {
+                    for i in 0..iterations {
+                        let preferences = if i % 2 == 0 {
+                            AppPreferences::placeholder_other()
+                        } else {
+                            AppPreferences::placeholder()
+                        };
+                        wallet.update_app_preferences(preferences).unwrap();
+                    }
+                });
+                s.spawn(|| {
+                    for _ in 0..iterations {
+                        assert_eq!(wallet.profile().networks.len(), 2);
+                        assert!(!wallet.json_snapshot().is_empty());
+                        assert_eq!(wallet.profile_headers().unwrap().len(), 1);
+                    }
+                });
+            }
+        });
+        assert_eq!(counter(&wallet), 4 * iterations);
+    }
+
+    #[test]
+    fn reads_recover_after_panic_while_writing() {
+        let (wallet, _) = counter_wallet();
+        std::thread::scope(|s| {
+            assert!(s
+                .spawn(|| {
+                    wallet.update_profile_with(|p| {
+                        p.networks = ProfileNetworks::new();
+                        panic!("Panicking while holding the write lock");
+                    })
+                })
+                .join()
+                .is_err());
+        });
+        assert!(wallet.profile.is_poisoned());
+        assert_eq!(counter(&wallet), 0);
+        increment_counter(&wallet).unwrap();
+        assert_eq!(counter(&wallet), 1);
+    }
+
+    #[test]
+    fn try_update_profile_with_unchanged_when_mutate_fails() {
+        let (wallet, storage) = Wallet::ephemeral(Profile::placeholder());
+        assert_eq!(
+            wallet.try_update_profile_with(|p| {
+                p.networks = ProfileNetworks::new();
+                Err::<(), _>(CommonError::Unknown)
+            }),
+            Err(CommonError::Unknown)
+        );
+        assert_eq!(wallet.profile(), Profile::placeholder());
+        assert_eq!(saved_profile(&storage), Profile::placeholder());
+    }
 }
 
 #[cfg(test)]
@@ -341,6 +640,16 @@ mod uniffi_tests {
         assert_eq!(wallet.json_snapshot(), expected_json);
     }
 
+    #[test]
+    fn redacted_snapshot_json() {
+        let (wallet, _) = Wallet::ephemeral(Profile::placeholder());
+        let json = wallet.redacted_json_snapshot();
+        assert_eq!(
+            serde_json::from_str::<Profile>(&json).unwrap(),
+            Profile::placeholder().redacted()
+        );
+    }
+
     #[test]
     fn by_creating_new_profile_and_secrets_with_entropy() {
         let secure_storage = EphemeralSecureStorage::new();
@@ -370,5 +679,12 @@ mod uniffi_tests {
         let active_id =
             serde_json::from_slice::<ProfileID>(&active_id_data).unwrap();
         assert_eq!(active_id, wallet.profile().id());
+
+        let device_info: DeviceInfo = wallet
+            .wallet_client_storage
+            .load(SecureStorageKey::DeviceInfo)
+            .unwrap()
+            .unwrap();
+        assert_eq!(device_info, wallet.profile().header.creating_device);
     }
 }
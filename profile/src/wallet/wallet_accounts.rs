@@ -48,10 +48,10 @@ impl Wallet {
     /// Returns `Err` if `factor_source` is already present in factor source,
     /// or if saving to SecureStorage fails.
     ///
-    /// If only saving to SecureStorage fails, the Profile still remains
-    /// edited.
+    /// If saving to SecureStorage fails, the Profile remains unchanged.
     pub fn add_factor_source(&self, factor_source: FactorSource) -> Result<()> {
-        self.try_update_profile_with(|mut p| {
+        let id = factor_source.factor_source_id();
+        self.try_update_profile_with(|p| {
             trace!(
                 "About to add FactorSource: {}, to list of factor sources: {}",
                 &factor_source,
@@ -69,10 +69,11 @@ impl Wallet {
             }
         })
         .map_err(|_| {
-            CommonError::UnableToSaveFactorSourceToProfile(
-                factor_source.factor_source_id(),
-            )
-        })
+            CommonError::UnableToSaveFactorSourceToProfile(id.clone())
+        })?;
+
+        self.notify_observers(WalletEvent::FactorSourceAdded { id });
+        Ok(())
     }
 
     /// Loads a `MnemonicWithPassphrase` with the `id` of `device_factor_source`,
@@ -133,28 +134,76 @@ impl Wallet {
         network_id: NetworkID,
         name: DisplayName,
     ) -> Result<Account> {
+        self.create_new_accounts(network_id, vec![name])
+            .map(|accounts| {
+                accounts
+                    .into_iter()
+                    .next()
+                    .expect("Should have created one account per name.")
+            })
+    }
+
+    /// Creates one new non securified account per name in `names` **WITHOUT**
+    /// adding them to Profile, using the *main* "Babylon" `DeviceFactorSource`
+    /// and consecutive derivation indices, starting at the "next" index for
+    /// this FactorSource. The mnemonic is loaded from SecureStorage only once.
+    ///
+    /// The `AppearanceID`s of the accounts are assigned in sequence, following
+    /// the accounts already on the network.
+    ///
+    /// If you want to add them to Profile, call
+    /// `wallet.create_and_save_new_accounts(network_id, names)` instead.
+    pub fn create_new_accounts(
+        &self,
+        network_id: NetworkID,
+        names: Vec<DisplayName>,
+    ) -> Result<Vec<Account>> {
+        if names.is_empty() {
+            return Ok(Vec::new());
+        }
         let profile = &self.profile();
         let bdfs = profile.bdfs();
-        let index = profile
-            .next_derivation_index_for_entity(EntityKind::Accounts, network_id);
+        let index = profile.next_derivation_index_for_entity(
+            EntityKind::Accounts,
+            network_id,
+        )?;
+        let indices = (0..names.len())
+            .map(|i| {
+                HDPathValue::try_from(i)
+                    .ok()
+                    .and_then(|i| index.checked_add(i))
+                    .filter(|index| *index < Profile::DERIVATION_INDEX_LIMIT)
+                    .ok_or(CommonError::DerivationIndicesExhausted {
+                        entity_kind: EntityKind::Accounts,
+                        network_id,
+                        factor_source_id: bdfs.id.clone(),
+                    })
+            })
+            .collect::<Result<Vec<HDPathValue>>>()?;
         let number_of_accounts_on_network = profile
             .networks
             .get(&network_id)
             .map(|n| n.accounts.len())
             .unwrap_or(0);
 
-        let appearance_id = AppearanceID::from_number_of_accounts_on_network(
-            number_of_accounts_on_network,
-        );
+        let private_bdfs = self.load_private_device_factor_source(&bdfs)?;
 
-        let factor_instance =
-            self.load_private_device_factor_source(&bdfs).map(|p| {
-                p.derive_entity_creation_factor_instance(network_id, index)
-            })?;
-
-        let account = Account::new(factor_instance, name, appearance_id);
+        let accounts = names
+            .into_iter()
+            .zip(indices)
+            .enumerate()
+            .map(|(i, (name, index))| {
+                let factor_instance = private_bdfs
+                    .derive_entity_creation_factor_instance(network_id, index);
+                let appearance_id =
+                    AppearanceID::from_number_of_accounts_on_network(
+                        number_of_accounts_on_network + i,
+                    );
+                Account::new(factor_instance, name, appearance_id)
+            })
+            .collect_vec();
 
-        Ok(account)
+        Ok(accounts)
     }
 
     /// Returns `Ok(())` if the `account` was new and successfully added. If saving failed or if the account was already present in Profile, an
@@ -164,7 +213,11 @@ impl Wallet {
         let network_id = account.network_id;
         let err_exists =
             CommonError::AccountAlreadyPresent(account.id().clone());
-        self.try_update_profile_with(|mut p| {
+        self.try_update_profile_with(|p| {
+            if p.networks.get_account(&account.address).is_some() {
+                return Err(err_exists.clone());
+            }
+            p.ensure_entity_derivation_index_is_free(&account.security_state)?;
             let networks = &mut p.networks;
             if networks.contains_id(&network_id) {
                 networks
@@ -188,7 +241,12 @@ impl Wallet {
                 networks.append(network);
                 Ok(())
             }
-        })
+        })?;
+
+        self.notify_observers(WalletEvent::AccountAdded {
+            address: account.address,
+        });
+        Ok(())
     }
 
     /// Create a new Account and adds it to the active Profile.
@@ -202,12 +260,155 @@ impl Wallet {
         Ok(account)
     }
 
+    /// Creates one new Account per name in `names` and adds them all to the
+    /// active Profile, which is saved once. See `create_new_accounts`.
+    ///
+    /// Returns `Err` if any of the accounts could not be added, in which case
+    /// none of them is added.
+    pub fn create_and_save_new_accounts(
+        &self,
+        network_id: NetworkID,
+        names: Vec<DisplayName>,
+    ) -> Result<Vec<Account>> {
+        let accounts = self.create_new_accounts(network_id, names)?;
+        if accounts.is_empty() {
+            return Ok(accounts);
+        }
+        self.try_update_profile_with(|p| {
+            for account in accounts.iter() {
+                p.ensure_entity_derivation_index_is_free(
+                    &account.security_state,
+                )?;
+            }
+            p.add_accounts(accounts.clone())
+        })?;
+
+        self.notify_accounts_added(&accounts);
+        Ok(accounts)
+    }
+
+    /// Creates a new non securified account **WITHOUT** adding it to Profile,
+    /// controlled by the FactorSource with `factor_source_id`, using the next
+    /// free derivation index of that FactorSource.
+    ///
+    /// The public key of a `DeviceFactorSource` is derived from its mnemonic,
+    /// loaded from SecureStorage, whereas the public key of a
+    /// `LedgerHardwareWalletFactorSource` is derived by the Ledger device
+    /// using `ledger_driver`, since its mnemonic never leaves the device.
+    ///
+    /// Returns `Err` if Profile does not contain the FactorSource, if it does
+    /// not support Babylon, or if it is a Ledger and `ledger_driver` is `None`
+    /// or fails to derive the public key.
+    ///
+    /// If you want to add it to Profile, call `wallet.add_account(account)`
+    pub fn create_new_account_with_factor_source(
+        &self,
+        network_id: NetworkID,
+        name: DisplayName,
+        factor_source_id: FactorSourceID,
+        ledger_driver: Option<Arc<dyn LedgerHardwareWalletDriver>>,
+    ) -> Result<Account> {
+        let profile = &self.profile();
+        let factor_source = profile
+            .factor_sources
+            .get(&factor_source_id)
+            .ok_or(CommonError::ProfileDoesNotContainFactorSourceWithID(
+                factor_source_id.clone(),
+            ))?;
+        let (id, common) = match factor_source {
+            FactorSource::Device { value } => (value.id.clone(), &value.common),
+            FactorSource::Ledger { value } => (value.id.clone(), &value.common),
+        };
+        if !common.supports_babylon() {
+            return Err(CommonError::FactorSourceDoesNotSupportBabylon(
+                factor_source_id,
+            ));
+        }
+
+        let index = profile
+            .next_derivation_index_for_entity_for_factor_source(
+                EntityKind::Accounts,
+                network_id,
+                id.clone(),
+            )?;
+        let path: DerivationPath = AccountPath::new(
+            network_id,
+            CAP26KeyKind::TransactionSigning,
+            index,
+        )
+        .into();
+
+        let public_key = match factor_source {
+            FactorSource::Device { value } => self
+                .load_private_device_factor_source(value)?
+                .mnemonic_with_passphrase
+                .derive_private_key(path)
+                .public_key(),
+            FactorSource::Ledger { .. } => {
+                let ledger_driver = ledger_driver.ok_or(
+                    CommonError::LedgerHardwareWalletDriverRequired(id.clone()),
+                )?;
+                ledger_driver
+                    .derive_public_keys(id.clone(), vec![path.clone()])?
+                    .into_iter()
+                    .exactly_one()
+                    .ok()
+                    .filter(|k| k.derivation_path == path)
+                    .ok_or(
+                        CommonError::UnexpectedPublicKeysFromLedgerHardwareWallet(
+                            id.clone(),
+                        ),
+                    )?
+            }
+        };
+
+        let number_of_accounts_on_network = profile
+            .networks
+            .get(&network_id)
+            .map(|n| n.accounts.len())
+            .unwrap_or(0);
+
+        Account::with_transaction_signing_factor_instance(
+            network_id,
+            HierarchicalDeterministicFactorInstance::new(id, public_key),
+            name,
+            AppearanceID::from_number_of_accounts_on_network(
+                number_of_accounts_on_network,
+            ),
+        )
+    }
+
+    /// Create a new Account controlled by the FactorSource with
+    /// `factor_source_id` and adds it to the active Profile, see
+    /// `create_new_account_with_factor_source`.
+    pub fn create_and_save_new_account_with_factor_source(
+        &self,
+        network_id: NetworkID,
+        name: DisplayName,
+        factor_source_id: FactorSourceID,
+        ledger_driver: Option<Arc<dyn LedgerHardwareWalletDriver>>,
+    ) -> Result<Account> {
+        let account = self.create_new_account_with_factor_source(
+            network_id,
+            name,
+            factor_source_id,
+            ledger_driver,
+        )?;
+        self.add_account(account.clone())?;
+        Ok(account)
+    }
+
     /// Updates `account` as a whole, if it exists, else an error is thrown.
     pub fn update_account(&self, to: Account) -> Result<Account> {
-        self.update_profile_with(|mut p| {
+        let account = self.try_update_profile_with(|p| {
             p.update_account(&to.address, |a| *a = to.to_owned())
-        })
-        .ok_or(CommonError::UnknownAccount)
+                .ok_or(CommonError::UnknownAccount)
+        })?;
+
+        self.notify_observers(WalletEvent::AccountUpdated {
+            address: account.address.clone(),
+        });
+        Ok(account)
     }
 
     /// Updates the display name of account with the provided address, throws an error if the account is unknown to the wallet.
@@ -216,10 +417,82 @@ impl Wallet {
         address: AccountAddress,
         to: DisplayName,
     ) -> Result<Account> {
-        self.update_profile_with(|mut p| {
+        let account = self.try_update_profile_with(|p| {
             p.update_account(&address, |a| a.display_name = to.to_owned())
+                .ok_or(CommonError::UnknownAccount)
+        })?;
+
+        self.notify_observers(WalletEvent::AccountRenamed {
+            address: account.address.clone(),
+            display_name: account.display_name.clone(),
+        });
+        Ok(account)
+    }
+
+    /// Hides the account with `address`, it is kept in Profile and can be
+    /// unhidden at any time using `unhide_account`.
+    ///
+    /// Returns `Err` if the account is unknown.
+    pub fn hide_account(&self, address: AccountAddress) -> Result<Account> {
+        self.update_account_flags(&address, |f| {
+            _ = f.insert_flag(EntityFlag::HiddenByUser)
         })
-        .ok_or(CommonError::UnknownAccount)
+    }
+
+    /// Unhides the account with `address`, previously hidden by
+    /// `hide_account`.
+    ///
+    /// Returns `Err` if the account is unknown.
+    pub fn unhide_account(&self, address: AccountAddress) -> Result<Account> {
+        self.update_account_flags(&address, |f| {
+            _ = f.remove_flag(&EntityFlag::HiddenByUser)
+        })
+    }
+
+    /// Flags the account with `address` as deleted by the user and removes
+    /// it from the accounts shared with AuthorizedDapps, the changes made to
+    /// them are returned.
+    ///
+    /// Returns `Err` if the account is unknown.
+    pub fn delete_account(
+        &self,
+        address: AccountAddress,
+    ) -> Result<Vec<AuthorizedDappCascade>> {
+        let cascades =
+            self.try_update_profile_with(|p| p.delete_account(&address))?;
+
+        self.notify_observers(WalletEvent::AccountDeleted { address });
+        self.notify_authorized_dapp_cascades(&cascades);
+        Ok(cascades)
+    }
+}
+
+impl Wallet {
+    pub(crate) fn notify_accounts_added(&self, accounts: &[Account]) {
+        for account in accounts {
+            self.notify_observers(WalletEvent::AccountAdded {
+                address: account.address.clone(),
+            });
+        }
+    }
+
+    fn update_account_flags<F>(
+        &self,
+        address: &AccountAddress,
+        mut mutate: F,
+    ) -> Result<Account>
+    where
+        F: FnMut(&mut EntityFlags),
+    {
+        let account = self.try_update_profile_with(|p| {
+            p.update_account(address, |a| mutate(&mut a.flags))
+                .ok_or(CommonError::UnknownAccount)
+        })?;
+
+        self.notify_observers(WalletEvent::AccountUpdated {
+            address: account.address.clone(),
+        });
+        Ok(account)
     }
 }
 
@@ -229,7 +502,7 @@ mod tests {
     use std::{
         borrow::{Borrow, BorrowMut},
         ops::Deref,
-        sync::atomic::AtomicBool,
+        sync::atomic::{AtomicBool, Ordering as AtomicOrdering},
     };
 
     use crate::prelude::*;
@@ -262,6 +535,69 @@ mod tests {
         );
     }
 
+    #[test]
+    fn hide_and_unhide_account() {
+        let (wallet, _) = Wallet::ephemeral(Profile::placeholder());
+        let address = Account::placeholder_mainnet_alice().address;
+        assert!(wallet
+            .hide_account(address.clone())
+            .unwrap()
+            .flags
+            .is_hidden());
+        wallet.access_profile_with(|p| {
+            assert!(p.networks[0].get_visible_accounts().is_empty())
+        });
+        assert!(wallet
+            .unhide_account(address.clone())
+            .unwrap()
+            .flags
+            .is_visible());
+        wallet.access_profile_with(|p| {
+            assert_eq!(p.networks[0].get_visible_accounts()[0].address, address)
+        });
+        assert_eq!(
+            wallet.hide_account(AccountAddress::placeholder_mainnet()),
+            Err(CommonError::UnknownAccount)
+        );
+    }
+
+    #[test]
+    fn delete_account() {
+        let (wallet, _) = Wallet::ephemeral(Profile::placeholder());
+        let address = Account::placeholder_mainnet_bob().address;
+        assert_eq!(wallet.delete_account(address.clone()), Ok(Vec::new()));
+        wallet.access_profile_with(|p| {
+            assert!(p
+                .networks
+                .get_account(&address)
+                .unwrap()
+                .flags
+                .contains_id(&EntityFlag::DeletedByUser))
+        });
+        assert_eq!(
+            wallet.delete_account(AccountAddress::placeholder_mainnet()),
+            Err(CommonError::UnknownAccount)
+        );
+    }
+
+    #[test]
+    fn add_account_fails_when_derivation_index_is_in_use() {
+        let (wallet, _) = Wallet::ephemeral(Profile::placeholder());
+        let mut account = Account::placeholder_mainnet_bob();
+        account.address = AccountAddress::placeholder_mainnet();
+        let before = wallet.profile();
+        assert_eq!(
+            wallet.add_account(account),
+            Err(CommonError::DerivationIndexAlreadyInUse {
+                index: 1,
+                entity_kind: EntityKind::Accounts,
+                network_id: NetworkID::Mainnet,
+                factor_source_id: before.bdfs().id,
+            })
+        );
+        assert_eq!(wallet.profile(), before);
+    }
+
     #[test]
     fn update_account() {
         let profile = Profile::placeholder();
@@ -355,21 +691,31 @@ mod tests {
         #[derive(Debug)]
         struct TestStorage {
             delete_data_was_called: Arc<RwLock<Option<SecureStorageKey>>>,
+            fail_saving_profile: AtomicBool,
         }
         impl SecureStorage for TestStorage {
             fn load_data(
                 &self,
                 _key: SecureStorageKey,
             ) -> Result<Option<Vec<u8>>> {
-                todo!()
+                Ok(None)
             }
 
             fn save_data(
                 &self,
-                _key: SecureStorageKey,
+                key: SecureStorageKey,
                 _data: Vec<u8>,
             ) -> Result<()> {
-                Ok(()) // mnemonic gets saved
+                match key {
+                    SecureStorageKey::ProfileSnapshot { .. }
+                        if self
+                            .fail_saving_profile
+                            .load(AtomicOrdering::SeqCst) =>
+                    {
+                        Err(CommonError::SecureStorageWriteError)
+                    }
+                    _ => Ok(()), // mnemonic gets saved
+                }
             }
 
             fn delete_data_for_key(&self, key: SecureStorageKey) -> Result<()> {
@@ -381,11 +727,14 @@ mod tests {
         }
         let storage = Arc::new(TestStorage {
             delete_data_was_called: delete_data_was_called.clone(),
+            fail_saving_profile: AtomicBool::new(false),
         });
         let wallet = Wallet::by_importing_profile(profile, storage.clone());
 
-        // Acquire write lock, in order to make `wallet.add_private_device_factor_source` fail (because cant have multiple writers).
-        let lock = wallet.profile.write().unwrap();
+        // Fail saving of Profile, in order to make `wallet.add_private_device_factor_source` fail.
+        storage
+            .fail_saving_profile
+            .store(true, AtomicOrdering::SeqCst);
 
         assert_eq!(
             wallet.add_private_device_factor_source(new.clone()),
@@ -393,7 +742,6 @@ mod tests {
                 new.factor_source.factor_source_id()
             ))
         );
-        drop(lock);
 
         assert_eq!(
             wallet
@@ -562,4 +910,335 @@ mod tests {
             assert_eq!(q.networks[0].accounts[2], a);
         })
     }
+
+    fn names(names: &[&str]) -> Vec<DisplayName> {
+        names.iter().map(|n| DisplayName::new(n).unwrap()).collect()
+    }
+
+    #[test]
+    fn create_new_accounts_uses_consecutive_indices() {
+        let (wallet, _) = Wallet::ephemeral_with_bdfs_mnemonic();
+        let single = wallet
+            .create_new_account(
+                NetworkID::Mainnet,
+                DisplayName::new("Carla").unwrap(),
+            )
+            .unwrap();
+        let accounts = wallet
+            .create_new_accounts(
+                NetworkID::Mainnet,
+                names(&["Carla", "Dave", "Erin"]),
+            )
+            .unwrap();
+        assert_eq!(accounts[0], single);
+        assert_eq!(
+            accounts.iter().map(derivation_index).collect_vec(),
+            [2, 3, 4]
+        );
+        assert_eq!(
+            accounts.iter().map(|a| a.appearance_id.value).collect_vec(),
+            [2, 3, 4]
+        );
+        assert_eq!(
+            accounts
+                .iter()
+                .map(|a| a.display_name.value.clone())
+                .collect_vec(),
+            ["Carla", "Dave", "Erin"]
+        );
+        assert_eq!(accounts.iter().map(|a| &a.address).unique().count(), 3);
+
+        // Not yet added to Profile
+        assert_eq!(wallet.profile().networks[0].accounts.len(), 2);
+    }
+
+    #[test]
+    fn create_and_save_new_accounts() {
+        let (wallet, _) = Wallet::ephemeral_with_bdfs_mnemonic();
+        let accounts = wallet
+            .create_and_save_new_accounts(
+                NetworkID::Mainnet,
+                names(&["Carla", "Dave", "Erin"]),
+            )
+            .unwrap();
+        let profile = wallet.profile();
+        assert_eq!(profile.networks[0].accounts.items()[2..], accounts[..]);
+        assert_eq!(
+            profile.next_derivation_index_for_entity(
+                EntityKind::Accounts,
+                NetworkID::Mainnet
+            ),
+            Ok(5)
+        );
+    }
+
+    #[test]
+    fn create_and_save_new_accounts_on_new_network() {
+        let (wallet, _) = Wallet::ephemeral_with_bdfs_mnemonic();
+        let accounts = wallet
+            .create_and_save_new_accounts(
+                NetworkID::Nebunet,
+                names(&["Carla", "Dave"]),
+            )
+            .unwrap();
+        let profile = wallet.profile();
+        let network = profile.networks.get(&NetworkID::Nebunet).unwrap();
+        assert_eq!(network.accounts.items(), accounts);
+        assert_eq!(
+            accounts.iter().map(|a| a.appearance_id.value).collect_vec(),
+            [0, 1]
+        );
+    }
+
+    #[test]
+    fn create_and_save_new_accounts_without_names() {
+        let (wallet, _) = Wallet::ephemeral(Profile::placeholder());
+        let before = wallet.profile();
+        // Mnemonic is not in SecureStorage, but is not needed either.
+        assert_eq!(
+            wallet.create_and_save_new_accounts(NetworkID::Mainnet, vec![]),
+            Ok(Vec::new())
+        );
+        assert_eq!(wallet.profile(), before);
+    }
+
+    #[test]
+    fn create_new_accounts_fails_without_mnemonic() {
+        let (wallet, _) = Wallet::ephemeral(Profile::placeholder());
+        let before = wallet.profile();
+        assert!(wallet
+            .create_and_save_new_accounts(NetworkID::Mainnet, names(&["Carla"]))
+            .is_err());
+        assert_eq!(wallet.profile(), before);
+    }
+
+    #[test]
+    fn create_new_accounts_fails_when_batch_exhausts_indices() {
+        let mut profile = Profile::placeholder();
+        profile
+            .add_accounts(vec![Account::new(
+                PrivateHierarchicalDeterministicFactorSource::placeholder()
+                    .derive_entity_creation_factor_instance(
+                        NetworkID::Mainnet,
+                        Profile::DERIVATION_INDEX_LIMIT - 2,
+                    ),
+                DisplayName::new("Imported").unwrap(),
+                AppearanceID::default(),
+            )])
+            .unwrap();
+        let (wallet, _) = Wallet::ephemeral(profile);
+        let private =
+            PrivateHierarchicalDeterministicFactorSource::placeholder();
+        wallet
+            .wallet_client_storage
+            .save_mnemonic_with_passphrase(
+                &private.mnemonic_with_passphrase,
+                &private.factor_source.id,
+            )
+            .unwrap();
+
+        assert_eq!(
+            wallet.create_new_accounts(
+                NetworkID::Mainnet,
+                names(&["Carla", "Dave"])
+            ),
+            Err(CommonError::DerivationIndicesExhausted {
+                entity_kind: EntityKind::Accounts,
+                network_id: NetworkID::Mainnet,
+                factor_source_id: private.factor_source.id
+            })
+        );
+        let last = wallet
+            .create_new_accounts(NetworkID::Mainnet, names(&["Carla"]))
+            .unwrap();
+        assert_eq!(
+            derivation_index(&last[0]),
+            Profile::DERIVATION_INDEX_LIMIT - 1
+        );
+    }
+
+    #[test]
+    fn create_and_save_new_accounts_loads_mnemonic_and_saves_profile_once() {
+        let storage = Arc::new(InMemorySecureStorage::new());
+        let wallet = Wallet::by_importing_profile(
+            Profile::placeholder(),
+            storage.clone().as_secure_storage(),
+        );
+        let private =
+            PrivateHierarchicalDeterministicFactorSource::placeholder();
+        wallet
+            .wallet_client_storage
+            .save_mnemonic_with_passphrase(
+                &private.mnemonic_with_passphrase,
+                &private.factor_source.id,
+            )
+            .unwrap();
+        storage.clear_calls();
+
+        let accounts = wallet
+            .create_and_save_new_accounts(
+                NetworkID::Mainnet,
+                names(&["Carla", "Dave", "Erin", "Frank"]),
+            )
+            .unwrap();
+        assert_eq!(accounts.len(), 4);
+
+        let calls = storage.calls();
+        let mnemonic_loads = calls
+            .iter()
+            .filter(|c| {
+                matches!(c, SecureStorageCall::Load { key } if key.kind() == SecureStorageKeyKind::DeviceFactorSourceMnemonic)
+            })
+            .count();
+        let snapshot_saves = calls
+            .iter()
+            .filter(|c| {
+                matches!(c, SecureStorageCall::Save { key, .. } if key.kind() == SecureStorageKeyKind::ProfileSnapshot)
+            })
+            .count();
+        assert_eq!(mnemonic_loads, 1);
+        assert_eq!(snapshot_saves, 1);
+    }
+
+    fn transaction_signing(
+        account: &Account,
+    ) -> HierarchicalDeterministicFactorInstance {
+        let EntitySecurityState::Unsecured { value: control } =
+            &account.security_state;
+        control.transaction_signing.clone()
+    }
+
+    fn derivation_index(account: &Account) -> HDPathValue {
+        transaction_signing(account)
+            .derivation_path()
+            .last_component()
+            .index()
+    }
+
+    #[test]
+    fn create_new_account_with_secondary_device_factor_source() {
+        let (wallet, _) = Wallet::ephemeral(Profile::placeholder());
+        let private =
+            PrivateHierarchicalDeterministicFactorSource::generate_new(
+                WalletClientModel::Unknown,
+            );
+        wallet
+            .add_private_device_factor_source(private.clone())
+            .unwrap();
+        let account = wallet
+            .create_and_save_new_account_with_factor_source(
+                NetworkID::Mainnet,
+                DisplayName::new("Carla").unwrap(),
+                private.factor_source.factor_source_id(),
+                None,
+            )
+            .unwrap();
+        assert_eq!(
+            account,
+            Account::new(
+                private.derive_entity_creation_factor_instance(
+                    NetworkID::Mainnet,
+                    0
+                ),
+                DisplayName::new("Carla").unwrap(),
+                AppearanceID::new(2).unwrap(),
+            )
+        );
+        assert_eq!(wallet.profile().networks[0].accounts[2], account);
+    }
+
+    #[test]
+    fn create_new_accounts_with_ledger_factor_source() {
+        let (wallet, _) = Wallet::ephemeral(Profile::placeholder());
+        let ledger =
+            Arc::new(LedgerHardwareWalletSimulator::placeholder_other());
+        wallet
+            .add_factor_source(ledger.factor_source().into())
+            .unwrap();
+        let factor_source_id: FactorSourceID = ledger.id().into();
+        let create = |name: &str| {
+            wallet
+                .create_and_save_new_account_with_factor_source(
+                    NetworkID::Mainnet,
+                    DisplayName::new(name).unwrap(),
+                    factor_source_id.clone(),
+                    Some(ledger.clone()),
+                )
+                .unwrap()
+        };
+        let first = create("Treasury");
+        let second = create("Treasury 2");
+
+        assert_eq!(transaction_signing(&first).factor_source_id, ledger.id());
+        assert_eq!(derivation_index(&first), 0);
+        assert_eq!(derivation_index(&second), 1);
+        assert_eq!(second.appearance_id, AppearanceID::new(3).unwrap());
+        assert_eq!(wallet.profile().networks[0].accounts.len(), 4);
+    }
+
+    #[test]
+    fn create_new_account_with_ledger_factor_source_requires_driver() {
+        let (wallet, _) = Wallet::ephemeral(Profile::placeholder());
+        let ledger = LedgerHardwareWalletFactorSource::placeholder();
+        assert_eq!(
+            wallet.create_new_account_with_factor_source(
+                NetworkID::Mainnet,
+                DisplayName::new("Treasury").unwrap(),
+                ledger.factor_source_id(),
+                None,
+            ),
+            Err(CommonError::LedgerHardwareWalletDriverRequired(ledger.id))
+        );
+    }
+
+    #[test]
+    fn create_new_account_with_ledger_factor_source_wrong_device() {
+        let (wallet, _) = Wallet::ephemeral(Profile::placeholder());
+        let ledger = LedgerHardwareWalletFactorSource::placeholder();
+        assert_eq!(
+            wallet.create_new_account_with_factor_source(
+                NetworkID::Mainnet,
+                DisplayName::new("Treasury").unwrap(),
+                ledger.factor_source_id(),
+                Some(Arc::new(
+                    LedgerHardwareWalletSimulator::placeholder_other()
+                )),
+            ),
+            Err(CommonError::WrongLedgerHardwareWallet {
+                expected: ledger.id,
+                found: LedgerHardwareWalletSimulator::placeholder_other().id(),
+            })
+        );
+    }
+
+    #[test]
+    fn create_new_account_with_unknown_factor_source() {
+        let (wallet, _) = Wallet::ephemeral(Profile::placeholder());
+        let id = LedgerHardwareWalletFactorSource::placeholder_other()
+            .factor_source_id();
+        assert_eq!(
+            wallet.create_new_account_with_factor_source(
+                NetworkID::Mainnet,
+                DisplayName::new("Treasury").unwrap(),
+                id.clone(),
+                None,
+            ),
+            Err(CommonError::ProfileDoesNotContainFactorSourceWithID(id))
+        );
+    }
+
+    #[test]
+    fn create_new_account_with_olympia_factor_source() {
+        let (wallet, _) = Wallet::ephemeral(Profile::placeholder_other());
+        let id = DeviceFactorSource::placeholder_olympia().factor_source_id();
+        assert_eq!(
+            wallet.create_new_account_with_factor_source(
+                NetworkID::Mainnet,
+                DisplayName::new("Unsupported").unwrap(),
+                id.clone(),
+                None,
+            ),
+            Err(CommonError::FactorSourceDoesNotSupportBabylon(id))
+        );
+    }
 }
@@ -0,0 +1,42 @@
+use crate::prelude::*;
+
+//========
+// SET - AppPreferences
+//========
+#[uniffi::export]
+impl Wallet {
+    /// Updates the `AppPreferences` of the active Profile as a whole.
+    pub fn update_app_preferences(&self, to: AppPreferences) -> Result<()> {
+        self.update_profile_with(|p| p.app_preferences = to)?;
+
+        self.notify_observers(WalletEvent::AppPreferencesChanged);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn update_app_preferences() {
+        let (wallet, storage) = Wallet::ephemeral(Profile::placeholder());
+        assert_ne!(
+            wallet.profile().app_preferences,
+            AppPreferences::placeholder_other()
+        );
+        wallet
+            .update_app_preferences(AppPreferences::placeholder_other())
+            .unwrap();
+        let saved: Profile = serde_json::from_slice(
+            &storage
+                .load_data(SecureStorageKey::ProfileSnapshot {
+                    profile_id: wallet.profile().id(),
+                })
+                .unwrap()
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(saved.app_preferences, AppPreferences::placeholder_other());
+    }
+}
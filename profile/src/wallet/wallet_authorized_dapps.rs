@@ -0,0 +1,78 @@
+use crate::prelude::*;
+
+//========
+// GET - AuthorizedDapp
+//========
+#[uniffi::export]
+impl Wallet {
+    /// Resolves every Persona, Account and PersonaData entry referenced by
+    /// `dapp` using the active Profile, references which no longer resolve
+    /// are flagged, see `AuthorizedDappDetailed`.
+    pub fn authorized_dapp_detailed(
+        &self,
+        dapp: AuthorizedDapp,
+    ) -> AuthorizedDappDetailed {
+        AuthorizedDappDetailed::resolving(&dapp, &self.profile().networks)
+    }
+}
+
+impl Wallet {
+    /// Notifies observers about every AuthorizedDapp changed by `cascades`,
+    /// once per Dapp.
+    pub(crate) fn notify_authorized_dapp_cascades(
+        &self,
+        cascades: &[AuthorizedDappCascade],
+    ) {
+        let removed = cascades
+            .iter()
+            .filter(|c| c.is_authorized_dapp_removed())
+            .map(|c| c.dapp_definition_address().clone())
+            .collect::<HashSet<_>>();
+
+        for address in cascades
+            .iter()
+            .map(|c| c.dapp_definition_address().clone())
+            .unique()
+        {
+            let event = if removed.contains(&address) {
+                WalletEvent::AuthorizedDappRemoved { address }
+            } else {
+                WalletEvent::AuthorizedDappUpdated { address }
+            };
+            self.notify_observers(event);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn authorized_dapp_detailed_uses_active_profile() {
+        let (wallet, _) = Wallet::ephemeral(Profile::placeholder());
+        let dapp = AuthorizedDapp::placeholder_mainnet_dashboard();
+        assert_eq!(
+            wallet.authorized_dapp_detailed(dapp.clone()),
+            AuthorizedDappDetailed::resolving(
+                &dapp,
+                &wallet.profile().networks
+            )
+        );
+
+        let satoshi = Persona::placeholder_mainnet_satoshi().address;
+        wallet
+            .update_profile_with(|p| {
+                p.networks.update_with(&NetworkID::Mainnet, |n| {
+                    _ = n.personas.remove_by_id(&satoshi)
+                })
+            })
+            .unwrap();
+        assert_eq!(
+            wallet
+                .authorized_dapp_detailed(dapp)
+                .unresolved_persona_addresses,
+            vec![satoshi]
+        );
+    }
+}
@@ -0,0 +1,299 @@
+use crate::prelude::*;
+
+//========
+// Remove FactorSources
+//========
+#[uniffi::export]
+impl Wallet {
+    /// Removes the FactorSource with `id` from Profile and updates Profile in
+    /// SecureStorage. If it is a `DeviceFactorSource` its mnemonic is deleted
+    /// from SecureStorage, unless another stored Profile references it.
+    ///
+    /// Returns `Err` if any Account or Persona, on any network, is controlled
+    /// by the FactorSource, or if it is the only Babylon `DeviceFactorSource`,
+    /// in which cases neither Profile nor SecureStorage are changed.
+    pub fn remove_factor_source(&self, id: FactorSourceID) -> Result<()> {
+        // Read everything fallible before Profile is changed, so that an
+        // `Err` always means that nothing was removed.
+        let is_device = self
+            .profile()
+            .factor_sources
+            .get(&id)
+            .is_some_and(|f| f.as_device().is_some());
+        let referenced_elsewhere = if is_device {
+            let active_profile_id = self.profile().id();
+            self.device_factor_source_ids_of_stored_profiles_except(&[
+                active_profile_id,
+            ])?
+        } else {
+            None
+        };
+
+        let removed =
+            self.try_update_profile_with(|p| p.remove_factor_source(&id))?;
+        debug!("Removed FactorSource: {}", &removed);
+
+        if let Some(device) = removed.as_device() {
+            match referenced_elsewhere {
+                Some(ids) if !ids.contains(&device.id) => {
+                    if let Err(e) =
+                        self.wallet_client_storage.delete_mnemonic(&device.id)
+                    {
+                        error!(
+                            "Failed to delete mnemonic of removed FactorSource: {}, error: {}",
+                            &device.id, e
+                        );
+                    }
+                }
+                Some(_) => {}
+                None => warn!(
+                    "Kept mnemonic of removed FactorSource: {}, since the headers list might not contain every stored Profile",
+                    &device.id
+                ),
+            }
+        }
+
+        self.notify_observers(WalletEvent::FactorSourceRemoved { id });
+        Ok(())
+    }
+
+    /// Flags the FactorSource with `id` as `DeletedByUser` and updates Profile
+    /// in SecureStorage, keeping both the FactorSource and - for a
+    /// `DeviceFactorSource` - its mnemonic.
+    ///
+    /// Returns `Err` under the same conditions as `remove_factor_source`.
+    pub fn soft_delete_factor_source(&self, id: FactorSourceID) -> Result<()> {
+        self.try_update_profile_with(|p| p.soft_delete_factor_source(&id))?;
+        debug!("Flagged FactorSource: {} as deleted by user", &id);
+        self.notify_observers(WalletEvent::FactorSourceUpdated { id });
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use std::sync::atomic::{AtomicBool, Ordering as AtomicOrdering};
+
+    /// A Wallet with a Profile without entities, with the BDFS from
+    /// `PrivateHierarchicalDeterministicFactorSource::placeholder()` and
+    /// another Babylon `DeviceFactorSource`, both having their mnemonics
+    /// saved in SecureStorage.
+    fn wallet_with_two_babylon_device_factor_sources() -> (
+        Wallet,
+        Arc<EphemeralSecureStorage>,
+        FactorSourceIDFromHash,
+        FactorSourceIDFromHash,
+    ) {
+        let main = PrivateHierarchicalDeterministicFactorSource::placeholder();
+        let (wallet, storage) =
+            Wallet::ephemeral(Profile::new(main.clone(), "Test"));
+        wallet
+            .wallet_client_storage
+            .save_mnemonic_with_passphrase(
+                &main.mnemonic_with_passphrase,
+                &main.factor_source.id,
+            )
+            .unwrap();
+        let other = PrivateHierarchicalDeterministicFactorSource::generate_new(
+            WalletClientModel::Unknown,
+        );
+        wallet
+            .add_private_device_factor_source(other.clone())
+            .unwrap();
+        (
+            wallet,
+            storage,
+            main.factor_source.id,
+            other.factor_source.id,
+        )
+    }
+
+    fn has_mnemonic(
+        storage: &EphemeralSecureStorage,
+        id: &FactorSourceIDFromHash,
+    ) -> bool {
+        storage.storage.read().unwrap().contains_key(
+            &SecureStorageKey::DeviceFactorSourceMnemonic {
+                factor_source_id: id.clone(),
+            },
+        )
+    }
+
+    #[test]
+    fn remove_device_factor_source_deletes_mnemonic() {
+        let (wallet, storage, main, other) =
+            wallet_with_two_babylon_device_factor_sources();
+        assert!(has_mnemonic(&storage, &main));
+
+        wallet.remove_factor_source(main.clone().into()).unwrap();
+
+        assert!(!has_mnemonic(&storage, &main));
+        assert!(has_mnemonic(&storage, &other));
+        let profile = wallet.profile();
+        assert!(!profile.factor_sources.contains_id(&main.into()));
+        assert_eq!(profile.bdfs().id, other);
+        assert!(profile.bdfs().is_main_bdfs());
+    }
+
+    #[test]
+    fn remove_keeps_mnemonic_referenced_by_other_stored_profile() {
+        let (wallet, storage, main, _) =
+            wallet_with_two_babylon_device_factor_sources();
+        let private =
+            PrivateHierarchicalDeterministicFactorSource::placeholder();
+        let other_profile = Profile::new(private, "Other");
+        wallet.save_profile(&other_profile).unwrap();
+
+        wallet.remove_factor_source(main.clone().into()).unwrap();
+
+        assert!(has_mnemonic(&storage, &main));
+    }
+
+    #[derive(Debug)]
+    struct FlakyStorage {
+        inner: Arc<EphemeralSecureStorage>,
+        fail_loading_headers_list: AtomicBool,
+        fail_deleting_mnemonics: AtomicBool,
+    }
+
+    impl SecureStorage for FlakyStorage {
+        fn load_data(&self, key: SecureStorageKey) -> Result<Option<Vec<u8>>> {
+            if key == SecureStorageKey::SnapshotHeadersList
+                && self.fail_loading_headers_list.load(AtomicOrdering::SeqCst)
+            {
+                return Err(CommonError::Unknown);
+            }
+            self.inner.load_data(key)
+        }
+
+        fn save_data(
+            &self,
+            key: SecureStorageKey,
+            data: Vec<u8>,
+        ) -> Result<()> {
+            self.inner.save_data(key, data)
+        }
+
+        fn delete_data_for_key(&self, key: SecureStorageKey) -> Result<()> {
+            if key.kind() == SecureStorageKeyKind::DeviceFactorSourceMnemonic
+                && self.fail_deleting_mnemonics.load(AtomicOrdering::SeqCst)
+            {
+                return Err(CommonError::Unknown);
+            }
+            self.inner.delete_data_for_key(key)
+        }
+    }
+
+    fn flaky_wallet() -> (Wallet, Arc<FlakyStorage>, FactorSourceIDFromHash) {
+        let main = PrivateHierarchicalDeterministicFactorSource::placeholder();
+        let storage = Arc::new(FlakyStorage {
+            inner: EphemeralSecureStorage::new(),
+            fail_loading_headers_list: AtomicBool::new(false),
+            fail_deleting_mnemonics: AtomicBool::new(false),
+        });
+        let wallet = Wallet::by_importing_profile(
+            Profile::new(main, "Test"),
+            storage.clone(),
+        );
+        let other = PrivateHierarchicalDeterministicFactorSource::generate_new(
+            WalletClientModel::Unknown,
+        );
+        wallet
+            .add_private_device_factor_source(other.clone())
+            .unwrap();
+        (wallet, storage, other.factor_source.id)
+    }
+
+    #[test]
+    fn remove_succeeds_when_deleting_mnemonic_fails() {
+        let (wallet, storage, other) = flaky_wallet();
+        storage
+            .fail_deleting_mnemonics
+            .store(true, AtomicOrdering::SeqCst);
+
+        assert_eq!(wallet.remove_factor_source(other.clone().into()), Ok(()));
+
+        assert!(!wallet.profile().factor_sources.contains_id(&other.into()));
+    }
+
+    #[test]
+    fn remove_fails_without_changes_when_reading_storage_fails() {
+        let (wallet, storage, other) = flaky_wallet();
+        let before = wallet.profile();
+        storage
+            .fail_loading_headers_list
+            .store(true, AtomicOrdering::SeqCst);
+
+        assert!(wallet.remove_factor_source(other.clone().into()).is_err());
+
+        assert_eq!(wallet.profile(), before);
+        assert!(has_mnemonic(&storage.inner, &other));
+    }
+
+    #[test]
+    fn remove_keeps_mnemonic_if_headers_list_is_incomplete() {
+        let (wallet, storage, main, _) =
+            wallet_with_two_babylon_device_factor_sources();
+        storage
+            .delete_data_for_key(
+                SecureStorageKey::SnapshotHeadersListIsComplete,
+            )
+            .unwrap();
+
+        wallet.remove_factor_source(main.clone().into()).unwrap();
+
+        assert!(has_mnemonic(&storage, &main));
+    }
+
+    #[test]
+    fn remove_factor_source_controlling_entities_fails() {
+        let (wallet, _) = Wallet::ephemeral(Profile::placeholder());
+        let id = FactorSource::placeholder_device().factor_source_id();
+        assert_eq!(
+            wallet.remove_factor_source(id.clone()),
+            Err(CommonError::UnableToRemoveFactorSourceControllingEntities(
+                id
+            ))
+        );
+        assert_eq!(wallet.profile(), Profile::placeholder());
+    }
+
+    #[test]
+    fn remove_unknown_factor_source_fails() {
+        let (wallet, _) = Wallet::ephemeral(Profile::placeholder());
+        let id = FactorSource::placeholder_device_olympia().factor_source_id();
+        assert_eq!(
+            wallet.remove_factor_source(id.clone()),
+            Err(CommonError::ProfileDoesNotContainFactorSourceWithID(id))
+        );
+    }
+
+    #[test]
+    fn remove_ledger() {
+        let (wallet, _) = Wallet::ephemeral(Profile::placeholder());
+        let id = FactorSource::placeholder_ledger().factor_source_id();
+        wallet.remove_factor_source(id.clone()).unwrap();
+        assert!(!wallet.profile().factor_sources.contains_id(&id));
+    }
+
+    #[test]
+    fn soft_delete_keeps_factor_source_and_mnemonic() {
+        let (wallet, storage, main, _) =
+            wallet_with_two_babylon_device_factor_sources();
+
+        wallet
+            .soft_delete_factor_source(main.clone().into())
+            .unwrap();
+
+        assert!(has_mnemonic(&storage, &main));
+        assert!(wallet
+            .profile()
+            .factor_sources
+            .get(&main.into())
+            .unwrap()
+            .common_properties()
+            .flags
+            .contains_id(&FactorSourceFlag::DeletedByUser));
+    }
+}
@@ -0,0 +1,142 @@
+use crate::prelude::*;
+
+//========
+// Mnemonic Backup
+//========
+#[uniffi::export]
+impl Wallet {
+    /// Creates a new `MnemonicBackupQuiz` about the mnemonic of the
+    /// `DeviceFactorSource` with `factor_source_id`, asking for the words at
+    /// some random positions.
+    ///
+    /// Returns `Err` if Profile does not contain a `DeviceFactorSource` with
+    /// `factor_source_id` or if its mnemonic cannot be loaded from
+    /// SecureStorage.
+    pub fn create_mnemonic_backup_quiz(
+        &self,
+        factor_source_id: FactorSourceIDFromHash,
+    ) -> Result<MnemonicBackupQuiz> {
+        let private =
+            self.load_private_device_factor_source_by_id(&factor_source_id)?;
+        Ok(MnemonicBackupQuiz::new(
+            factor_source_id,
+            &private.mnemonic_with_passphrase.mnemonic,
+        ))
+    }
+
+    /// Checks `answers` - the words at the positions of `quiz`, in the same
+    /// order - against the mnemonic stored in SecureStorage, and if enough
+    /// of them are correct marks the `DeviceFactorSource` as backed up and
+    /// updates Profile in SecureStorage.
+    ///
+    /// Returns `Ok(true)` if the quiz was passed, and `Ok(false)` if it was
+    /// not, in which case Profile is left unchanged.
+    pub fn answer_mnemonic_backup_quiz(
+        &self,
+        quiz: MnemonicBackupQuiz,
+        answers: Vec<String>,
+    ) -> Result<bool> {
+        let id = quiz.factor_source_id.clone();
+        let private = self.load_private_device_factor_source_by_id(&id)?;
+        if !quiz.is_passed(&private.mnemonic_with_passphrase.mnemonic, &answers)
+        {
+            info!("Mnemonic backup quiz failed for FactorSource: {}", id);
+            return Ok(false);
+        }
+
+        self.try_update_profile_with(|p| {
+            p.update_factor_source(
+                &id.clone().into(),
+                |mut d: DeviceFactorSource| {
+                    d.mnemonic_backed_up_on = Some(now());
+                    Ok(d)
+                },
+            )
+        })?;
+        info!("Mnemonic of FactorSource: {} marked as backed up", id);
+
+        self.notify_observers(WalletEvent::FactorSourceUpdated {
+            id: id.into(),
+        });
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    fn wallet() -> (Wallet, MnemonicBackupQuiz) {
+        let private =
+            PrivateHierarchicalDeterministicFactorSource::placeholder();
+        let (wallet, _) = Wallet::ephemeral_with_bdfs_mnemonic();
+        let quiz = MnemonicBackupQuiz::with_word_indices(
+            private.factor_source.id,
+            [0, 5, 11, 23],
+        );
+        (wallet, quiz)
+    }
+
+    fn answers(words: &[&str]) -> Vec<String> {
+        words.iter().map(|w| w.to_string()).collect()
+    }
+
+    fn is_backed_up(wallet: &Wallet) -> bool {
+        wallet
+            .profile()
+            .device_factor_source_by_id(&FactorSourceIDFromHash::placeholder())
+            .unwrap()
+            .is_mnemonic_backed_up()
+    }
+
+    #[test]
+    fn create_quiz() {
+        let (wallet, _) = wallet();
+        let quiz = wallet
+            .create_mnemonic_backup_quiz(FactorSourceIDFromHash::placeholder())
+            .unwrap();
+        assert_eq!(
+            quiz.factor_source_id,
+            FactorSourceIDFromHash::placeholder()
+        );
+        assert_eq!(quiz.word_indices.len(), MnemonicBackupQuiz::QUESTION_COUNT);
+    }
+
+    #[test]
+    fn create_quiz_fails_without_mnemonic() {
+        let (wallet, _) = Wallet::ephemeral(Profile::placeholder());
+        let id = FactorSourceIDFromHash::placeholder();
+        assert_eq!(
+            wallet.create_mnemonic_backup_quiz(id.clone()),
+            Err(CommonError::UnableToLoadMnemonicFromSecureStorage(id))
+        );
+    }
+
+    #[test]
+    fn passed_quiz_marks_backed_up() {
+        let (wallet, quiz) = wallet();
+        assert!(!is_backed_up(&wallet));
+        assert_eq!(
+            wallet.answer_mnemonic_backup_quiz(
+                quiz,
+                answers(&["bright", "pull", "woman", "mandate"])
+            ),
+            Ok(true)
+        );
+        assert!(is_backed_up(&wallet));
+    }
+
+    #[test]
+    fn failed_quiz_leaves_profile_unchanged() {
+        let (wallet, quiz) = wallet();
+        let before = wallet.profile();
+        assert_eq!(
+            wallet.answer_mnemonic_backup_quiz(
+                quiz,
+                answers(&["bright", "pull", "woman", "zoo"])
+            ),
+            Ok(false)
+        );
+        assert_eq!(wallet.profile(), before);
+    }
+}
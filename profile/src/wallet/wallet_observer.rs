@@ -0,0 +1,386 @@
+use crate::prelude::*;
+
+/// A typed description of a change made to the active Profile of a `Wallet`,
+/// sent to every registered `WalletObserver` after the change has been
+/// successfully committed.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, uniffi::Enum)]
+pub enum WalletEvent {
+    /// The Profile was saved to SecureStorage.
+    ProfileSaved { profile_id: ProfileID },
+
+    /// The active Profile was replaced by the stored Profile with `profile_id`.
+    ProfileSwitched { profile_id: ProfileID },
+
+    /// A new Account was added to the Profile.
+    AccountAdded { address: AccountAddress },
+
+    /// An Account was updated as a whole.
+    AccountUpdated { address: AccountAddress },
+
+    /// The display name of an Account was changed.
+    AccountRenamed {
+        address: AccountAddress,
+        display_name: DisplayName,
+    },
+
+    /// A new Persona was added to the Profile.
+    PersonaAdded { address: IdentityAddress },
+
+    /// A Persona was updated, e.g. an entry of its PersonaData was edited.
+    PersonaUpdated { address: IdentityAddress },
+
+    /// A Persona was flagged as deleted by the user.
+    PersonaDeleted { address: IdentityAddress },
+
+    /// An Account was flagged as deleted by the user.
+    AccountDeleted { address: AccountAddress },
+
+    /// The references of an AuthorizedDapp were changed, since a Persona,
+    /// Account or PersonaData entry it referenced was deleted.
+    AuthorizedDappUpdated { address: DappDefinitionAddress },
+
+    /// An AuthorizedDapp was removed, since it had no authorized Personas
+    /// left.
+    AuthorizedDappRemoved { address: DappDefinitionAddress },
+
+    /// A new FactorSource was added to the Profile.
+    FactorSourceAdded { id: FactorSourceID },
+
+    /// A FactorSource was removed from the Profile.
+    FactorSourceRemoved { id: FactorSourceID },
+
+    /// A FactorSource in the Profile was updated, e.g. flagged as deleted by
+    /// the user.
+    FactorSourceUpdated { id: FactorSourceID },
+
+    /// The AppPreferences of the Profile were changed.
+    AppPreferencesChanged,
+}
+
+/// An observer of changes made to the Profile of a `Wallet`, implemented by
+/// Wallet Clients, e.g. to update UI without polling `wallet.profile()`.
+#[uniffi::export]
+pub trait WalletObserver: Send + Sync + std::fmt::Debug {
+    /// Called after a change to the Profile has been committed, never while
+    /// the Wallet holds a lock on the Profile, so it is safe to read the
+    /// Profile from within this method.
+    fn handle_event(&self, event: WalletEvent);
+}
+
+//========
+// Observers
+//========
+#[uniffi::export]
+impl Wallet {
+    /// Registers `observer` which will be notified about every committed
+    /// change to the Profile of this Wallet.
+    pub fn register_observer(&self, observer: Arc<dyn WalletObserver>) {
+        self.observers
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(observer);
+    }
+
+    /// Unregisters all observers, they will no longer be notified.
+    pub fn unregister_observers(&self) {
+        self.observers
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .clear();
+    }
+}
+
+impl Wallet {
+    /// Sends `event` to all registered observers. The list of observers is
+    /// copied before any of them is called, so that observers can register
+    /// or unregister observers from within `handle_event`.
+    ///
+    /// A poisoned lock - from a panic while it was held - is recovered
+    /// from, since the list of observers is never left half updated.
+    pub(crate) fn notify_observers(&self, event: WalletEvent) {
+        let observers = self
+            .observers
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .to_vec();
+        trace!(
+            "Notifying #{} observers about event: {:?}",
+            observers.len(),
+            event
+        );
+        for observer in observers {
+            observer.handle_event(event.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use std::sync::RwLock;
+
+    #[derive(Debug, Default)]
+    struct RecordingObserver {
+        events: RwLock<Vec<WalletEvent>>,
+    }
+
+    impl WalletObserver for RecordingObserver {
+        fn handle_event(&self, event: WalletEvent) {
+            self.events.write().unwrap().push(event);
+        }
+    }
+
+    impl RecordingObserver {
+        fn events(&self) -> Vec<WalletEvent> {
+            self.events.read().unwrap().clone()
+        }
+    }
+
+    fn observed_wallet() -> (Wallet, Arc<RecordingObserver>) {
+        let (wallet, _) = Wallet::ephemeral_with_bdfs_mnemonic();
+        let observer = Arc::new(RecordingObserver::default());
+        wallet.register_observer(observer.clone());
+        (wallet, observer)
+    }
+
+    #[test]
+    fn account_renamed() {
+        let (wallet, observer) = observed_wallet();
+        let address = wallet
+            .access_profile_with(|p| p.networks[0].accounts[0].address.clone());
+        let name = DisplayName::new("Stella").unwrap();
+        wallet
+            .change_name_of_account(address.clone(), name.clone())
+            .unwrap();
+        assert_eq!(
+            observer.events(),
+            vec![
+                WalletEvent::ProfileSaved {
+                    profile_id: wallet.profile().id()
+                },
+                WalletEvent::AccountRenamed {
+                    address,
+                    display_name: name
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn account_updated() {
+        let (wallet, observer) = observed_wallet();
+        let mut account =
+            wallet.access_profile_with(|p| p.networks[0].accounts[0].clone());
+        account.appearance_id = AppearanceID::new(7).unwrap();
+        wallet.update_account(account.clone()).unwrap();
+        assert_eq!(
+            observer.events().last().cloned(),
+            Some(WalletEvent::AccountUpdated {
+                address: account.address
+            })
+        );
+    }
+
+    #[test]
+    fn account_added() {
+        let (wallet, observer) = observed_wallet();
+        let account = Account::placeholder_mainnet_carol();
+        wallet.add_account(account.clone()).unwrap();
+        assert_eq!(
+            observer.events().last().cloned(),
+            Some(WalletEvent::AccountAdded {
+                address: account.address
+            })
+        );
+    }
+
+    #[test]
+    fn accounts_added_in_batch_saves_profile_once() {
+        let (wallet, observer) = observed_wallet();
+        let accounts = wallet
+            .create_and_save_new_accounts(
+                NetworkID::Mainnet,
+                vec![
+                    DisplayName::new("Carla").unwrap(),
+                    DisplayName::new("Dave").unwrap(),
+                ],
+            )
+            .unwrap();
+        assert_eq!(
+            observer.events(),
+            vec![
+                WalletEvent::ProfileSaved {
+                    profile_id: wallet.profile().id()
+                },
+                WalletEvent::AccountAdded {
+                    address: accounts[0].address.clone()
+                },
+                WalletEvent::AccountAdded {
+                    address: accounts[1].address.clone()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn factor_source_added() {
+        let (wallet, observer) = observed_wallet();
+        let new = PrivateHierarchicalDeterministicFactorSource::generate_new(
+            WalletClientModel::Unknown,
+        );
+        wallet
+            .add_private_device_factor_source(new.clone())
+            .unwrap();
+        assert_eq!(
+            observer.events().last().cloned(),
+            Some(WalletEvent::FactorSourceAdded {
+                id: new.factor_source.factor_source_id()
+            })
+        );
+    }
+
+    #[test]
+    fn profile_switched() {
+        let storage = EphemeralSecureStorage::new();
+        let other = Profile::placeholder_other();
+        _ = Wallet::by_importing_profile(other.clone(), storage.clone());
+        let wallet =
+            Wallet::by_importing_profile(Profile::placeholder(), storage);
+        let observer = Arc::new(RecordingObserver::default());
+        wallet.register_observer(observer.clone());
+
+        wallet.switch_profile(other.id()).unwrap();
+
+        assert_eq!(
+            observer.events(),
+            vec![WalletEvent::ProfileSwitched {
+                profile_id: other.id()
+            }]
+        );
+    }
+
+    #[test]
+    fn app_preferences_changed() {
+        let (wallet, observer) = observed_wallet();
+        wallet
+            .update_app_preferences(AppPreferences::placeholder_other())
+            .unwrap();
+        assert_eq!(
+            wallet.profile().app_preferences,
+            AppPreferences::placeholder_other()
+        );
+        assert_eq!(
+            observer.events().last().cloned(),
+            Some(WalletEvent::AppPreferencesChanged)
+        );
+    }
+
+    #[test]
+    fn persona_deleted_and_authorized_dapps_cascaded() {
+        let (wallet, observer) = observed_wallet();
+        let address = Persona::placeholder_mainnet_batman().address;
+        wallet.delete_persona(address.clone()).unwrap();
+        let dashboard = AuthorizedDapp::placeholder_mainnet_dashboard();
+        let gumball = AuthorizedDapp::placeholder_mainnet_gumballclub();
+        assert_eq!(
+            observer.events()[1..],
+            [
+                WalletEvent::PersonaDeleted { address },
+                WalletEvent::AuthorizedDappUpdated {
+                    address: dashboard.dapp_definition_address
+                },
+                WalletEvent::AuthorizedDappRemoved {
+                    address: gumball.dapp_definition_address
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn no_event_on_failure() {
+        let (wallet, observer) = observed_wallet();
+        let existing =
+            PrivateHierarchicalDeterministicFactorSource::placeholder();
+        assert!(wallet
+            .add_factor_source(existing.factor_source.into())
+            .is_err());
+        assert!(observer.events().is_empty());
+    }
+
+    #[test]
+    fn unregister_observers() {
+        let (wallet, observer) = observed_wallet();
+        wallet.unregister_observers();
+        wallet
+            .update_app_preferences(AppPreferences::placeholder_other())
+            .unwrap();
+        assert!(observer.events().is_empty());
+    }
+
+    #[test]
+    fn observers_notified_after_lock_poisoned() {
+        let (wallet, observer) = observed_wallet();
+        let wallet = Arc::new(wallet);
+        let poisoning = wallet.clone();
+        assert!(std::thread::spawn(move || {
+            let _guard = poisoning.observers.write().unwrap();
+            panic!("Poisoning the lock of the observers");
+        })
+        .join()
+        .is_err());
+        assert!(wallet.observers.is_poisoned());
+
+        let other = Arc::new(RecordingObserver::default());
+        wallet.register_observer(other.clone());
+        wallet
+            .update_app_preferences(AppPreferences::placeholder_other())
+            .unwrap();
+        assert_eq!(
+            observer.events().last().cloned(),
+            Some(WalletEvent::AppPreferencesChanged)
+        );
+        assert_eq!(observer.events(), other.events());
+
+        wallet.unregister_observers();
+        assert!(wallet
+            .observers
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .is_empty());
+    }
+
+    #[test]
+    fn observer_can_read_profile_from_handle_event() {
+        #[derive(Debug)]
+        struct ReadingObserver {
+            wallet: Arc<Wallet>,
+            names: RwLock<Vec<String>>,
+        }
+        impl WalletObserver for ReadingObserver {
+            fn handle_event(&self, _event: WalletEvent) {
+                let name = self.wallet.access_profile_with(|p| {
+                    p.networks[0].accounts[0].display_name.value.clone()
+                });
+                self.names.write().unwrap().push(name);
+            }
+        }
+        let wallet = Arc::new(Wallet::placeholder());
+        let observer = Arc::new(ReadingObserver {
+            wallet: wallet.clone(),
+            names: RwLock::new(Vec::new()),
+        });
+        wallet.register_observer(observer.clone());
+        let address = wallet
+            .access_profile_with(|p| p.networks[0].accounts[0].address.clone());
+        wallet
+            .change_name_of_account(
+                address,
+                DisplayName::new("Stella").unwrap(),
+            )
+            .unwrap();
+        assert_eq!(
+            *observer.names.read().unwrap(),
+            vec!["Stella".to_owned(), "Stella".to_owned()]
+        );
+    }
+}
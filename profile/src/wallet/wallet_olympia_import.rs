@@ -0,0 +1,411 @@
+use crate::prelude::*;
+
+//========
+// Import Olympia Accounts
+//========
+#[uniffi::export]
+impl Wallet {
+    /// Imports the `Software` accounts of `export` onto Mainnet, controlled
+    /// by the Olympia `DeviceFactorSource` of `mnemonic_with_passphrase`.
+    ///
+    /// If Profile does not contain a `DeviceFactorSource` for the mnemonic,
+    /// one with Olympia crypto parameters is added and the mnemonic is saved
+    /// to SecureStorage, if it does, Olympia crypto parameters are added to
+    /// it, if missing.
+    ///
+    /// Returns `Err` if any account is not derived from the mnemonic, or if
+    /// any account has already been imported, in which case neither Profile
+    /// nor SecureStorage are changed, a mnemonic already saved in
+    /// SecureStorage - e.g. by another Profile - is never deleted.
+    pub fn import_olympia_software_accounts(
+        &self,
+        export: OlympiaWalletExport,
+        mnemonic_with_passphrase: MnemonicWithPassphrase,
+    ) -> Result<Vec<Account>> {
+        let factor_source_id = FactorSourceIDFromHash::new_for_device(
+            mnemonic_with_passphrase.clone(),
+        );
+        let exported = export
+            .accounts
+            .into_iter()
+            .filter(|a| a.account_type == OlympiaAccountType::Software)
+            .collect_vec();
+        if let Some(not_derived) = exported.iter().find(|a| {
+            mnemonic_with_passphrase
+                .derive_private_key(a.derivation_path.clone())
+                .public_key()
+                .public_key
+                != a.public_key.clone().into()
+        }) {
+            return Err(CommonError::OlympiaAccountNotDerivedFromMnemonic(
+                not_derived.address(NetworkID::Mainnet),
+            ));
+        }
+
+        let is_new_factor_source = !self
+            .profile()
+            .factor_sources
+            .contains_id(&factor_source_id.clone().into());
+        // SecureStorage is shared by all stored Profiles, so the mnemonic
+        // might already be saved, in which case it must never be deleted.
+        let is_new_mnemonic = is_new_factor_source
+            && !self
+                .wallet_client_storage
+                .contains_mnemonic(&factor_source_id)?;
+        if is_new_mnemonic {
+            self.wallet_client_storage.save_mnemonic_with_passphrase(
+                &mnemonic_with_passphrase,
+                &factor_source_id,
+            )?;
+        }
+
+        let accounts = self
+            .try_update_profile_with(|p| {
+                if is_new_factor_source {
+                    let olympia = DeviceFactorSource::new(
+                        factor_source_id.clone(),
+                        FactorSourceCommon::new(
+                            FactorSourceCryptoParameters::olympia(),
+                            [],
+                        ),
+                        DeviceFactorSourceHint::unknown_model_of_client(
+                            mnemonic_with_passphrase.mnemonic.word_count,
+                            WalletClientModel::Unknown,
+                        ),
+                    );
+                    p.factor_sources.append(olympia.into());
+                } else {
+                    p.update_factor_source(
+                        &factor_source_id.clone().into(),
+                        |mut d: DeviceFactorSource| {
+                            d.common.crypto_parameters =
+                                d.common.crypto_parameters.with_olympia();
+                            Ok(d)
+                        },
+                    )?;
+                }
+                p.add_olympia_accounts(&exported, &factor_source_id)
+            })
+            .map_err(|e| {
+                if is_new_mnemonic {
+                    _ = self
+                        .wallet_client_storage
+                        .delete_mnemonic(&factor_source_id);
+                }
+                e
+            })?;
+
+        let id: FactorSourceID = factor_source_id.into();
+        self.notify_observers(if is_new_factor_source {
+            WalletEvent::FactorSourceAdded { id }
+        } else {
+            WalletEvent::FactorSourceUpdated { id }
+        });
+        self.notify_accounts_added(&accounts);
+        Ok(accounts)
+    }
+
+    /// Imports the `Hardware` accounts of `export` onto Mainnet, controlled
+    /// by the `LedgerHardwareWalletFactorSource` with `ledger_id`, which
+    /// must already have been added to Profile.
+    ///
+    /// Returns `Err` if Profile does not contain a Ledger with `ledger_id`,
+    /// or if any account has already been imported, in which case Profile is
+    /// not changed.
+    pub fn import_olympia_hardware_accounts(
+        &self,
+        export: OlympiaWalletExport,
+        ledger_id: FactorSourceIDFromHash,
+    ) -> Result<Vec<Account>> {
+        let factor_source_id: FactorSourceID = ledger_id.clone().into();
+        self.profile()
+            .factor_sources
+            .get(&factor_source_id)
+            .ok_or(CommonError::ProfileDoesNotContainFactorSourceWithID(
+                factor_source_id,
+            ))?
+            .as_ledger()
+            .ok_or(
+                CommonError::ExpectedLedgerHardwareWalletFactorSourceGotSomethingElse,
+            )?;
+
+        let exported = export
+            .accounts
+            .into_iter()
+            .filter(|a| a.account_type == OlympiaAccountType::Hardware)
+            .collect_vec();
+        let accounts = self.try_update_profile_with(|p| {
+            p.add_olympia_accounts(&exported, &ledger_id)
+        })?;
+
+        self.notify_accounts_added(&accounts);
+        Ok(accounts)
+    }
+}
+
+impl Profile {
+    /// Adds the `exported` Olympia accounts to Mainnet, controlled by the
+    /// FactorSource with `factor_source_id`, returning the added accounts.
+    fn add_olympia_accounts(
+        &mut self,
+        exported: &[OlympiaAccountExport],
+        factor_source_id: &FactorSourceIDFromHash,
+    ) -> Result<Vec<Account>> {
+        let network_id = NetworkID::Mainnet;
+        let number_of_accounts_on_network = self
+            .networks
+            .get(&network_id)
+            .map(|n| n.accounts.len())
+            .unwrap_or(0);
+        let accounts = exported
+            .iter()
+            .enumerate()
+            .map(|(i, a)| {
+                a.to_account(
+                    network_id,
+                    factor_source_id.clone(),
+                    AppearanceID::from_number_of_accounts_on_network(
+                        number_of_accounts_on_network + i,
+                    ),
+                )
+            })
+            .collect_vec();
+        self.add_accounts(accounts.clone())?;
+        Ok(accounts)
+    }
+}
+
+impl FactorSourceCryptoParameters {
+    /// These parameters extended with support for Olympia, i.e. the curve
+    /// `secp256k1` and the `Bip44Olympia` derivation path scheme.
+    pub fn with_olympia(&self) -> Self {
+        let mut parameters = self.clone();
+        parameters.supported_curves.append(SLIP10Curve::Secp256k1);
+        parameters
+            .supported_derivation_path_schemes
+            .append(DerivationPathScheme::Bip44Olympia);
+        parameters
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+
+    fn mnemonic_with_passphrase() -> MnemonicWithPassphrase {
+        MnemonicWithPassphrase::placeholder_other()
+    }
+
+    /// An export with a software account derived from
+    /// `mnemonic_with_passphrase()` at `index` and a hardware account.
+    fn export(index: HDPathValue) -> OlympiaWalletExport {
+        let public_key = mnemonic_with_passphrase()
+            .derive_private_key(BIP44LikePath::new(index))
+            .public_key()
+            .public_key
+            .into_secp256k1()
+            .unwrap();
+        let hardware = Secp256k1PublicKey::placeholder_bob();
+        OlympiaWalletExport::from_payloads([format!(
+            "1^0^12]S^{}^{}^Software}}~H^{}^0^Hardware}}",
+            BASE64.encode(public_key.to_bytes()),
+            index,
+            BASE64.encode(hardware.to_bytes()),
+        )])
+        .unwrap()
+    }
+
+    fn has_mnemonic(
+        storage: &EphemeralSecureStorage,
+        id: &FactorSourceIDFromHash,
+    ) -> bool {
+        storage.storage.read().unwrap().contains_key(
+            &SecureStorageKey::DeviceFactorSourceMnemonic {
+                factor_source_id: id.clone(),
+            },
+        )
+    }
+
+    #[test]
+    fn import_software_accounts_adds_olympia_device_factor_source() {
+        let (wallet, storage) = Wallet::ephemeral(Profile::placeholder());
+        let accounts = wallet
+            .import_olympia_software_accounts(
+                export(0),
+                mnemonic_with_passphrase(),
+            )
+            .unwrap();
+        assert_eq!(accounts.len(), 1);
+        assert_eq!(accounts[0].display_name.value, "Software");
+
+        let id =
+            FactorSourceIDFromHash::new_for_device(mnemonic_with_passphrase());
+        assert!(has_mnemonic(&storage, &id));
+        let profile = wallet.profile();
+        assert_eq!(
+            profile
+                .device_factor_source_by_id(&id)
+                .unwrap()
+                .common
+                .crypto_parameters,
+            FactorSourceCryptoParameters::olympia()
+        );
+        assert_eq!(
+            profile.networks.get_account(&accounts[0].address),
+            Some(accounts[0].clone())
+        );
+        assert_eq!(profile.validate(), Vec::new());
+    }
+
+    #[test]
+    fn import_software_accounts_twice_fails() {
+        let (wallet, _) = Wallet::ephemeral(Profile::placeholder());
+        wallet
+            .import_olympia_software_accounts(
+                export(0),
+                mnemonic_with_passphrase(),
+            )
+            .unwrap();
+        let address = export(0).accounts[0].address(NetworkID::Mainnet);
+        let before = wallet.profile();
+        assert_eq!(
+            wallet.import_olympia_software_accounts(
+                export(0),
+                mnemonic_with_passphrase()
+            ),
+            Err(CommonError::AccountAlreadyPresent(address))
+        );
+        assert_eq!(wallet.profile(), before);
+    }
+
+    #[test]
+    fn import_software_accounts_with_wrong_mnemonic_fails() {
+        let (wallet, _) = Wallet::ephemeral(Profile::placeholder());
+        let address = export(0).accounts[0].address(NetworkID::Mainnet);
+        assert_eq!(
+            wallet.import_olympia_software_accounts(
+                export(0),
+                MnemonicWithPassphrase::placeholder()
+            ),
+            Err(CommonError::OlympiaAccountNotDerivedFromMnemonic(address))
+        );
+        assert_eq!(wallet.profile(), Profile::placeholder());
+    }
+
+    #[test]
+    fn import_software_accounts_adds_olympia_parameters_to_existing() {
+        let private = PrivateHierarchicalDeterministicFactorSource::new(
+            mnemonic_with_passphrase(),
+            DeviceFactorSource::babylon(
+                true,
+                mnemonic_with_passphrase(),
+                WalletClientModel::Unknown,
+            ),
+        );
+        let (wallet, _) = Wallet::ephemeral(Profile::new(private, "Test"));
+        wallet
+            .import_olympia_software_accounts(
+                export(1),
+                mnemonic_with_passphrase(),
+            )
+            .unwrap();
+        let profile = wallet.profile();
+        assert_eq!(profile.factor_sources.len(), 1);
+        assert_eq!(
+            profile.bdfs().common.crypto_parameters,
+            FactorSourceCryptoParameters::babylon_olympia_compatible()
+        );
+    }
+
+    #[test]
+    fn import_hardware_accounts() {
+        let (wallet, _) = Wallet::ephemeral(Profile::placeholder());
+        let ledger = LedgerHardwareWalletFactorSource::placeholder();
+        let accounts = wallet
+            .import_olympia_hardware_accounts(export(0), ledger.id.clone())
+            .unwrap();
+        assert_eq!(accounts.len(), 1);
+        assert_eq!(accounts[0].display_name.value, "Hardware");
+        let EntitySecurityState::Unsecured { value: control } =
+            accounts[0].security_state.clone();
+        assert_eq!(control.transaction_signing.factor_source_id, ledger.id);
+
+        assert_eq!(
+            wallet.import_olympia_hardware_accounts(export(0), ledger.id),
+            Err(CommonError::AccountAlreadyPresent(
+                accounts[0].address.clone()
+            ))
+        );
+    }
+
+    #[test]
+    fn import_hardware_accounts_requires_ledger() {
+        let (wallet, _) = Wallet::ephemeral(Profile::placeholder());
+        let device = DeviceFactorSource::placeholder().id;
+        assert_eq!(
+            wallet.import_olympia_hardware_accounts(export(0), device),
+            Err(CommonError::ExpectedLedgerHardwareWalletFactorSourceGotSomethingElse)
+        );
+        let unknown = LedgerHardwareWalletFactorSource::placeholder_other().id;
+        assert_eq!(
+            wallet.import_olympia_hardware_accounts(export(0), unknown.clone()),
+            Err(CommonError::ProfileDoesNotContainFactorSourceWithID(
+                unknown.into()
+            ))
+        );
+    }
+
+    #[test]
+    fn failed_import_keeps_mnemonic_saved_by_other_profile() {
+        let (wallet, storage) = Wallet::ephemeral(Profile::placeholder());
+        let id =
+            FactorSourceIDFromHash::new_for_device(mnemonic_with_passphrase());
+        // Saved by another stored Profile
+        wallet
+            .wallet_client_storage
+            .save_mnemonic_with_passphrase(&mnemonic_with_passphrase(), &id)
+            .unwrap();
+        // Make updating Profile fail
+        let account = export(0).accounts[0].to_account(
+            NetworkID::Mainnet,
+            id.clone(),
+            AppearanceID::default(),
+        );
+        wallet.add_account(account.clone()).unwrap();
+        let before = wallet.profile();
+
+        assert_eq!(
+            wallet.import_olympia_software_accounts(
+                export(0),
+                mnemonic_with_passphrase()
+            ),
+            Err(CommonError::AccountAlreadyPresent(account.address))
+        );
+
+        assert!(has_mnemonic(&storage, &id));
+        assert_eq!(wallet.profile(), before);
+    }
+
+    #[test]
+    fn failed_import_deletes_newly_saved_mnemonic() {
+        let (wallet, storage) = Wallet::ephemeral(Profile::placeholder());
+        let id =
+            FactorSourceIDFromHash::new_for_device(mnemonic_with_passphrase());
+        let account = export(0).accounts[0].to_account(
+            NetworkID::Mainnet,
+            id.clone(),
+            AppearanceID::default(),
+        );
+        wallet.add_account(account).unwrap();
+
+        assert!(wallet
+            .import_olympia_software_accounts(
+                export(0),
+                mnemonic_with_passphrase()
+            )
+            .is_err());
+
+        assert!(!has_mnemonic(&storage, &id));
+    }
+}
@@ -0,0 +1,573 @@
+use crate::prelude::*;
+
+//========
+// SET - Persona
+//========
+#[uniffi::export]
+impl Wallet {
+    /// Creates a new non securified persona **WITHOUT** add it to Profile, using the *main* "Babylon"
+    /// `DeviceFactorSource` and the "next" index for this FactorSource as derivation path.
+    ///
+    /// If `persona_data` is `None` the persona is created with empty `PersonaData`.
+    ///
+    /// If you want to add it to Profile, call `wallet.add_persona(persona)`
+    pub fn create_new_persona(
+        &self,
+        network_id: NetworkID,
+        name: DisplayName,
+        persona_data: Option<PersonaData>,
+    ) -> Result<Persona> {
+        let profile = &self.profile();
+        let bdfs = profile.bdfs();
+        let index = profile.next_derivation_index_for_entity(
+            EntityKind::Persona,
+            network_id,
+        )?;
+
+        let factor_instance =
+            self.load_private_device_factor_source(&bdfs).map(|p| {
+                p.derive_entity_creation_factor_instance(network_id, index)
+            })?;
+
+        let persona = Persona::new(factor_instance, name, persona_data);
+
+        Ok(persona)
+    }
+
+    /// Returns `Ok(())` if the `persona` was new and successfully added. If saving failed or if the persona was already present in Profile, an
+    /// error is returned.
+    pub fn add_persona(&self, persona: Persona) -> Result<()> {
+        let network_id = persona.network_id;
+        let err_exists =
+            CommonError::PersonaAlreadyPresent(persona.id().clone());
+        self.try_update_profile_with(|p| {
+            if p.networks.get_persona(&persona.address).is_some() {
+                return Err(err_exists.clone());
+            }
+            p.ensure_entity_derivation_index_is_free(&persona.security_state)?;
+            let networks = &mut p.networks;
+            if networks.contains_id(&network_id) {
+                networks
+                    .try_update_with(&network_id, |network| {
+                        if network.personas.append(persona.clone()).0 {
+                            Ok(network.clone())
+                        } else {
+                            Err(err_exists.clone())
+                        }
+                    })
+                    .and_then(
+                        |r| if r { Ok(()) } else { Err(err_exists.clone()) },
+                    )
+            } else {
+                let network = ProfileNetwork::new(
+                    network_id,
+                    Accounts::new(),
+                    Personas::from_iter([persona.to_owned()]),
+                    AuthorizedDapps::default(),
+                );
+                networks.append(network);
+                Ok(())
+            }
+        })?;
+
+        self.notify_observers(WalletEvent::PersonaAdded {
+            address: persona.address,
+        });
+        Ok(())
+    }
+
+    /// Create a new Persona and adds it to the active Profile.
+    pub fn create_and_save_new_persona(
+        &self,
+        network_id: NetworkID,
+        name: DisplayName,
+        persona_data: Option<PersonaData>,
+    ) -> Result<Persona> {
+        let persona =
+            self.create_new_persona(network_id, name, persona_data)?;
+        self.add_persona(persona.clone())?;
+        Ok(persona)
+    }
+
+    /// Hides the persona with `address`, it is kept in Profile and can be
+    /// unhidden at any time using `unhide_persona`.
+    ///
+    /// Returns `Err` if the persona is unknown.
+    pub fn hide_persona(&self, address: IdentityAddress) -> Result<Persona> {
+        self.update_persona_flags(&address, |f| {
+            _ = f.insert_flag(EntityFlag::HiddenByUser)
+        })
+    }
+
+    /// Unhides the persona with `address`, previously hidden by
+    /// `hide_persona`.
+    ///
+    /// Returns `Err` if the persona is unknown.
+    pub fn unhide_persona(&self, address: IdentityAddress) -> Result<Persona> {
+        self.update_persona_flags(&address, |f| {
+            _ = f.remove_flag(&EntityFlag::HiddenByUser)
+        })
+    }
+
+    /// Flags the persona with `address` as deleted by the user and removes
+    /// every reference to it from the AuthorizedDapps, the changes made to
+    /// them are returned.
+    ///
+    /// Returns `Err` if the persona is unknown.
+    pub fn delete_persona(
+        &self,
+        address: IdentityAddress,
+    ) -> Result<Vec<AuthorizedDappCascade>> {
+        let cascades =
+            self.try_update_profile_with(|p| p.delete_persona(&address))?;
+
+        self.notify_observers(WalletEvent::PersonaDeleted { address });
+        self.notify_authorized_dapp_cascades(&cascades);
+        Ok(cascades)
+    }
+}
+
+//========
+// SET - PersonaData
+//========
+#[uniffi::export]
+impl Wallet {
+    /// Adds `entry` to the PersonaData of the persona with `address`, under a
+    /// newly generated ID, which is returned.
+    ///
+    /// Returns `Err` if the persona is unknown, or if `entry` is a name and
+    /// the persona already has a name.
+    pub fn add_persona_data_entry(
+        &self,
+        address: IdentityAddress,
+        entry: PersonaDataEntry,
+    ) -> Result<PersonaDataEntryID> {
+        self.update_persona_data_with(&address, |d| d.add_entry(entry))
+    }
+
+    /// Replaces the value of the PersonaData entry with `id` of the persona
+    /// with `address` with `entry`, keeping the ID, so that dApps with
+    /// ongoing access to the entry will be sent the new value.
+    ///
+    /// Returns `Err` if the persona is unknown or if it has no entry of the
+    /// same kind as `entry` with `id`.
+    pub fn update_persona_data_entry(
+        &self,
+        address: IdentityAddress,
+        id: PersonaDataEntryID,
+        entry: PersonaDataEntry,
+    ) -> Result<()> {
+        self.update_persona_data_with(&address, |d| d.update_entry(&id, entry))
+    }
+
+    /// Removes the PersonaData entry with `id` of the persona with `address`,
+    /// should the user re-add the same value it will get a new ID.
+    ///
+    /// The entry is also removed from the PersonaData the persona shares with
+    /// AuthorizedDapps, the changes made to them are returned.
+    ///
+    /// Returns `Err` if the persona is unknown or if it has no entry with `id`.
+    pub fn remove_persona_data_entry(
+        &self,
+        address: IdentityAddress,
+        id: PersonaDataEntryID,
+    ) -> Result<Vec<AuthorizedDappCascade>> {
+        let cascades = self.try_update_profile_with(|p| {
+            p.remove_persona_data_entry(&address, &id)
+        })?;
+
+        self.notify_observers(WalletEvent::PersonaUpdated { address });
+        self.notify_authorized_dapp_cascades(&cascades);
+        Ok(cascades)
+    }
+}
+
+impl Wallet {
+    fn update_persona_flags<F>(
+        &self,
+        address: &IdentityAddress,
+        mut mutate: F,
+    ) -> Result<Persona>
+    where
+        F: FnMut(&mut EntityFlags),
+    {
+        let persona = self.try_update_profile_with(|p| {
+            p.update_persona(address, |x| mutate(&mut x.flags))
+                .ok_or(CommonError::UnknownPersona)
+        })?;
+
+        self.notify_observers(WalletEvent::PersonaUpdated {
+            address: address.clone(),
+        });
+        Ok(persona)
+    }
+
+    /// Mutates the PersonaData of the persona with `address` using `mutate`
+    /// and saves Profile, if `mutate` fails Profile is left unchanged.
+    fn update_persona_data_with<F, R>(
+        &self,
+        address: &IdentityAddress,
+        mutate: F,
+    ) -> Result<R>
+    where
+        F: FnOnce(&mut PersonaData) -> Result<R>,
+    {
+        let result = self.try_update_profile_with(|p| {
+            let mut persona = p
+                .networks
+                .get_persona(address)
+                .ok_or(CommonError::UnknownPersona)?;
+            let result = mutate(&mut persona.persona_data)?;
+            p.update_persona(address, |x| *x = persona.clone());
+            Ok(result)
+        })?;
+
+        self.notify_observers(WalletEvent::PersonaUpdated {
+            address: address.clone(),
+        });
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    fn derivation_index(persona: &Persona) -> HDPathValue {
+        let EntitySecurityState::Unsecured { value: control } =
+            &persona.security_state;
+        control
+            .transaction_signing
+            .derivation_path()
+            .last_component()
+            .index()
+    }
+
+    #[test]
+    fn create_new_persona_uses_next_index() {
+        let (wallet, _) = Wallet::ephemeral_with_bdfs_mnemonic();
+        let persona = wallet
+            .create_new_persona(
+                NetworkID::Mainnet,
+                DisplayName::new("Alan").unwrap(),
+                None,
+            )
+            .unwrap();
+        assert_eq!(persona.display_name.value, "Alan");
+        assert_eq!(persona.network_id, NetworkID::Mainnet);
+        assert_eq!(persona.persona_data, PersonaData::default());
+        assert_eq!(derivation_index(&persona), 2);
+        assert!(persona.address.address.starts_with("identity_rdx"));
+
+        // not added
+        assert_eq!(
+            wallet
+                .profile()
+                .networks
+                .get(&NetworkID::Mainnet)
+                .unwrap()
+                .personas,
+            Personas::placeholder_mainnet()
+        );
+    }
+
+    #[test]
+    fn create_new_persona_with_persona_data() {
+        let (wallet, _) = Wallet::ephemeral_with_bdfs_mnemonic();
+        let persona_data = Persona::placeholder_mainnet_satoshi().persona_data;
+        let persona = wallet
+            .create_new_persona(
+                NetworkID::Mainnet,
+                DisplayName::new("Alan").unwrap(),
+                Some(persona_data.clone()),
+            )
+            .unwrap();
+        assert_eq!(persona.persona_data, persona_data);
+    }
+
+    #[test]
+    fn create_new_persona_fails_without_mnemonic() {
+        let (wallet, _) = Wallet::ephemeral(Profile::placeholder());
+        assert_eq!(
+            wallet.create_new_persona(
+                NetworkID::Mainnet,
+                DisplayName::new("Alan").unwrap(),
+                None,
+            ),
+            Err(CommonError::UnableToLoadMnemonicFromSecureStorage(
+                FactorSourceIDFromHash::placeholder()
+            ))
+        );
+    }
+
+    #[test]
+    fn create_and_save_new_persona() {
+        let (wallet, _) = Wallet::ephemeral_with_bdfs_mnemonic();
+        let first = wallet
+            .create_and_save_new_persona(
+                NetworkID::Mainnet,
+                DisplayName::new("Alan").unwrap(),
+                None,
+            )
+            .unwrap();
+        let second = wallet
+            .create_and_save_new_persona(
+                NetworkID::Mainnet,
+                DisplayName::new("Ada").unwrap(),
+                None,
+            )
+            .unwrap();
+        assert_eq!(derivation_index(&second), 3);
+
+        let personas = wallet
+            .profile()
+            .networks
+            .get(&NetworkID::Mainnet)
+            .unwrap()
+            .personas
+            .clone();
+        assert_eq!(personas.len(), 4);
+        assert!(personas.contains_id(&first.address));
+        assert!(personas.contains_id(&second.address));
+    }
+
+    #[test]
+    fn add_persona_on_new_network() {
+        let (wallet, _) = Wallet::ephemeral_with_bdfs_mnemonic();
+        let persona = wallet
+            .create_new_persona(
+                NetworkID::Nebunet,
+                DisplayName::new("Alan").unwrap(),
+                None,
+            )
+            .unwrap();
+        assert_eq!(derivation_index(&persona), 0);
+        wallet.add_persona(persona.clone()).unwrap();
+
+        let profile = wallet.profile();
+        let network = profile.networks.get(&NetworkID::Nebunet).unwrap();
+        assert_eq!(network.personas, Personas::from_iter([persona]));
+        assert_eq!(network.accounts.len(), 0);
+    }
+
+    #[test]
+    fn add_persona_fails_when_already_present() {
+        let (wallet, _) = Wallet::ephemeral_with_bdfs_mnemonic();
+        let persona = Persona::placeholder_mainnet_satoshi();
+        let before = wallet.profile();
+        assert_eq!(
+            wallet.add_persona(persona.clone()),
+            Err(CommonError::PersonaAlreadyPresent(persona.address))
+        );
+        assert_eq!(wallet.profile(), before);
+    }
+
+    #[test]
+    fn hide_and_unhide_persona() {
+        let (wallet, _) = Wallet::ephemeral_with_bdfs_mnemonic();
+        let address = Persona::placeholder_mainnet_satoshi().address;
+        assert!(wallet
+            .hide_persona(address.clone())
+            .unwrap()
+            .flags
+            .is_hidden());
+        assert_eq!(
+            wallet
+                .profile()
+                .networks
+                .get(&NetworkID::Mainnet)
+                .unwrap()
+                .get_hidden_personas()
+                .len(),
+            1
+        );
+        assert!(wallet
+            .unhide_persona(address.clone())
+            .unwrap()
+            .flags
+            .is_visible());
+        // Hiding is not deleting, the AuthorizedDapps are untouched.
+        assert_eq!(wallet.profile().networks, Profile::placeholder().networks);
+    }
+
+    #[test]
+    fn hide_unknown_persona_fails() {
+        let (wallet, _) = Wallet::ephemeral_with_bdfs_mnemonic();
+        let address = Persona::placeholder_mainnet_satoshi().address;
+        wallet
+            .update_profile_with(|p| {
+                p.networks.update_with(&NetworkID::Mainnet, |n| {
+                    _ = n.personas.remove_by_id(&address)
+                })
+            })
+            .unwrap();
+        assert_eq!(
+            wallet.hide_persona(address),
+            Err(CommonError::UnknownPersona)
+        );
+    }
+
+    #[test]
+    fn delete_persona() {
+        let (wallet, _) = Wallet::ephemeral_with_bdfs_mnemonic();
+        let address = Persona::placeholder_mainnet_satoshi().address;
+        let cascades = wallet.delete_persona(address.clone()).unwrap();
+        assert_eq!(
+            cascades,
+            vec![AuthorizedDappCascade::PersonaReferenceRemoved {
+                dapp_definition_address:
+                    AuthorizedDapp::placeholder_mainnet_dashboard()
+                        .dapp_definition_address,
+                identity_address: address.clone(),
+            }]
+        );
+        let profile = wallet.profile();
+        assert!(profile
+            .networks
+            .get_persona(&address)
+            .unwrap()
+            .flags
+            .contains_id(&EntityFlag::DeletedByUser));
+    }
+
+    fn satoshi(wallet: &Wallet) -> Persona {
+        wallet
+            .profile()
+            .networks
+            .get_persona(&Persona::placeholder_mainnet_satoshi().address)
+            .unwrap()
+    }
+
+    #[test]
+    fn add_persona_data_entry() {
+        let (wallet, _) = Wallet::ephemeral_with_bdfs_mnemonic();
+        let address = satoshi(&wallet).address;
+        let email =
+            PersonaDataEntryEmailAddress::new("satoshi@bitcoin.org").unwrap();
+        let id = wallet
+            .add_persona_data_entry(address, email.clone().into())
+            .unwrap();
+        assert_eq!(
+            satoshi(&wallet)
+                .persona_data
+                .email_addresses
+                .get(&id)
+                .unwrap()
+                .value,
+            email
+        );
+    }
+
+    #[test]
+    fn update_persona_data_entry_keeps_id() {
+        let (wallet, _) = Wallet::ephemeral_with_bdfs_mnemonic();
+        let persona = satoshi(&wallet);
+        let id = persona.persona_data.phone_numbers.items()[0].id.clone();
+        let phone = PersonaDataEntryPhoneNumber::new("+1555123456").unwrap();
+        wallet
+            .update_persona_data_entry(
+                persona.address,
+                id.clone(),
+                phone.clone().into(),
+            )
+            .unwrap();
+        let phone_numbers = satoshi(&wallet).persona_data.phone_numbers;
+        assert_eq!(phone_numbers.get(&id).unwrap().value, phone);
+        assert_eq!(
+            phone_numbers.len(),
+            persona.persona_data.phone_numbers.len()
+        );
+    }
+
+    #[test]
+    fn remove_persona_data_entry() {
+        let (wallet, _) = Wallet::ephemeral_with_bdfs_mnemonic();
+        let persona = satoshi(&wallet);
+        let id = persona.persona_data.email_addresses.items()[0].id.clone();
+        wallet
+            .remove_persona_data_entry(persona.address.clone(), id.clone())
+            .unwrap();
+        assert!(!satoshi(&wallet)
+            .persona_data
+            .email_addresses
+            .contains_id(&id));
+        assert_eq!(
+            wallet.remove_persona_data_entry(persona.address, id.clone()),
+            Err(CommonError::UnknownPersonaDataEntry(id))
+        );
+    }
+
+    #[test]
+    fn remove_persona_data_entry_cascades_to_authorized_dapps() {
+        let (wallet, _) = Wallet::ephemeral_with_bdfs_mnemonic();
+        let persona = satoshi(&wallet);
+        let id = persona.persona_data.name.clone().unwrap().id;
+        let cascades = wallet
+            .remove_persona_data_entry(persona.address.clone(), id.clone())
+            .unwrap();
+        assert_eq!(
+            cascades,
+            vec![AuthorizedDappCascade::SharedPersonaDataEntryRemoved {
+                dapp_definition_address:
+                    AuthorizedDapp::placeholder_mainnet_dashboard()
+                        .dapp_definition_address,
+                identity_address: persona.address.clone(),
+                entry_id: id,
+            }]
+        );
+        let dapp = wallet
+            .profile()
+            .networks
+            .get(&NetworkID::Mainnet)
+            .unwrap()
+            .authorized_dapps
+            .get(
+                &AuthorizedDapp::placeholder_mainnet_dashboard()
+                    .dapp_definition_address,
+            )
+            .cloned()
+            .unwrap();
+        assert_eq!(
+            dapp.references_to_authorized_personas
+                .get(&persona.address)
+                .unwrap()
+                .shared_persona_data
+                .name,
+            None
+        );
+    }
+
+    #[test]
+    fn add_persona_data_name_fails_if_present() {
+        let (wallet, _) = Wallet::ephemeral_with_bdfs_mnemonic();
+        let before = wallet.profile();
+        assert_eq!(
+            wallet.add_persona_data_entry(
+                satoshi(&wallet).address,
+                PersonaDataEntryName::placeholder().into()
+            ),
+            Err(CommonError::PersonaDataNameAlreadyPresent)
+        );
+        assert_eq!(wallet.profile(), before);
+    }
+
+    #[test]
+    fn persona_data_entry_of_unknown_persona_fails() {
+        let (wallet, _) = Wallet::ephemeral_with_bdfs_mnemonic();
+        let address = Persona::placeholder_mainnet_satoshi().address;
+        wallet
+            .update_profile_with(|p| {
+                p.networks.update_with(&NetworkID::Mainnet, |n| {
+                    _ = n.personas.remove_by_id(&address)
+                })
+            })
+            .unwrap();
+        assert_eq!(
+            wallet.remove_persona_data_entry(
+                address,
+                PersonaDataEntryID::placeholder()
+            ),
+            Err(CommonError::UnknownPersona)
+        );
+    }
+}
@@ -0,0 +1,131 @@
+use crate::prelude::*;
+
+//========
+// Profile Backup - Export
+//========
+#[uniffi::export]
+impl Wallet {
+    /// Takes a snapshot of the profile, encrypts it with a key derived from
+    /// `password` and returns the versioned encrypted envelope as a String
+    /// of JSON, suitable to be saved as a backup file.
+    pub fn encrypted_json_snapshot(&self, password: String) -> Result<String> {
+        EncryptedProfileSnapshot::encrypting(&self.profile(), password)
+            .and_then(|e| {
+                serde_json::to_string(&e)
+                    .map_err(|_| CommonError::FailedToSerializeToJSON)
+            })
+    }
+}
+
+//========
+// Profile Backup - Import
+//========
+#[uniffi::export]
+impl Wallet {
+    /// Creates wallet by *importing* a Profile backup, being either a
+    /// plaintext Profile snapshot (JSON) or an encrypted Profile snapshot,
+    /// in which case `password` is required to decrypt it.
+    #[uniffi::constructor]
+    pub fn by_importing_profile_backup(
+        backup: Vec<u8>,
+        password: Option<String>,
+        secure_storage: Arc<dyn SecureStorage>,
+    ) -> Result<Self> {
+        ProfileBackupContents::decode(backup)
+            .and_then(|c| c.into_profile(password))
+            .map(|p| Self::by_importing_profile(p, secure_storage))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn import_encrypted_backup() {
+        let wallet = Wallet::placeholder();
+        let backup = wallet
+            .encrypted_json_snapshot("babylon".to_owned())
+            .unwrap();
+        let imported = Wallet::by_importing_profile_backup(
+            backup.into_bytes(),
+            Some("babylon".to_owned()),
+            EphemeralSecureStorage::new(),
+        )
+        .unwrap();
+        assert_eq!(imported.profile(), wallet.profile());
+    }
+
+    #[test]
+    fn import_plaintext_backup() {
+        let wallet = Wallet::placeholder();
+        let imported = Wallet::by_importing_profile_backup(
+            wallet.json_snapshot().into_bytes(),
+            None,
+            EphemeralSecureStorage::new(),
+        )
+        .unwrap();
+        assert_eq!(imported.profile(), wallet.profile());
+    }
+
+    #[test]
+    fn import_encrypted_backup_wrong_password() {
+        let wallet = Wallet::placeholder();
+        let backup = wallet
+            .encrypted_json_snapshot("babylon".to_owned())
+            .unwrap();
+        assert_eq!(
+            Wallet::by_importing_profile_backup(
+                backup.into_bytes(),
+                Some("olympia".to_owned()),
+                EphemeralSecureStorage::new(),
+            )
+            .map(|w| w.profile()),
+            Err(CommonError::FailedToDecryptEncryptedProfileSnapshot)
+        );
+    }
+
+    #[test]
+    fn import_encrypted_backup_without_password() {
+        let wallet = Wallet::placeholder();
+        let backup = wallet
+            .encrypted_json_snapshot("babylon".to_owned())
+            .unwrap();
+        assert_eq!(
+            Wallet::by_importing_profile_backup(
+                backup.into_bytes(),
+                None,
+                EphemeralSecureStorage::new(),
+            )
+            .map(|w| w.profile()),
+            Err(CommonError::PasswordRequiredToDecryptEncryptedProfileSnapshot)
+        );
+    }
+
+    #[test]
+    fn import_unsupported_format() {
+        assert_eq!(
+            Wallet::by_importing_profile_backup(
+                "not a profile".as_bytes().to_vec(),
+                None,
+                EphemeralSecureStorage::new(),
+            )
+            .map(|w| w.profile()),
+            Err(CommonError::UnsupportedProfileSnapshotFormat)
+        );
+    }
+
+    #[test]
+    fn import_does_not_save_on_failure() {
+        let storage = EphemeralSecureStorage::new();
+        _ = Wallet::by_importing_profile_backup(
+            "not a profile".as_bytes().to_vec(),
+            None,
+            storage.clone(),
+        );
+        assert_eq!(
+            storage.load_data(SecureStorageKey::ActiveProfileID),
+            Ok(None)
+        );
+    }
+}
@@ -4,13 +4,30 @@ use crate::prelude::*;
 // Wallet + SecureStorage
 //========
 impl Wallet {
+    /// Saves `profile` and updates its `Header` in the list of headers
+    /// of all stored Profiles.
+    /// Saves the snapshot of `profile` and then updates its header in the
+    /// headers list.
+    ///
+    /// Once the snapshot has been saved the Profile counts as saved, so
+    /// failing to update the headers list is logged instead of returned, and
+    /// the headers list is no longer considered complete.
     pub(crate) fn save_profile(&self, profile: &Profile) -> Result<()> {
         self.wallet_client_storage.save(
             SecureStorageKey::ProfileSnapshot {
                 profile_id: profile.header.id.clone(),
             },
             profile,
-        )
+        )?;
+        if let Err(e) = self.update_header_in_headers_list(&profile.header) {
+            error!(
+                "Failed to update header of Profile with ID: {} in headers list, error: {}",
+                profile.id(),
+                e
+            );
+            self.mark_headers_list_incomplete();
+        }
+        Ok(())
     }
     pub(crate) fn save_active_profile_id(
         &self,
@@ -65,10 +82,164 @@ impl Wallet {
     }
 }
 
+//========
+// Wallet + SecureStorage - HeadersList
+//========
+impl Wallet {
+    /// Loads the list of headers of all stored Profiles, returns an empty
+    /// list if none has been saved yet.
+    pub(crate) fn load_headers_list(&self) -> Result<HeadersList> {
+        self.wallet_client_storage
+            .load(SecureStorageKey::SnapshotHeadersList)
+            .map(|l| l.unwrap_or_else(HeadersList::new))
+            .map_err(|_| CommonError::FailedToLoadProfileHeadersList)
+    }
+
+    pub(crate) fn save_headers_list(
+        &self,
+        headers: &HeadersList,
+    ) -> Result<()> {
+        self.wallet_client_storage
+            .save(SecureStorageKey::SnapshotHeadersList, headers)
+    }
+
+    /// Replaces the header with the same ID as `header`, or appends it if
+    /// the list does not contain it yet.
+    pub(crate) fn update_header_in_headers_list(
+        &self,
+        header: &Header,
+    ) -> Result<()> {
+        let mut headers = self.load_headers_list()?;
+        if !headers.update_with(&header.id, |h| *h = header.clone()) {
+            headers.append(header.clone());
+        }
+        self.save_headers_list(&headers)
+    }
+
+    pub(crate) fn remove_header_from_headers_list(
+        &self,
+        profile_id: &ProfileID,
+    ) -> Result<()> {
+        let mut headers = self.load_headers_list()?;
+        headers.remove_by_id(profile_id);
+        self.save_headers_list(&headers)
+    }
+
+    /// Returns `true` if the headers list is known to contain every Profile
+    /// saved in SecureStorage, see `SnapshotHeadersListIsComplete`.
+    pub(crate) fn is_headers_list_complete(&self) -> Result<bool> {
+        self.wallet_client_storage
+            .load(SecureStorageKey::SnapshotHeadersListIsComplete)
+            .map(|c| c.unwrap_or(false))
+    }
+
+    pub(crate) fn mark_headers_list_complete(&self) -> Result<()> {
+        self.wallet_client_storage
+            .save(SecureStorageKey::SnapshotHeadersListIsComplete, &true)
+    }
+
+    /// Forgets that the headers list is complete, e.g. since it could not be
+    /// updated, so that no mnemonics are deleted based on it.
+    pub(crate) fn mark_headers_list_incomplete(&self) {
+        if let Err(e) = self
+            .wallet_client_storage
+            .delete(SecureStorageKey::SnapshotHeadersListIsComplete)
+        {
+            error!("Failed to mark headers list as incomplete, error: {}", e);
+        }
+    }
+
+    /// Returns `true` if neither a Profile nor a headers list has ever been
+    /// saved in SecureStorage, in which case the headers list will contain
+    /// every Profile saved from now on.
+    pub(crate) fn is_storage_without_profiles(&self) -> bool {
+        matches!(
+            self.wallet_client_storage
+                .load::<ProfileID>(SecureStorageKey::ActiveProfileID),
+            Ok(None)
+        ) && matches!(
+            self.wallet_client_storage
+                .load::<HeadersList>(SecureStorageKey::SnapshotHeadersList),
+            Ok(None)
+        )
+    }
+}
+
+//========
+// Wallet + SecureStorage - DeviceInfo
+//========
+#[uniffi::export]
+impl Wallet {
+    /// Sets the `DeviceInfo` of the host device, which is stamped as
+    /// `last_used_on_device` on the Profile every time it is saved.
+    ///
+    /// Until it has been set, the `last_used_on_device` of the Profile is
+    /// kept as is when it is saved.
+    pub fn set_host_device_info(&self, device_info: DeviceInfo) -> Result<()> {
+        self.save_host_device_info(&device_info)
+    }
+}
+
+impl Wallet {
+    /// Loads the `DeviceInfo` of the host device, `None` if the host has not
+    /// set it, see `set_host_device_info`.
+    pub(crate) fn host_device_info(&self) -> Result<Option<DeviceInfo>> {
+        self.wallet_client_storage
+            .load(SecureStorageKey::DeviceInfo)
+    }
+
+    /// Saves `device_info` as the `DeviceInfo` of the host device, unless
+    /// one has already been saved.
+    pub(crate) fn save_host_device_info_if_absent(
+        &self,
+        device_info: &DeviceInfo,
+    ) -> Result<()> {
+        if self
+            .wallet_client_storage
+            .load::<DeviceInfo>(SecureStorageKey::DeviceInfo)?
+            .is_some()
+        {
+            return Ok(());
+        }
+        self.save_host_device_info(device_info)
+    }
+
+    fn save_host_device_info(&self, device_info: &DeviceInfo) -> Result<()> {
+        self.wallet_client_storage
+            .save(SecureStorageKey::DeviceInfo, device_info)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::prelude::*;
 
+    #[test]
+    fn update_without_host_device_info_keeps_last_used_on_device() {
+        let (wallet, storage) = Wallet::ephemeral(Profile::placeholder());
+        wallet
+            .update_app_preferences(AppPreferences::placeholder_other())
+            .unwrap();
+        assert_eq!(
+            wallet.profile().header.last_used_on_device,
+            Profile::placeholder().header.last_used_on_device
+        );
+        assert_eq!(storage.load_data(SecureStorageKey::DeviceInfo), Ok(None));
+    }
+
+    #[test]
+    fn update_uses_host_device_info_set_by_host() {
+        let storage = EphemeralSecureStorage::new();
+        let wallet =
+            Wallet::by_importing_profile(Profile::placeholder(), storage);
+        let device_info = DeviceInfo::with_description("My Pixel");
+        wallet.set_host_device_info(device_info.clone()).unwrap();
+        wallet
+            .update_app_preferences(AppPreferences::placeholder_other())
+            .unwrap();
+        assert_eq!(wallet.profile().header.last_used_on_device, device_info);
+    }
+
     #[should_panic(
         expected = "Fatal error: 'Failed to save active ProfileID: 12345678-bbbb-cccc-dddd-abcd12345678, error: Unknown Error'"
     )]
@@ -82,7 +253,7 @@ mod tests {
                 &self,
                 _key: SecureStorageKey,
             ) -> Result<Option<Vec<u8>>> {
-                todo!()
+                Ok(None)
             }
 
             fn save_data(
@@ -123,7 +294,7 @@ mod tests {
                 &self,
                 _key: SecureStorageKey,
             ) -> Result<Option<Vec<u8>>> {
-                todo!()
+                Ok(None)
             }
 
             fn save_data(
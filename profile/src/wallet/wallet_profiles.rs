@@ -0,0 +1,399 @@
+use crate::prelude::*;
+
+//========
+// Multi-Profile
+//========
+#[uniffi::export]
+impl Wallet {
+    /// The headers of all Profiles saved in SecureStorage, including the
+    /// active one, in the order they were first saved.
+    pub fn profile_headers(&self) -> Result<HeadersList> {
+        self.load_headers_list()
+    }
+
+    /// Loads the Profile with `profile_id` from SecureStorage and makes it the
+    /// active Profile of this Wallet, replacing the current one in place.
+    ///
+    /// Observers are notified with `WalletEvent::ProfileSwitched`.
+    ///
+    /// Returns `Err` if no Profile with `profile_id` is stored, in which case
+    /// the current Profile remains active.
+    pub fn switch_profile(&self, profile_id: ProfileID) -> Result<()> {
+        let profile = self
+            .wallet_client_storage
+            .load_profile_snapshot_or_fail(&profile_id)?;
+
+        let mut active = self.write_profile();
+
+        self.save_active_profile_id(&profile_id)?;
+        *active = profile;
+        drop(active);
+
+        info!("Switched active Profile to Profile with ID: {}", profile_id);
+        self.notify_observers(WalletEvent::ProfileSwitched { profile_id });
+        Ok(())
+    }
+
+    /// Deletes the Profile with `profile_id` from SecureStorage, together
+    /// with the mnemonics of its `DeviceFactorSource`s which no other stored
+    /// Profile references.
+    ///
+    /// Mnemonics are only deleted if the headers list is known to contain
+    /// every stored Profile, see `complete_profile_headers_list`, since a
+    /// Profile missing from it might reference them.
+    ///
+    /// Returns `Err` if `profile_id` is the ID of the active Profile.
+    pub fn delete_profile(&self, profile_id: ProfileID) -> Result<()> {
+        let active_profile = self.profile();
+        if active_profile.id() == profile_id {
+            return Err(CommonError::UnableToDeleteActiveProfile(profile_id));
+        }
+
+        let profile = self
+            .wallet_client_storage
+            .load_profile_snapshot_or_fail(&profile_id)?;
+
+        let referenced_elsewhere = self
+            .device_factor_source_ids_of_stored_profiles_except(&[
+                profile_id.clone(),
+                active_profile.id(),
+            ])?
+            .map(|mut ids| {
+                ids.extend(active_profile.device_factor_source_ids());
+                ids
+            });
+
+        // Remove the header first, so that the headers list never contains
+        // the header of a deleted snapshot.
+        self.remove_header_from_headers_list(&profile_id)?;
+        if let Err(e) = self.wallet_client_storage.delete(
+            SecureStorageKey::ProfileSnapshot {
+                profile_id: profile_id.clone(),
+            },
+        ) {
+            if self.update_header_in_headers_list(&profile.header).is_err() {
+                self.mark_headers_list_incomplete();
+            }
+            return Err(e);
+        }
+
+        match referenced_elsewhere {
+            Some(referenced_elsewhere) => {
+                for id in profile
+                    .device_factor_source_ids()
+                    .difference(&referenced_elsewhere)
+                {
+                    self.wallet_client_storage.delete_mnemonic(id)?;
+                }
+            }
+            None => warn!(
+                "Kept mnemonics of deleted Profile with ID: {}, since the headers list might not contain every stored Profile",
+                profile_id
+            ),
+        }
+
+        info!("Deleted Profile with ID: {}", profile_id);
+        Ok(())
+    }
+
+    /// Adds the headers of the Profiles with `profile_ids`, saved in
+    /// SecureStorage, to the headers list and marks it as complete.
+    ///
+    /// Wallet Clients which saved Profiles before the headers list was
+    /// introduced MUST call this once with the IDs of **all** of them, until
+    /// then no mnemonic is deleted when a Profile or FactorSource is deleted,
+    /// since a Profile missing from the list might reference it.
+    ///
+    /// Returns `Err` if any of the Profiles cannot be loaded, in which case
+    /// the list is not marked as complete.
+    pub fn complete_profile_headers_list(
+        &self,
+        profile_ids: Vec<ProfileID>,
+    ) -> Result<()> {
+        for profile_id in profile_ids {
+            let profile = self
+                .wallet_client_storage
+                .load_profile_snapshot_or_fail(&profile_id)?;
+            self.update_header_in_headers_list(&profile.header)?;
+        }
+        self.mark_headers_list_complete()
+    }
+}
+
+impl Wallet {
+    /// The IDs of the `DeviceFactorSource`s of all Profiles saved in
+    /// SecureStorage, except the Profiles with an ID in `excluded`.
+    ///
+    /// Returns `None` if the headers list might not contain every stored
+    /// Profile, in which case any mnemonic might be referenced by another
+    /// Profile.
+    pub(crate) fn device_factor_source_ids_of_stored_profiles_except(
+        &self,
+        excluded: &[ProfileID],
+    ) -> Result<Option<HashSet<FactorSourceIDFromHash>>> {
+        if !self.is_headers_list_complete()? {
+            return Ok(None);
+        }
+        let mut ids = HashSet::new();
+        for header in self.load_headers_list()?.items() {
+            if excluded.contains(&header.id) {
+                continue;
+            }
+            if let Some(other) = self
+                .wallet_client_storage
+                .load_profile_snapshot(&header.id)?
+            {
+                ids.extend(other.device_factor_source_ids());
+            }
+        }
+        Ok(Some(ids))
+    }
+}
+
+impl Profile {
+    fn device_factor_source_ids(&self) -> HashSet<FactorSourceIDFromHash> {
+        self.factor_sources
+            .items()
+            .into_iter()
+            .filter_map(|f| f.as_device().map(|d| d.id.clone()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    fn import(
+        private: &PrivateHierarchicalDeterministicFactorSource,
+        storage: Arc<EphemeralSecureStorage>,
+    ) -> (Wallet, Profile) {
+        let profile = Profile::new(private.clone(), "Test");
+        let wallet = Wallet::by_importing_profile(profile.clone(), storage);
+        wallet
+            .wallet_client_storage
+            .save_mnemonic_with_passphrase(
+                &private.mnemonic_with_passphrase,
+                &private.factor_source.id,
+            )
+            .unwrap();
+        (wallet, profile)
+    }
+
+    fn has_mnemonic(
+        storage: &EphemeralSecureStorage,
+        private: &PrivateHierarchicalDeterministicFactorSource,
+    ) -> bool {
+        storage.storage.read().unwrap().contains_key(
+            &SecureStorageKey::DeviceFactorSourceMnemonic {
+                factor_source_id: private.factor_source.id.clone(),
+            },
+        )
+    }
+
+    #[test]
+    fn headers_list_updated_on_import() {
+        let storage = EphemeralSecureStorage::new();
+        let first = Profile::placeholder();
+        let second = Profile::placeholder_other();
+        _ = Wallet::by_importing_profile(first.clone(), storage.clone());
+        let wallet =
+            Wallet::by_importing_profile(second.clone(), storage.clone());
+        assert_eq!(
+            wallet.profile_headers().unwrap(),
+            HeadersList::from_iter([first.header, second.header])
+        );
+    }
+
+    #[test]
+    fn headers_list_updated_on_save() {
+        let (wallet, _) = Wallet::ephemeral(Profile::placeholder());
+        let address = wallet
+            .access_profile_with(|p| p.networks[0].accounts[0].address.clone());
+        let account = wallet
+            .change_name_of_account(
+                address,
+                DisplayName::new("Stella").unwrap(),
+            )
+            .unwrap();
+        assert_eq!(account.display_name.value, "Stella");
+        assert_eq!(
+            wallet.profile_headers().unwrap(),
+            HeadersList::from_iter([wallet.profile().header])
+        );
+    }
+
+    #[test]
+    fn headers_list_empty_if_never_saved() {
+        let (wallet, storage) = Wallet::ephemeral(Profile::placeholder());
+        storage
+            .delete_data_for_key(SecureStorageKey::SnapshotHeadersList)
+            .unwrap();
+        assert_eq!(wallet.profile_headers().unwrap(), HeadersList::new());
+    }
+
+    #[test]
+    fn switch_profile() {
+        let storage = EphemeralSecureStorage::new();
+        let first = Profile::placeholder();
+        let second = Profile::placeholder_other();
+        _ = Wallet::by_importing_profile(first.clone(), storage.clone());
+        let wallet =
+            Wallet::by_importing_profile(second.clone(), storage.clone());
+        assert_eq!(wallet.profile(), second);
+
+        assert!(wallet.switch_profile(first.id()).is_ok());
+        assert_eq!(wallet.profile(), first);
+        assert_eq!(
+            storage.load_data(SecureStorageKey::ActiveProfileID),
+            Ok(Some(serde_json::to_vec(&first.id()).unwrap()))
+        );
+    }
+
+    #[test]
+    fn switch_profile_unknown() {
+        let (wallet, _) = Wallet::ephemeral(Profile::placeholder());
+        let unknown = ProfileID::placeholder_other();
+        assert_eq!(
+            wallet.switch_profile(unknown.clone()),
+            Err(CommonError::ProfileSnapshotNotFound(unknown))
+        );
+        assert_eq!(wallet.profile(), Profile::placeholder());
+    }
+
+    #[test]
+    fn delete_profile_failing_to_update_headers_list_keeps_snapshot() {
+        let storage = Arc::new(InMemorySecureStorage::new());
+        let first = Profile::placeholder();
+        let second = Profile::placeholder_other();
+        _ = Wallet::by_importing_profile(
+            first.clone(),
+            storage.clone().as_secure_storage(),
+        );
+        let wallet = Wallet::by_importing_profile(
+            second.clone(),
+            storage.clone().as_secure_storage(),
+        );
+        storage.add_fault(SecureStorageFault::FailKeyKind {
+            kind: SecureStorageKeyKind::SnapshotHeadersList,
+        });
+
+        assert!(wallet.delete_profile(first.id()).is_err());
+
+        storage.remove_all_faults();
+        assert!(wallet.profile_headers().unwrap().contains_id(&first.id()));
+        assert_eq!(wallet.switch_profile(first.id()), Ok(()));
+        assert_eq!(wallet.profile(), first);
+    }
+
+    #[test]
+    fn delete_active_profile_fails() {
+        let (wallet, _) = Wallet::ephemeral(Profile::placeholder());
+        let id = wallet.profile().id();
+        assert_eq!(
+            wallet.delete_profile(id.clone()),
+            Err(CommonError::UnableToDeleteActiveProfile(id))
+        );
+    }
+
+    #[test]
+    fn delete_unknown_profile_fails() {
+        let (wallet, _) = Wallet::ephemeral(Profile::placeholder());
+        let unknown = ProfileID::placeholder_other();
+        assert_eq!(
+            wallet.delete_profile(unknown.clone()),
+            Err(CommonError::ProfileSnapshotNotFound(unknown))
+        );
+    }
+
+    #[test]
+    fn delete_profile_only_deletes_unreferenced_mnemonics() {
+        let storage = EphemeralSecureStorage::new();
+        let shared =
+            PrivateHierarchicalDeterministicFactorSource::placeholder();
+        let unique = PrivateHierarchicalDeterministicFactorSource::generate_new(
+            WalletClientModel::Unknown,
+        );
+        let (_, sharing) = import(&shared, storage.clone());
+        let (_, not_sharing) = import(&unique, storage.clone());
+        let (wallet, active) = import(&shared, storage.clone());
+
+        assert!(wallet.delete_profile(not_sharing.id()).is_ok());
+        assert!(!has_mnemonic(&storage, &unique));
+        assert!(has_mnemonic(&storage, &shared));
+
+        assert!(wallet.delete_profile(sharing.id()).is_ok());
+        assert!(has_mnemonic(&storage, &shared));
+
+        assert_eq!(
+            wallet.profile_headers().unwrap(),
+            HeadersList::from_iter([active.header])
+        );
+        assert_eq!(
+            storage.load_data(SecureStorageKey::ProfileSnapshot {
+                profile_id: sharing.id()
+            }),
+            Ok(None)
+        );
+    }
+
+    #[test]
+    fn delete_profile_keeps_mnemonics_used_by_profile_missing_from_headers_list(
+    ) {
+        let storage = EphemeralSecureStorage::new();
+        let shared =
+            PrivateHierarchicalDeterministicFactorSource::placeholder();
+        let unique = PrivateHierarchicalDeterministicFactorSource::generate_new(
+            WalletClientModel::Unknown,
+        );
+
+        // Saved before the headers list was introduced.
+        let legacy = Profile::new(shared.clone(), "Legacy");
+        storage
+            .save_data(
+                SecureStorageKey::ProfileSnapshot {
+                    profile_id: legacy.id(),
+                },
+                serde_json::to_vec(&legacy).unwrap(),
+            )
+            .unwrap();
+        storage
+            .save_data(
+                SecureStorageKey::ActiveProfileID,
+                serde_json::to_vec(&legacy.id()).unwrap(),
+            )
+            .unwrap();
+
+        let (_, sharing) = import(&shared, storage.clone());
+        let (wallet, _) = import(&unique, storage.clone());
+        assert!(!wallet.profile_headers().unwrap().contains_id(&legacy.id()));
+
+        assert!(wallet.delete_profile(sharing.id()).is_ok());
+        assert!(has_mnemonic(&storage, &shared));
+
+        wallet
+            .complete_profile_headers_list(vec![legacy.id()])
+            .unwrap();
+        assert!(wallet.profile_headers().unwrap().contains_id(&legacy.id()));
+
+        assert!(wallet.delete_profile(legacy.id()).is_ok());
+        assert!(!has_mnemonic(&storage, &shared));
+        assert!(has_mnemonic(&storage, &unique));
+    }
+
+    #[test]
+    fn complete_profile_headers_list_unknown_profile_fails() {
+        let (wallet, storage) = Wallet::ephemeral(Profile::placeholder());
+        storage
+            .delete_data_for_key(
+                SecureStorageKey::SnapshotHeadersListIsComplete,
+            )
+            .unwrap();
+        let unknown = ProfileID::placeholder_other();
+        assert_eq!(
+            wallet.complete_profile_headers_list(vec![unknown.clone()]),
+            Err(CommonError::ProfileSnapshotNotFound(unknown))
+        );
+        assert_eq!(wallet.is_headers_list_complete(), Ok(false));
+    }
+}
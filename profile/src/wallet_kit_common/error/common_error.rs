@@ -304,4 +304,120 @@ pub enum CommonError {
 
     #[error("Invalid UUID (v4), got: {0}")]
     InvalidUUIDv4(String) = 10086,
+
+    #[error("Unrecognized Profile backup format, expected a Profile snapshot or an encrypted Profile snapshot.")]
+    UnsupportedProfileSnapshotFormat = 10087,
+
+    #[error("Unsupported Profile snapshot version: {0}")]
+    UnsupportedProfileSnapshotVersion(u16) = 10088,
+
+    #[error("Unsupported encrypted Profile snapshot version: {0}")]
+    UnsupportedEncryptedProfileSnapshotVersion(u16) = 10089,
+
+    #[error(
+        "Profile backup is encrypted, a password is required to decrypt it."
+    )]
+    PasswordRequiredToDecryptEncryptedProfileSnapshot = 10090,
+
+    #[error("Failed to derive encryption key from password.")]
+    FailedToDeriveEncryptionKeyFromPassword = 10091,
+
+    #[error("Failed to encrypt Profile snapshot.")]
+    FailedToEncryptProfileSnapshot = 10092,
+
+    #[error(
+        "Failed to decrypt Profile snapshot, wrong password or corrupt data."
+    )]
+    FailedToDecryptEncryptedProfileSnapshot = 10093,
+
+    #[error("Unable to delete the active Profile with ID: {0}, switch to another Profile first.")]
+    UnableToDeleteActiveProfile(ProfileID) = 10094,
+
+    #[error("Failed to create SecureStorage directory at path: {0}")]
+    FailedToCreateSecureStorageDirectory(String) = 10095,
+
+    #[error("Failed to decrypt data in SecureStorage, wrong encryption key or corrupt data.")]
+    FailedToDecryptSecureStorageData = 10096,
+
+    #[error("Failed to migrate Profile snapshot from version {from_version} to version {to_version}")]
+    FailedToMigrateProfileSnapshot { from_version: u16, to_version: u16 } =
+        10097,
+
+    #[error("Unable to merge Profiles with different IDs")]
+    UnableToMergeProfilesWithDifferentIDs = 10098,
+
+    #[error("Unable to remove FactorSource which controls Accounts or Personas, id: {0}")]
+    UnableToRemoveFactorSourceControllingEntities(FactorSourceID) = 10099,
+
+    #[error("Unable to remove the only Babylon DeviceFactorSource, id: {0}")]
+    UnableToRemoveOnlyBabylonDeviceFactorSource(FactorSourceID) = 10100,
+
+    #[error("Invalid Olympia wallet export payload: '{0}'")]
+    InvalidOlympiaWalletExportPayload(String) = 10101,
+
+    #[error("Incomplete Olympia wallet export, expected {expected} payloads, found: {found}")]
+    IncompleteOlympiaWalletExport { expected: u16, found: u16 } = 10102,
+
+    #[error("Olympia account is not derived from the mnemonic, address: {0}")]
+    OlympiaAccountNotDerivedFromMnemonic(AccountAddress) = 10103,
+
+    #[error(
+        "Wrong Ledger hardware wallet, expected: {expected}, found: {found}"
+    )]
+    WrongLedgerHardwareWallet {
+        expected: FactorSourceIDFromHash,
+        found: FactorSourceIDFromHash,
+    } = 10104,
+
+    #[error("Persona Already Present {0}")]
+    PersonaAlreadyPresent(IdentityAddress) = 10105,
+
+    #[error("Unknown Persona")]
+    UnknownPersona = 10106,
+
+    #[error("Unknown PersonaData entry, id: {0}")]
+    UnknownPersonaDataEntry(PersonaDataEntryID) = 10107,
+
+    #[error("Persona already has a name, update it instead")]
+    PersonaDataNameAlreadyPresent = 10108,
+
+    #[error("Derivation index {index} of {entity_kind:?} on {network_id} is already in use by factor source {factor_source_id}")]
+    DerivationIndexAlreadyInUse {
+        index: HDPathValue,
+        entity_kind: EntityKind,
+        network_id: NetworkID,
+        factor_source_id: FactorSourceIDFromHash,
+    } = 10109,
+
+    #[error("FactorSource does not support Babylon, unable to create entities with it, id: {0}")]
+    FactorSourceDoesNotSupportBabylon(FactorSourceID) = 10110,
+
+    #[error("A LedgerHardwareWalletDriver is required to derive keys with Ledger hardware wallet: {0}")]
+    LedgerHardwareWalletDriverRequired(FactorSourceIDFromHash) = 10111,
+
+    #[error(
+        "Ledger hardware wallet {0} did not derive the requested public keys"
+    )]
+    UnexpectedPublicKeysFromLedgerHardwareWallet(FactorSourceIDFromHash) =
+        10112,
+
+    #[error("Password based key derivation parameters exceed the limits, memory cost (KiB): {memory_cost_kib}, iterations: {iterations}, parallelism: {parallelism}")]
+    PasswordBasedKeyDerivationParametersExceedLimits {
+        memory_cost_kib: u32,
+        iterations: u32,
+        parallelism: u32,
+    } = 10113,
+
+    #[error("Invalid range of derivation indices, start index: {start_index}, count: {count}")]
+    InvalidDerivationIndexRange { start_index: u32, count: u32 } = 10114,
+
+    #[error("Account recovery scan gap limit {gap_limit} exceeds the maximum: {max}")]
+    AccountRecoveryScanGapLimitTooLarge { gap_limit: u32, max: u32 } = 10115,
+
+    #[error("No derivation index left for {entity_kind:?} on {network_id} for factor source {factor_source_id}")]
+    DerivationIndicesExhausted {
+        entity_kind: EntityKind,
+        network_id: NetworkID,
+        factor_source_id: FactorSourceIDFromHash,
+    } = 10116,
 }
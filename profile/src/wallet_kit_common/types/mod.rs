@@ -4,6 +4,7 @@ mod entity_kind;
 mod hex_32bytes;
 mod keys;
 mod logged_result;
+mod redactable;
 mod safe_to_log;
 
 pub use bag_of_bytes::*;
@@ -12,4 +13,5 @@ pub use entity_kind::*;
 pub use hex_32bytes::*;
 pub use keys::*;
 pub use logged_result::*;
+pub use redactable::*;
 pub use safe_to_log::*;
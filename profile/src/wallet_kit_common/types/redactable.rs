@@ -0,0 +1,23 @@
+use crate::prelude::*;
+
+/// A trait used to mark that a value contains personal or secret information
+/// which can be redacted, e.g. before sending a Profile in a support ticket.
+///
+/// Just like `SafeToLog`, the redacted value MUST NOT contain sensitive
+/// information. Unlike `SafeToLog` the redacted value is of the same type,
+/// keeping its structure and IDs, replacing sensitive values with
+/// deterministic placeholders, so that redacting the same value twice gives
+/// the same result.
+pub trait Redactable {
+    /// A copy of `self` with all sensitive values replaced by placeholders.
+    fn redacted(&self) -> Self;
+}
+
+impl<T> Redactable for IdentifiedVecVia<T>
+where
+    T: Redactable + Identifiable + std::fmt::Debug + Clone,
+{
+    fn redacted(&self) -> Self {
+        self.iter().map(|e| e.redacted()).collect()
+    }
+}